@@ -0,0 +1,164 @@
+#!/usr/bin/env node
+/**
+ * Native-messaging host for the phevere browser extension
+ *
+ * Chrome and Firefox start this process and exchange messages on stdio, each
+ * a UTF-8 JSON document prefixed with its length as a 32-bit native-endian
+ * integer. Selection messages are relayed as JSON lines to the running app's
+ * browser bridge (src/services/browser-bridge.ts); every message gets a reply
+ * of {ok: true} or {ok: false, error}. The bridge only accepts clients that
+ * first send the token from the app's selection-token file
+ * (src/services/local-auth.ts).
+ *
+ * Register com.phevere.selection.{chrome,firefox}.json (renamed to
+ * com.phevere.selection.json) with the browser, pointing "path" at
+ * this file (or host.bat on Windows, since browsers there launch it directly).
+ */
+
+const { execFileSync } = require('child_process');
+const fs = require('fs');
+const net = require('net');
+const os = require('os');
+const path = require('path');
+
+// Browsers reject host messages larger than 1 MB; replies are tiny anyway
+const MAX_INCOMING_BYTES = 64 * 1024 * 1024;
+
+// Same per-user names as src/services/local-socket.ts
+function windowsUserTag() {
+  try {
+    const output = execFileSync('whoami', ['/user', '/fo', 'csv', '/nh'], { encoding: 'utf8', windowsHide: true });
+    const sid = /"(S-[\d-]+)"/.exec(output);
+    if (sid) {
+      return sid[1];
+    }
+  } catch (error) {
+    // Fall through to the account name
+  }
+  return `${process.env.USERDOMAIN || ''}.${os.userInfo().username}`.replace(/[^\w.-]/g, '_');
+}
+
+function bridgeSocketPath() {
+  if (process.env.PHEVERE_BROWSER_BRIDGE_SOCKET) {
+    return process.env.PHEVERE_BROWSER_BRIDGE_SOCKET;
+  }
+  if (process.platform === 'win32') {
+    return `\\\\.\\pipe\\phevere-browser-bridge-${windowsUserTag()}`;
+  }
+  const runtimeDir = process.env.XDG_RUNTIME_DIR;
+  if (runtimeDir && fs.existsSync(runtimeDir)) {
+    return path.join(runtimeDir, 'phevere-browser-bridge.sock');
+  }
+  return path.join(os.tmpdir(), `phevere-browser-bridge-${os.userInfo().uid}.sock`);
+}
+
+// The app's userData directory, as Electron resolves it for "phevere"
+function tokenFilePath() {
+  if (process.env.PHEVERE_SELECTION_TOKEN_FILE) {
+    return process.env.PHEVERE_SELECTION_TOKEN_FILE;
+  }
+  const home = os.homedir();
+  let userData;
+  if (process.platform === 'win32') {
+    userData = path.join(process.env.APPDATA || path.join(home, 'AppData', 'Roaming'), 'phevere');
+  } else if (process.platform === 'darwin') {
+    userData = path.join(home, 'Library', 'Application Support', 'phevere');
+  } else {
+    userData = path.join(process.env.XDG_CONFIG_HOME || path.join(home, '.config'), 'phevere');
+  }
+  return path.join(userData, 'selection-token');
+}
+
+function readToken() {
+  try {
+    return fs.readFileSync(tokenFilePath(), 'utf8').trim();
+  } catch (error) {
+    return null;
+  }
+}
+
+function reply(message) {
+  const body = Buffer.from(JSON.stringify(message), 'utf8');
+  const header = Buffer.alloc(4);
+  header.writeUInt32LE(body.length, 0);
+  process.stdout.write(Buffer.concat([header, body]));
+}
+
+let bridge = null;
+let socketPath = null;
+
+function connectBridge() {
+  if (bridge && !bridge.destroyed) {
+    return bridge;
+  }
+  if (!socketPath) {
+    socketPath = bridgeSocketPath();
+  }
+  bridge = net.createConnection(socketPath);
+  bridge.on('error', () => {
+    // Reported per message below; the app may simply not be running
+    bridge = null;
+  });
+  // Read on every connect: the app creates the file the first time it listens
+  const token = readToken();
+  if (token) {
+    bridge.write(JSON.stringify({ type: 'auth', token }) + '\n');
+  }
+  return bridge;
+}
+
+function relay(message) {
+  if (!message || message.type !== 'selection' || typeof message.text !== 'string') {
+    reply({ ok: false, error: 'expected {type: "selection", text}' });
+    return;
+  }
+  const socket = connectBridge();
+  const line = JSON.stringify(message) + '\n';
+  const send = () => socket.write(line, (error) => reply(error ? { ok: false, error: error.message } : { ok: true }));
+  if (socket.connecting) {
+    const onError = (error) => reply({ ok: false, error: `phevere is not running (${error.code || error.message})` });
+    socket.once('error', onError);
+    socket.once('connect', () => {
+      socket.off('error', onError);
+      send();
+    });
+  } else {
+    send();
+  }
+}
+
+let pending = Buffer.alloc(0);
+process.stdin.on('data', (chunk) => {
+  pending = Buffer.concat([pending, chunk]);
+  while (pending.length >= 4) {
+    const length = pending.readUInt32LE(0);
+    if (length > MAX_INCOMING_BYTES) {
+      process.exit(1);
+    }
+    if (pending.length < 4 + length) {
+      break;
+    }
+    const body = pending.subarray(4, 4 + length).toString('utf8');
+    pending = pending.subarray(4 + length);
+    let message;
+    try {
+      message = JSON.parse(body);
+    } catch (error) {
+      reply({ ok: false, error: 'invalid JSON' });
+      continue;
+    }
+    relay(message);
+  }
+});
+
+// The browser closes stdin when the extension disconnects. Exit once queued
+// lines have been flushed to the app, not before
+process.stdin.on('end', () => {
+  if (!bridge || bridge.destroyed) {
+    process.exit(0);
+  }
+  const exit = () => process.exit(0);
+  bridge.once('finish', exit);
+  bridge.once('close', exit);
+  bridge.end();
+});
@@ -0,0 +1,137 @@
+#!/usr/bin/env node
+/**
+ * phevere-watch - print selections seen by the native monitor
+ *
+ * Verifies that selection capture works on this desktop without starting the
+ * app. The addon uses N-API, so the same build loads in plain Node.
+ *
+ *   phevere-watch [--format plain|json|jsonl] [--debounce <ms>]
+ *                 [--include <app.exe>]... [--exclude <app.exe>]...
+ *                 [--backend ui-automation|polling] [--poll-interval <ms>]
+ */
+
+// Addon diagnostics go to stderr so stdout only carries selections
+console.log = console.error;
+
+const NativeSelectionMonitor = require('..');
+
+const USAGE = 'Usage: phevere-watch [--format plain|json|jsonl] [--debounce <ms>] ' +
+  '[--include <app>]... [--exclude <app>]... [--backend ui-automation|polling] [--poll-interval <ms>]';
+
+function parseArgs(argv) {
+  const options = {
+    format: 'plain',
+    debounceMs: 500,
+    includeApps: [],
+    excludeApps: [],
+    backend: 'ui-automation',
+    pollIntervalMs: 500,
+  };
+
+  for (let i = 0; i < argv.length; i++) {
+    const flag = argv[i];
+    const value = argv[i + 1];
+    const needsValue = () => {
+      if (value === undefined) {
+        throw new Error(`${flag} needs a value`);
+      }
+      i++;
+      return value;
+    };
+
+    switch (flag) {
+      case '--format':
+        options.format = needsValue();
+        if (!['plain', 'json', 'jsonl'].includes(options.format)) {
+          throw new Error(`Unknown format: ${options.format}`);
+        }
+        break;
+      case '--debounce':
+        options.debounceMs = Number(needsValue());
+        break;
+      case '--include':
+        options.includeApps.push(needsValue());
+        break;
+      case '--exclude':
+        options.excludeApps.push(needsValue());
+        break;
+      case '--backend':
+        options.backend = needsValue();
+        if (!['ui-automation', 'polling'].includes(options.backend)) {
+          throw new Error(`Unknown backend: ${options.backend}`);
+        }
+        break;
+      case '--poll-interval':
+        options.pollIntervalMs = Number(needsValue());
+        break;
+      case '--help':
+      case '-h':
+        process.stdout.write(USAGE + '\n');
+        process.exit(0);
+        break;
+      default:
+        throw new Error(`Unknown option: ${flag}`);
+    }
+  }
+  return options;
+}
+
+function formatSelection(payload, format) {
+  const event = { ...payload, timestamp: Date.now() };
+  switch (format) {
+    case 'json':
+      return JSON.stringify(event, null, 2);
+    case 'jsonl':
+      return JSON.stringify(event);
+    default:
+      return `[${new Date(event.timestamp).toISOString()}] ${event.app || '?'}: ${event.text}`;
+  }
+}
+
+function main() {
+  let options;
+  try {
+    options = parseArgs(process.argv.slice(2));
+  } catch (error) {
+    process.stderr.write(`${error.message}\n${USAGE}\n`);
+    process.exit(2);
+  }
+
+  const monitor = new NativeSelectionMonitor();
+  monitor.configure({
+    debounceMs: options.debounceMs,
+    includeApps: options.includeApps,
+    excludeApps: options.excludeApps,
+    pollIntervalMs: options.backend === 'polling' ? options.pollIntervalMs : 0,
+  });
+  monitor.onSelection((payload) => {
+    if (payload && payload.text) {
+      process.stdout.write(formatSelection(payload, options.format) + '\n');
+    }
+  });
+  monitor.onStateChange((state) => {
+    if (state === 'failed') {
+      process.stderr.write(`Selection monitor failed: ${monitor.getInitError()}\n`);
+      process.exit(1);
+    }
+    if (state === 'ready') {
+      process.stderr.write(`Watching selections (${monitor.getCaptureMethod()}), Ctrl+C to stop\n`);
+    }
+  });
+
+  if (!monitor.start()) {
+    process.stderr.write('Failed to start the selection monitor\n');
+    process.exit(1);
+  }
+
+  const shutdown = () => {
+    monitor.stop();
+    process.exit(0);
+  };
+  process.on('SIGINT', shutdown);
+  process.on('SIGTERM', shutdown);
+  // Thread-safe callbacks don't keep Node alive on their own
+  setInterval(() => {}, 1 << 30);
+}
+
+main();
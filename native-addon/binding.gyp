@@ -22,7 +22,9 @@
         "-luser32.lib",
         "-lkernel32.lib",
         "-lole32.lib",
+        "-lshell32.lib",
         "-loleaut32.lib",
+        "-loleacc.lib",
         "-luuid.lib",
         "-lUIAutomationCore.lib"
       ],
@@ -0,0 +1,151 @@
+/**
+ * Type declarations for the UI Automation selection monitor (index.js).
+ *
+ * Kept by hand next to the addon; when a field is added to SelectionInfo or
+ * CaretInfo in src/selection_monitor.cpp (ToJsObject / the caret callback) or
+ * an option to configure(), add it here as well. The app's service imports
+ * these types, so a mismatch shows up when it is compiled.
+ */
+
+declare namespace NativeSelectionMonitor {
+  /** Physical screen pixels */
+  interface PhysicalRect {
+    x: number;
+    y: number;
+    width: number;
+    height: number;
+  }
+
+  type Granularity = 'word' | 'paragraph' | 'drag' | 'keyboard' | 'unknown';
+
+  /** One settled selection as read from the focused element */
+  interface SelectionPayload {
+    text: string;
+    /** Anchor point (top-left of the selection), physical pixels */
+    x: number;
+    y: number;
+    bounds: PhysicalRect | null;
+    /** Executable name of the source process, e.g. "notepad.exe" */
+    app: string;
+    /** Read on demand via requestCapture() rather than from a UIA event */
+    requested?: boolean;
+    granularity?: Granularity;
+    /** Each discontiguous range; text is these joined with '\n' */
+    ranges?: string[];
+    contextBefore?: string;
+    contextAfter?: string;
+    /** A range was longer than maxReadLength and was cut */
+    truncated?: boolean;
+    /** File-system paths when the selection is files in Explorer */
+    files?: string[];
+    /** A drag over content with no readable text; bounds is the dragged area */
+    unreadable?: boolean;
+    /** Source element identity, with configure({ exposeElementHandles: true }) */
+    element?: ElementHandle;
+    /** Read while an IME composition was active, with configure({ imeComposition: 'flag' }) or on request */
+    composing?: boolean;
+    /** Paragraph direction the app lays the selection out in, when it reports one */
+    direction?: 'ltr' | 'rtl';
+    /** Formatting runs, with configure({ captureRichText: true }); ranges are separated by a '\n' run */
+    runs?: RichRun[];
+  }
+
+  interface RichRun {
+    text: string;
+    bold?: boolean;
+    italic?: boolean;
+    underline?: boolean;
+    link?: string;
+  }
+
+  interface ElementHandle {
+    /** HWND as a decimal string (pointer-sized) */
+    hwnd: string;
+    /** UI Automation runtime ID */
+    runtimeId: number[];
+  }
+
+  /** Text around a screen point; the point sits between before and after */
+  interface TextAtPointPayload {
+    before: string;
+    after: string;
+    app: string;
+  }
+
+  interface CaretPayload extends PhysicalRect {
+    app: string;
+  }
+
+  type MonitorState =
+    | 'ready'
+    | 'failed'
+    | 'suppressed-secure-desktop'
+    | 'active'
+    | 'selection-cleared'
+    | 'backend-exited';
+
+  type CaptureMethod = 'ui-automation' | 'ui-automation-polling' | 'ui-automation-hotkey' | 'winevent';
+
+  interface MonitorOptions {
+    debounceMs?: number;
+    triggers?: 'mouse-up' | 'key-up' | 'both';
+    captureMode?: 'always' | 'hotkey';
+    mouseHook?: boolean;
+    contextChars?: number;
+    trackCaret?: boolean;
+    exposeElementHandles?: boolean;
+    captureRichText?: boolean;
+    reportUnreadableDrags?: boolean;
+    /** 'suppress' (default) drops selections read mid-composition; 'flag' delivers them with composing: true */
+    imeComposition?: 'suppress' | 'flag';
+    callTimeoutMs?: number;
+    stopTimeoutMs?: number;
+    maxReadLength?: number;
+    pollIntervalMs?: number;
+    /** 'winevent': SetWinEventHook notifications instead of UIA event handlers */
+    eventSource?: 'uia' | 'winevent';
+    includeApps?: string[];
+    excludeApps?: string[];
+  }
+
+  interface MonitorStatus {
+    isRunning: boolean;
+    platform: NodeJS.Platform;
+    method: 'uiautomation';
+    callbacksCount: number;
+  }
+}
+
+declare class NativeSelectionMonitor {
+  isRunning: boolean;
+
+  start(): boolean;
+  startAsync(readyTimeoutMs?: number): Promise<boolean>;
+  /** False if a native thread did not exit within stopTimeoutMs; create a new monitor then */
+  stop(): boolean;
+  stopAsync(): Promise<boolean>;
+  configure(options: NativeSelectionMonitor.MonitorOptions): void;
+  getInitError(): string;
+  isSessionReady(): boolean;
+  requestCapture(): boolean;
+  getCaptureMethod(): NativeSelectionMonitor.CaptureMethod;
+  pause(): void;
+  resume(): void;
+  getCurrentSelection(): string | null;
+  /** Null when nothing is selected; throws when the native read fails */
+  getSelectionDetails(): NativeSelectionMonitor.SelectionPayload | null;
+  getSelectionAsync(timeoutMs?: number): Promise<NativeSelectionMonitor.SelectionPayload | null>;
+  getTextAtPointAsync(
+    x: number,
+    y: number,
+    chars?: number,
+    timeoutMs?: number
+  ): Promise<NativeSelectionMonitor.TextAtPointPayload | null>;
+  onSelection(callback: (payload: NativeSelectionMonitor.SelectionPayload) => void): void;
+  onStateChange(callback: (state: NativeSelectionMonitor.MonitorState) => void): void;
+  onError(callback: (message: string) => void): void;
+  onCaret(callback: (caret: NativeSelectionMonitor.CaretPayload) => void): void;
+  getStatus(): NativeSelectionMonitor.MonitorStatus;
+}
+
+export = NativeSelectionMonitor;
@@ -5,6 +5,9 @@ class NativeSelectionMonitor {
     this.monitor = new UIAutomationSelectionMonitor();
     this.isRunning = false;
     this.callbacks = [];
+    this.stateCallbacks = [];
+    this.errorCallbacks = [];
+    this.caretCallbacks = [];
   }
 
   /**
@@ -17,11 +20,7 @@ class NativeSelectionMonitor {
     }
 
     try {
-      // Set up the callback
-      this.monitor.setCallback((text, x, y) => {
-        console.log(`[UIA-ADDON] Selection detected: "${text}" @ (${x}, ${y})`);
-        this.notifyCallbacks({ text, x, y });
-      });
+      this.installCallbacks();
 
       // Start monitoring
       const result = this.monitor.start();
@@ -39,21 +38,203 @@ class NativeSelectionMonitor {
     }
   }
 
+  /**
+   * Start monitoring without blocking the calling thread; resolves once the
+   * native monitor thread is ready and rejects with its initialization error.
+   * Falls back to start() on native builds without async support.
+   * @param {number} [readyTimeoutMs]
+   * @returns {Promise<boolean>}
+   */
+  async startAsync(readyTimeoutMs) {
+    if (typeof this.monitor.startAsync !== 'function') {
+      return this.start();
+    }
+    if (this.isRunning) {
+      console.log('[UIA-ADDON] Already running');
+      return false;
+    }
+
+    this.installCallbacks();
+    await this.monitor.startAsync(readyTimeoutMs);
+    this.isRunning = true;
+    console.log('[UIA-ADDON] UIA selection monitoring started');
+    return true;
+  }
+
+  /**
+   * Route native callbacks to the registered JS listeners
+   */
+  installCallbacks() {
+    // Set up the callback
+    this.monitor.setCallback((payload) => {
+      console.log(`[UIA-ADDON] Selection detected: ${(payload.text || '').length} chars from ${payload.app || 'unknown app'} @ (${payload.x}, ${payload.y})`);
+      this.notifyCallbacks(payload);
+    });
+
+    if (typeof this.monitor.setStateCallback === 'function') {
+      this.monitor.setStateCallback((state) => {
+        console.log(`[UIA-ADDON] State changed: ${state}`);
+        this.stateCallbacks.forEach(callback => {
+          try {
+            callback(state);
+          } catch (error) {
+            console.error('[UIA-ADDON] Error in state callback:', error);
+          }
+        });
+      });
+    }
+
+    if (typeof this.monitor.setErrorCallback === 'function') {
+      this.monitor.setErrorCallback((message) => {
+        console.error(`[UIA-ADDON] Native error: ${message}`);
+        this.errorCallbacks.forEach(callback => {
+          try {
+            callback(message);
+          } catch (error) {
+            console.error('[UIA-ADDON] Error in error callback:', error);
+          }
+        });
+      });
+    }
+
+    if (typeof this.monitor.setCaretCallback === 'function') {
+      this.monitor.setCaretCallback((caret) => {
+        this.caretCallbacks.forEach(callback => {
+          try {
+            callback(caret);
+          } catch (error) {
+            console.error('[UIA-ADDON] Error in caret callback:', error);
+          }
+        });
+      });
+    }
+  }
+
   /**
    * Stop monitoring for text selections
+   * @returns {boolean} false if a native thread did not exit within stopTimeoutMs;
+   *   the monitor is then unusable and a new instance must be created
    */
   stop() {
     if (!this.isRunning) {
-      return;
+      return true;
     }
 
     try {
-      this.monitor.stop();
+      const stopped = this.monitor.stop();
       this.isRunning = false;
+      if (stopped === false) {
+        console.error('[UIA-ADDON] UIA selection monitoring did not stop in time');
+        return false;
+      }
       console.log('[UIA-ADDON] UIA selection monitoring stopped');
     } catch (error) {
       console.error('[UIA-ADDON] Error stopping UIA monitoring:', error);
     }
+    return true;
+  }
+
+  /**
+   * stop() on a worker thread, so joining the native threads never blocks the caller
+   * @returns {Promise<boolean>} see stop()
+   */
+  async stopAsync() {
+    if (typeof this.monitor.stopAsync !== 'function') {
+      return this.stop();
+    }
+    if (!this.isRunning) {
+      return true;
+    }
+
+    // Still running if the native stop throws, so the caller can retry
+    const stopped = await this.monitor.stopAsync();
+    this.isRunning = false;
+    if (stopped === false) {
+      console.error('[UIA-ADDON] UIA selection monitoring did not stop in time');
+      return false;
+    }
+    console.log('[UIA-ADDON] UIA selection monitoring stopped');
+    return true;
+  }
+
+  /**
+   * Configure debounce delay, selection triggers, capture mode, polling fallback and app filters
+   * @param {{ debounceMs?: number, triggers?: 'mouse-up' | 'key-up' | 'both', captureMode?: 'always' | 'hotkey', mouseHook?: boolean, contextChars?: number, trackCaret?: boolean, exposeElementHandles?: boolean, captureRichText?: boolean, reportUnreadableDrags?: boolean, imeComposition?: 'suppress' | 'flag', callTimeoutMs?: number, stopTimeoutMs?: number, maxReadLength?: number, pollIntervalMs?: number, eventSource?: 'uia' | 'winevent', includeApps?: string[], excludeApps?: string[] }} options
+   */
+  configure(options) {
+    if (typeof this.monitor.configure !== 'function') {
+      console.warn('[UIA-ADDON] configure() not supported by this native build');
+      return;
+    }
+
+    try {
+      this.monitor.configure(options || {});
+    } catch (error) {
+      console.error('[UIA-ADDON] Error configuring UIA monitoring:', error);
+    }
+  }
+
+  /**
+   * Why the monitor thread failed to initialize ('' if it did not fail)
+   */
+  getInitError() {
+    if (typeof this.monitor.getInitError !== 'function') {
+      return '';
+    }
+    return this.monitor.getInitError();
+  }
+
+  /**
+   * Whether the interactive desktop is usable: the input desktop is the default
+   * one and the shell (taskbar) is running
+   */
+  isSessionReady() {
+    if (typeof this.monitor.isSessionReady !== 'function') {
+      return true;
+    }
+    return this.monitor.isSessionReady();
+  }
+
+  /**
+   * Read the focused element's selection now; the result arrives through the
+   * selection callback with `requested: true`
+   * @returns {boolean} Whether the request was queued
+   */
+  requestCapture() {
+    if (typeof this.monitor.requestCapture !== 'function') {
+      return false;
+    }
+    return this.monitor.requestCapture();
+  }
+
+  /**
+   * 'ui-automation' when driven by UIA events, 'ui-automation-polling' when the
+   * low-fidelity polling fallback is active, 'winevent' when driven by WinEvent
+   * hooks, 'ui-automation-hotkey' in hotkey mode
+   */
+  getCaptureMethod() {
+    if (typeof this.monitor.getCaptureMethod !== 'function') {
+      return 'ui-automation';
+    }
+    return this.monitor.getCaptureMethod();
+  }
+
+  /**
+   * Temporarily ignore selection events without unregistering UIA handlers
+   */
+  pause() {
+    if (typeof this.monitor.setPaused === 'function') {
+      this.monitor.setPaused(true);
+    }
+  }
+
+  /**
+   * Resume delivering selection events after pause()
+   */
+  resume() {
+    if (typeof this.monitor.setPaused === 'function') {
+      this.monitor.setPaused(false);
+    }
   }
 
   /**
@@ -68,6 +249,43 @@ class NativeSelectionMonitor {
     }
   }
 
+  /**
+   * Get the last settled selection with its position and bounding rectangle
+   */
+  getSelectionDetails() {
+    // Native failures propagate so callers can tell them apart from "nothing selected"
+    return this.monitor.getSelectionDetails();
+  }
+
+  /**
+   * Read the focused element's selection now, on the native monitor thread,
+   * without delivering it to onSelection listeners. Resolves to the same shape
+   * as getSelectionDetails(), or null when nothing is selected.
+   * @param {number} [timeoutMs]
+   */
+  async getSelectionAsync(timeoutMs) {
+    if (typeof this.monitor.getSelectionAsync !== 'function') {
+      return this.getSelectionDetails();
+    }
+    return this.monitor.getSelectionAsync(timeoutMs);
+  }
+
+  /**
+   * Text on either side of a screen point (physical pixels) in the element
+   * under it, as { before, after, app }, or null when there is no readable text.
+   * Word lookups segment before + after at before.length.
+   * @param {number} x
+   * @param {number} y
+   * @param {number} [chars] Characters read on each side (default 100)
+   * @param {number} [timeoutMs]
+   */
+  async getTextAtPointAsync(x, y, chars, timeoutMs) {
+    if (typeof this.monitor.getTextAtPointAsync !== 'function') {
+      return null;
+    }
+    return this.monitor.getTextAtPointAsync(x, y, chars, timeoutMs);
+  }
+
   /**
    * Register a callback for selection events
    */
@@ -77,6 +295,38 @@ class NativeSelectionMonitor {
     }
   }
 
+  /**
+   * Register a callback for monitor state changes: 'ready' / 'failed' after start(),
+   * 'suppressed-secure-desktop' / 'active' / 'selection-cleared' while running, and
+   * 'backend-exited' if a native thread dies without stop()
+   */
+  onStateChange(callback) {
+    if (typeof callback === 'function') {
+      this.stateCallbacks.push(callback);
+    }
+  }
+
+  /**
+   * Register a callback for failures on the native threads (handler registration,
+   * hooks, exceptions) that would otherwise only reach stderr
+   */
+  onError(callback) {
+    if (typeof callback === 'function') {
+      this.errorCallbacks.push(callback);
+    }
+  }
+
+  /**
+   * Register a callback for caret moves while nothing is selected (needs
+   * configure({ trackCaret: true })); receives { x, y, width, height, app } in
+   * physical pixels
+   */
+  onCaret(callback) {
+    if (typeof callback === 'function') {
+      this.caretCallbacks.push(callback);
+    }
+  }
+
   /**
    * Notify all registered callbacks
    */
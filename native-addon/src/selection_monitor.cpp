@@ -1,6 +1,12 @@
 #include <napi.h>
 #include <windows.h>
 #include <UIAutomation.h>
+#include <richedit.h>
+#include <oleacc.h>
+#include <exdisp.h>
+#include <shlobj.h>
+#include <shlguid.h>
+#include <shobjidl.h>
 #include <atlbase.h>
 #include <string>
 #include <thread>
@@ -9,42 +15,276 @@
 #include <iostream>
 #include <chrono>
 #include <mutex>
+#include <future>
+#include <memory>
+#include <vector>
+#include <algorithm>
+#include <cctype>
+#include <cwctype>
+#include <cstdlib>
+#include <cstdint>
+#include <sstream>
 
 // Forward declaration of the handler class
 class UIAutomationEventHandler;
 
+// A stretch of selected text with uniform formatting
+struct RichRun {
+    std::string text;
+    bool bold = false;
+    bool italic = false;
+    bool underline = false;
+    std::string link; // target of an enclosing hyperlink, if any
+};
+
+// Everything reported to JS for a single settled selection
+struct SelectionInfo {
+    std::string text;
+    int x = 0; // anchor point (top-left of the selection), physical screen pixels
+    int y = 0;
+    bool hasBounds = false; // bounding rectangle of all selected text, physical screen pixels
+    double left = 0, top = 0, width = 0, height = 0;
+    std::string app; // executable name of the source process, e.g. "notepad.exe"
+    bool requested = false; // read on demand (capture hotkey) rather than from an event
+    std::string granularity = "unknown"; // "word", "paragraph", "drag", "keyboard" or "unknown"
+    std::vector<std::string> ranges; // each discontiguous range; text is these joined with '\n'
+    std::string contextBefore; // text preceding the selection in the same element
+    std::string contextAfter;  // text following the selection in the same element
+    bool truncated = false;    // a range was longer than the read limit and was cut
+    std::vector<std::string> files; // file-system paths when the selection is files in Explorer
+    bool unreadable = false;   // a drag over content with no readable text; bounds is the dragged area (OCR candidate)
+    // Identity of the source element, only filled when handles are exposed
+    HWND hwnd = NULL;          // nearest native window of the element
+    std::vector<int> runtimeId; // UIA runtime ID; find the element again with a PropertyCondition on it
+    std::vector<RichRun> runs; // formatting of the selected text, only read when rich capture is on
+    bool composing = false;    // an IME composition was active in the element when it was read
+    std::string direction;     // "rtl" or "ltr" flow direction of the first range, "" when not reported
+};
+
+// Upper bound on formatting runs read per selection; heavily formatted documents
+// would otherwise cost one cross-process call per run
+static const int MAX_RICH_RUNS = 256;
+
+// Insertion point reported in caret tracking mode, physical screen pixels
+struct CaretInfo {
+    RECT rect = {0, 0, 0, 0};
+    std::string app;
+};
+
+// Separator used to join discontiguous selection ranges into one text
+static const char* RANGE_SEPARATOR = "\n";
+
+// Mouse gestures recorded by the low-level hook, used to label a selection's granularity
+enum MouseGesture { GESTURE_NONE = 0, GESTURE_DRAG, GESTURE_DOUBLE_CLICK, GESTURE_TRIPLE_CLICK };
+// A selection event this soon after a mouse gesture is attributed to it
+static const ULONGLONG GESTURE_ATTRIBUTION_MS = 1000;
+
+// Posted to the monitor thread to read the focused selection on demand
+static const UINT WM_APP_CAPTURE = WM_APP + 1;
+// Posted by the low-level mouse hook when the left button is released after a drag
+// or a double/triple click
+static const UINT WM_APP_MOUSE_SELECTION = WM_APP + 2;
+// Posted by getSelectionAsync() and getTextAtPointAsync(): read the focused
+// selection (or the text around a point) and hand it back through the
+// ReadRequest in lParam without delivering it
+static const UINT WM_APP_READ = WM_APP + 3;
+
+struct ReadRequest {
+    std::promise<SelectionInfo> result;
+    bool atPoint = false; // read the text around point into contextBefore/contextAfter
+    POINT point = {0, 0};
+    int chars = 0;
+};
+
+static std::string describeFailure(const char* what, HRESULT hr) {
+    std::ostringstream message;
+    message << what << " (HRESULT 0x" << std::hex << (unsigned long)hr << ")";
+    return message.str();
+}
+
+// Window messages sent to other processes give up after this long (hung or busy UI thread)
+static const UINT LEGACY_MESSAGE_TIMEOUT_MS = 100;
+
+// Memory allocated inside another process, so window messages that take pointers
+// (EM_GETSEL, EM_GETSELTEXT) can be answered across the process boundary
+class RemoteBuffer {
+public:
+    RemoteBuffer(DWORD pid, SIZE_T size) {
+        process = OpenProcess(PROCESS_VM_OPERATION | PROCESS_VM_READ | PROCESS_VM_WRITE, FALSE, pid);
+        if (process) address = VirtualAllocEx(process, NULL, size, MEM_COMMIT | MEM_RESERVE, PAGE_READWRITE);
+    }
+    ~RemoteBuffer() {
+        if (address) VirtualFreeEx(process, address, 0, MEM_RELEASE);
+        if (process) CloseHandle(process);
+    }
+    RemoteBuffer(const RemoteBuffer&) = delete;
+    RemoteBuffer& operator=(const RemoteBuffer&) = delete;
+
+    bool valid() const { return address != nullptr; }
+    void* get() const { return address; }
+    bool read(void* out, SIZE_T bytes) const {
+        SIZE_T copied = 0;
+        return ReadProcessMemory(process, address, out, bytes, &copied) && copied == bytes;
+    }
+
+private:
+    HANDLE process = nullptr;
+    void* address = nullptr;
+};
+
+static bool sendWithTimeout(HWND hwnd, UINT msg, WPARAM wParam, LPARAM lParam, DWORD_PTR* result) {
+    return SendMessageTimeoutW(hwnd, msg, wParam, lParam, SMTO_ABORTIFHUNG | SMTO_BLOCK,
+                               LEGACY_MESSAGE_TIMEOUT_MS, result) != 0;
+}
+
+// Convert a UTF-16 buffer to UTF-8
+static std::string toUtf8(const wchar_t* data, int length) {
+    if (!data || length <= 0) return "";
+    int size_needed = WideCharToMultiByte(CP_UTF8, 0, data, length, NULL, 0, NULL, NULL);
+    std::string result(size_needed, 0);
+    WideCharToMultiByte(CP_UTF8, 0, data, length, &result[0], size_needed, NULL, NULL);
+    return result;
+}
+
+// Lower-case an app name and drop a trailing ".exe" so "KeePass" matches "keepass.exe"
+static std::string normalizeAppName(std::string name) {
+    std::transform(name.begin(), name.end(), name.begin(), [](unsigned char c) { return (char)std::tolower(c); });
+    if (name.size() > 4 && name.compare(name.size() - 4, 4, ".exe") == 0) {
+        name.resize(name.size() - 4);
+    }
+    return name;
+}
+
 class UIAutomationSelectionMonitor {
 private:
     std::atomic<bool> running{false};
     std::thread monitor_thread;
-    DWORD monitor_thread_id = 0; // Store thread ID to post messages to it
-    std::function<void(std::string, int, int)> callback;
-    std::string last_selection;
+    std::atomic<DWORD> monitor_thread_id{0}; // Store thread ID to post messages to it
+    std::atomic<bool> monitor_exited{false}; // Set when monitorLoop returns, on any path
+    std::atomic<bool> reached_ready{false};  // The monitor thread initialized and emitted "ready"
+    // stop() gives the worker threads this long to exit before abandoning them
+    std::atomic<int> stop_timeout_ms{5000};
+    std::atomic<bool> stop_timed_out{false}; // A worker was abandoned; this object must not be freed
+    // Written from the JS thread, read from the debounce thread
+    std::mutex callback_mutex;
+    std::function<void(const SelectionInfo&)> callback;
+    std::function<void(const std::string&)> state_callback;
+    std::function<void(const std::string&)> error_callback;
+    std::function<void(const CaretInfo&)> caret_callback;
+    SelectionInfo last_selection; // guarded by debounce_mutex
+
+    // True while capture is suppressed because the secure desktop or another
+    // user's session has the foreground
+    std::atomic<bool> suppressed_secure{false};
+
+    // Why the monitor thread failed to initialize; reported with the "failed" state
+    std::mutex init_error_mutex;
+    std::string init_error;
+
+    // While paused, events are ignored before any text is read; handlers stay registered
+    std::atomic<bool> paused{false};
+
+    // Polling backend: read the focused control's selection on a timer instead of
+    // relying on UIA events; no event handlers are registered. 0 = events only
+    // (polling still kicks in if no handler can be registered). Applied on the next start().
+    static constexpr int DEFAULT_POLL_INTERVAL_MS = 500;
+    std::atomic<int> poll_interval_ms{0};
+    std::atomic<bool> polling_active{false};
+    // Selection changes come from a SetWinEventHook instead of UIA event handlers:
+    // requested with configure({ eventSource: 'winevent' }), or used automatically
+    // when no UIA handler can be registered
+    std::atomic<bool> use_win_events{false};
+    std::atomic<bool> win_events_active{false};
+    std::string last_polled_text; // monitor thread only
+
+    // A selection was reported and has not been cleared since; an empty read
+    // then emits the "selection-cleared" state once
+    std::atomic<bool> selection_reported{false};
+
+    // Caret tracking: report the insertion point when nothing is selected
+    std::atomic<bool> track_caret{false};
+
+    // Attach the source element's window handle and runtime ID to each selection
+    std::atomic<bool> expose_handles{false};
+
+    // Read formatting runs (bold/italic/underline/links) from the text pattern
+    std::atomic<bool> capture_rich{false};
+
+    // Report drags that produced no readable text so JS can fall back to OCR
+    std::atomic<bool> report_unreadable{false};
+
+    // Drop event-driven selections read while an IME composition is active
+    // (half-composed text); when false they are delivered flagged as composing
+    std::atomic<bool> suppress_composition{true};
+
+    // UIA connection/transaction timeout for calls into other processes; a busy
+    // or hung app fails the call with UIA_E_TIMEOUT instead of stalling the thread.
+    // Applied on the next start().
+    std::atomic<int> call_timeout_ms{2000};
+    std::atomic<ULONGLONG> last_timeout_report{0};
+    RECT last_caret = {0, 0, 0, 0}; // monitor thread only
+
+    // Hotkey capture mode: no UIA event handlers or polling; the selection is only
+    // read when request_capture() is called. Applied on the next start().
+    std::atomic<bool> hotkey_only{false};
+
+    // Characters of surrounding text read on each side of the selection (0 = off)
+    std::atomic<int> context_chars{0};
+
+    // Maximum UTF-16 units read per range; longer ranges are cut and flagged (0 = no limit)
+    std::atomic<int> max_read_length{0};
+
+    // Low-level mouse hook for drag-to-select in apps whose providers raise no UIA
+    // selection events. Applied on the next start().
+    std::atomic<bool> mouse_hook_enabled{true};
+    HHOOK mouse_hook = NULL;  // monitor thread only
+    POINT drag_start = {0, 0}; // monitor thread only (hooks run on the installing thread)
+    POINT drag_end = {0, 0};
+    bool left_button_down = false;
+    int click_count = 0;
+    DWORD last_click_time = 0;
+    POINT last_click_pos = {0, 0};
+    // Read from UIA event threads as well
+    std::atomic<int> last_gesture{GESTURE_NONE};
+    std::atomic<ULONGLONG> last_gesture_tick{0};
     
     // UIA object (will be created and used only on the monitor thread)
     CComPtr<IUIAutomation> pAutomation;
 
-    // Static instance pointer for the event handler to call back into the class
-    static UIAutomationSelectionMonitor* instance;
+    // The low-level mouse hook and the WinEvent hook have no user data; they run on
+    // the monitor thread that installed them, so each monitor finds itself through this
+    static thread_local UIAutomationSelectionMonitor* hook_owner;
 
     // Debouncing mechanism
     std::thread debounce_thread;
     std::atomic<bool> debounce_running{false};
+    std::atomic<bool> debounce_exited{false};
     std::mutex debounce_mutex;
-    std::string pending_selection;
-    int pending_x = 0;
-    int pending_y = 0;
+    SelectionInfo pending_selection;
     std::chrono::steady_clock::time_point last_selection_time;
-    static constexpr int DEBOUNCE_DELAY_MS = 500; // 500ms delay like Youdao Dictionary
-    // Debug flag (enabled via env var PHEVERE_DEBUG_UIA=1)
-    static bool debugEnabled;
+    static constexpr int DEFAULT_DEBOUNCE_DELAY_MS = 500; // 500ms delay like Youdao Dictionary
+    std::atomic<int> debounce_delay_ms{DEFAULT_DEBOUNCE_DELAY_MS};
+
+    // Which input gestures may produce a selection event (see TRIGGER_* flags)
+    std::atomic<int> trigger_mask{TRIGGER_MOUSE | TRIGGER_KEYBOARD};
+
+    // App filters (normalized names); an empty include list allows every app
+    std::mutex filter_mutex;
+    std::vector<std::string> include_apps;
+    std::vector<std::string> exclude_apps;
+
+    // Chromium windows whose renderer accessibility we already switched on
+    std::mutex chromium_mutex;
+    std::vector<HWND> chromium_windows;
+    // Debug flag (enabled via env var PHEVERE_DEBUG_UIA=1); read once when the addon
+    // loads, so monitors on other threads never see it being written
+    static const bool debugEnabled;
 
 public:
+    static constexpr int TRIGGER_MOUSE = 1;
+    static constexpr int TRIGGER_KEYBOARD = 2;
+
     UIAutomationSelectionMonitor() {
-        instance = this;
-        // Enable debug only when explicitly requested
-        const char* dbg = std::getenv("PHEVERE_DEBUG_UIA");
-        debugEnabled = (dbg && std::string(dbg) == "1");
         // COM will be initialized on the dedicated thread, not here.
         if (debugEnabled) std::cout << "[UIA] Constructor called" << std::endl;
     }
@@ -54,58 +294,285 @@ public:
         if (debugEnabled) std::cout << "[UIA] Destructor called" << std::endl;
     }
 
-    void set_callback(std::function<void(std::string, int, int)> cb) {
+    void set_callback(std::function<void(const SelectionInfo&)> cb) {
+        std::lock_guard<std::mutex> lock(callback_mutex);
         callback = cb;
         if (debugEnabled) std::cout << "[UIA] Callback set successfully" << std::endl;
     }
 
+    void set_state_callback(std::function<void(const std::string&)> cb) {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        state_callback = cb;
+    }
+
+    // Runtime failures on the native threads (after start succeeded or in place of a crash)
+    void set_error_callback(std::function<void(const std::string&)> cb) {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        error_callback = cb;
+    }
+
+    void set_caret_callback(std::function<void(const CaretInfo&)> cb) {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        caret_callback = cb;
+    }
+
+    void set_track_caret(bool value) {
+        track_caret.store(value);
+    }
+
+    void set_expose_handles(bool value) {
+        expose_handles.store(value);
+    }
+
+    void set_capture_rich(bool value) {
+        capture_rich.store(value);
+    }
+
+    void set_report_unreadable(bool value) {
+        report_unreadable.store(value);
+    }
+
+    void set_suppress_composition(bool value) {
+        suppress_composition.store(value);
+    }
+
+    void set_call_timeout(int ms) {
+        call_timeout_ms.store(ms < 0 ? 0 : ms);
+    }
+
+    void set_stop_timeout(int ms) {
+        stop_timeout_ms.store(ms < 0 ? 0 : ms);
+    }
+
+    bool abandoned_threads() const {
+        return stop_timed_out.load();
+    }
+
+    void set_paused(bool value) {
+        paused.store(value);
+        if (value) {
+            std::lock_guard<std::mutex> lock(debounce_mutex);
+            pending_selection = SelectionInfo();
+        }
+        if (debugEnabled) std::cout << "[UIA] " << (value ? "Paused" : "Resumed") << std::endl;
+    }
+
+    void set_debounce_delay(int delayMs) {
+        debounce_delay_ms.store(delayMs < 0 ? 0 : delayMs);
+        if (debugEnabled) std::cout << "[UIA] Debounce delay set to " << debounce_delay_ms.load() << "ms" << std::endl;
+    }
+
+    void set_poll_interval(int intervalMs) {
+        poll_interval_ms.store(intervalMs < 0 ? 0 : intervalMs);
+    }
+
+    void set_mouse_hook_enabled(bool value) {
+        mouse_hook_enabled.store(value);
+    }
+
+    void set_max_read_length(int length) {
+        max_read_length.store(length < 0 ? 0 : length);
+    }
+
+    void set_context_chars(int chars) {
+        context_chars.store(chars < 0 ? 0 : chars);
+    }
+
+    void set_hotkey_only(bool value) {
+        hotkey_only.store(value);
+    }
+
+    // Ask the monitor thread to read the focused selection and deliver it right away
+    bool request_capture() {
+        DWORD tid = monitor_thread_id.load();
+        if (!running.load() || tid == 0) return false;
+        return PostThreadMessage(tid, WM_APP_CAPTURE, 0, 0) != 0;
+    }
+
+    // Read the focused selection on the monitor thread and wait for the result.
+    // Called from a worker thread, never the JS thread. Returns false if the
+    // monitor is not running or did not answer within timeoutMs.
+    bool read_selection_now(int timeoutMs, SelectionInfo& info) {
+        return post_read(std::make_shared<ReadRequest>(), timeoutMs, info);
+    }
+
+    // Read up to `chars` characters on each side of a screen point (physical
+    // pixels) on the monitor thread. Same threading rules as read_selection_now.
+    bool read_text_at_point(int x, int y, int chars, int timeoutMs, SelectionInfo& info) {
+        auto request = std::make_shared<ReadRequest>();
+        request->atPoint = true;
+        request->point = POINT{x, y};
+        request->chars = chars;
+        return post_read(request, timeoutMs, info);
+    }
+
+    bool post_read(std::shared_ptr<ReadRequest> request, int timeoutMs, SelectionInfo& info) {
+        DWORD tid = monitor_thread_id.load();
+        if (!running.load() || tid == 0) return false;
+        std::future<SelectionInfo> result = request->result.get_future();
+        // The monitor thread owns (and deletes) this extra reference once posted;
+        // if its loop exits first the request is leaked, which is harmless
+        auto* posted = new std::shared_ptr<ReadRequest>(request);
+        if (!PostThreadMessage(tid, WM_APP_READ, 0, reinterpret_cast<LPARAM>(posted))) {
+            delete posted;
+            return false;
+        }
+        if (result.wait_for(std::chrono::milliseconds(timeoutMs)) != std::future_status::ready) {
+            return false;
+        }
+        info = result.get();
+        return true;
+    }
+
+    // Block until the monitor thread finished initializing (true) or gave up (false)
+    bool wait_until_ready(int timeoutMs) {
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(timeoutMs);
+        while (!reached_ready.load() && !monitor_exited.load() && std::chrono::steady_clock::now() < deadline) {
+            std::this_thread::sleep_for(std::chrono::milliseconds(10));
+        }
+        return reached_ready.load();
+    }
+
+    std::string get_init_error() {
+        std::lock_guard<std::mutex> lock(init_error_mutex);
+        return init_error;
+    }
+
+    // The interactive desktop is usable: default input desktop and a running shell
+    bool is_session_ready() {
+        if (isSecureOrForeignContext(0)) return false;
+        return FindWindowW(L"Shell_TrayWnd", NULL) != NULL;
+    }
+
+    bool is_win_events() const {
+        return win_events_active.load();
+    }
+
+    void set_use_win_events(bool value) {
+        use_win_events.store(value);
+    }
+
+    bool is_polling() const {
+        return polling_active.load();
+    }
+
+    bool is_hotkey_only() const {
+        return hotkey_only.load();
+    }
+
+    void set_trigger_mask(int mask) {
+        trigger_mask.store(mask);
+        if (debugEnabled) std::cout << "[UIA] Trigger mask set to " << mask << std::endl;
+    }
+
+    void set_app_filters(const std::vector<std::string>& include, const std::vector<std::string>& exclude) {
+        std::lock_guard<std::mutex> lock(filter_mutex);
+        include_apps.clear();
+        exclude_apps.clear();
+        for (const auto& name : include) include_apps.push_back(normalizeAppName(name));
+        for (const auto& name : exclude) exclude_apps.push_back(normalizeAppName(name));
+        if (debugEnabled) std::cout << "[UIA] App filters set: " << include_apps.size() << " included, " << exclude_apps.size() << " excluded" << std::endl;
+    }
+
     bool start() {
         if (running.load()) {
             if (debugEnabled) std::cout << "[UIA] Already running" << std::endl;
             return true;
         }
+        {
+            std::lock_guard<std::mutex> lock(init_error_mutex);
+            init_error.clear();
+        }
+        if (stop_timed_out.load()) {
+            // An abandoned thread may still be inside UIA or holding the hooks
+            std::lock_guard<std::mutex> lock(init_error_mutex);
+            init_error = "a previous stop() timed out; create a new monitor";
+            return false;
+        }
 
         if (debugEnabled) std::cout << "[UIA] Starting UIAutomation selection monitoring..." << std::endl;
         running.store(true);
         debounce_running.store(true);
+        debounce_exited.store(false);
+        monitor_thread_id.store(0);
+        monitor_exited.store(false);
+        reached_ready.store(false);
         
         // Start the dedicated monitor thread
-        monitor_thread = std::thread(&UIAutomationSelectionMonitor::monitorLoop, this);
+        monitor_thread = std::thread(&UIAutomationSelectionMonitor::runGuarded, this, "monitor", &UIAutomationSelectionMonitor::monitorLoop);
         
         // Start the debounce thread
-        debounce_thread = std::thread(&UIAutomationSelectionMonitor::debounceLoop, this);
+        debounce_thread = std::thread(&UIAutomationSelectionMonitor::runGuarded, this, "debounce", &UIAutomationSelectionMonitor::debounceLoop);
         
         return true;
     }
 
-    void stop() {
+    // Returns false when a worker thread did not exit within stop_timeout_ms (a hung
+    // UIA call or hook); that thread is detached and left running, and the monitor
+    // cannot be restarted or freed.
+    bool stop() {
         if (!running.exchange(false)) {
-            return; // Already stopped or stopping
+            return !stop_timed_out.load(); // Already stopped or stopping
         }
 
         if (debugEnabled) std::cout << "[UIA] Stopping UIAutomation selection monitoring..." << std::endl;
-        
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(stop_timeout_ms.load());
+        auto waitFor = [&deadline](const std::atomic<bool>& exited) {
+            while (!exited.load() && std::chrono::steady_clock::now() < deadline) {
+                std::this_thread::sleep_for(std::chrono::milliseconds(5));
+            }
+            return exited.load();
+        };
+
         // Stop debounce thread first
         debounce_running.store(false);
-        if (debounce_thread.joinable()) {
-            debounce_thread.join();
+        bool debounceStopped = !debounce_thread.joinable() || waitFor(debounce_exited);
+
+        // The monitor thread publishes its ID once its message queue exists. If stop()
+        // races a thread that is still starting up, wait for that instead of posting
+        // WM_QUIT into the void and then blocking forever in join().
+        while (monitor_thread.joinable() && monitor_thread_id.load() == 0 && !monitor_exited.load() &&
+               std::chrono::steady_clock::now() < deadline) {
+            std::this_thread::sleep_for(std::chrono::milliseconds(5));
         }
-        
+
         // Post a WM_QUIT message to the monitor thread to break its message loop
-        if (monitor_thread_id != 0) {
-            PostThreadMessage(monitor_thread_id, WM_QUIT, 0, 0);
+        if (monitor_thread_id.load() != 0) {
+            PostThreadMessage(monitor_thread_id.load(), WM_QUIT, 0, 0);
         }
-        
-        // Wait for the thread to finish
-        if (monitor_thread.joinable()) {
-            monitor_thread.join();
+        bool monitorStopped = !monitor_thread.joinable() || waitFor(monitor_exited);
+
+        // The exit flags are set as the thread bodies return, so these joins are short
+        for (auto* worker : {&debounce_thread, &monitor_thread}) {
+            if (!worker->joinable()) continue;
+            bool stopped = worker == &debounce_thread ? debounceStopped : monitorStopped;
+            if (stopped) {
+                worker->join();
+            } else {
+                worker->detach();
+            }
         }
-        monitor_thread_id = 0;
-        
+        monitor_thread_id.store(0);
+
+        if (!debounceStopped || !monitorStopped) {
+            stop_timed_out.store(true);
+            std::string which = !monitorStopped ? "monitor" : "debounce";
+            std::cerr << "[UIA] " << which << " thread did not stop within " << stop_timeout_ms.load() << "ms" << std::endl;
+            reportError(which + " thread did not stop within " + std::to_string(stop_timeout_ms.load()) + "ms");
+            return false;
+        }
+
         if (debugEnabled) std::cout << "[UIA] Selection monitoring stopped" << std::endl;
+        return true;
     }
 
     std::string get_current_selection() {
+        std::lock_guard<std::mutex> lock(debounce_mutex);
+        return last_selection.text;
+    }
+
+    SelectionInfo get_selection_details() {
+        std::lock_guard<std::mutex> lock(debounce_mutex);
         return last_selection;
     }
 
@@ -123,23 +590,65 @@ private:
 
     // This method is called by the event handler when an event is received
     void handleSelectionChanged(IUIAutomationElement* sender);
+    void pollFocusedSelection();
+    void captureFocusedSelection();
+    bool readFocusedSelection(SelectionInfo& info, bool allowValueFallback = false);
+    bool readTextAtPoint(POINT pt, int chars, SelectionInfo& info);
+    void enableChromiumAccessibility(IUIAutomationElement* element);
+    static bool readValueFallback(IUIAutomationElement* element, std::string& value);
+    void deliverNow(const SelectionInfo& info);
+    void handleMouseSelection();
+    std::string currentGranularity();
+    static LRESULT CALLBACK lowLevelMouseProc(int nCode, WPARAM wParam, LPARAM lParam);
+    static void CALLBACK winEventProc(HWINEVENTHOOK hook, DWORD event, HWND hwnd, LONG idObject, LONG idChild,
+                                      DWORD eventThread, DWORD eventTime);
+    HWINEVENTHOOK installWinEventHook();
+    bool updateSecureState(DWORD pid);
 
     // Debouncing mechanism
     void debounceLoop();
-    void updatePendingSelection(const std::string& newSelection, int x, int y);
+    void updatePendingSelection(const SelectionInfo& newSelection);
 
+    bool readLegacyEditSelection(IUIAutomationElement* element, SelectionInfo& info);
+    bool readConsoleSelection(IUIAutomationElement* element, SelectionInfo& info);
+    bool readFileSelection(IUIAutomationElement* element, const std::string& processName, SelectionInfo& info);
+    static bool readExplorerSelection(HWND topLevel, std::vector<std::string>& files);
+    void handleItemSelectionChanged(IUIAutomationElement* sender);
     std::string getSelectedTextFromElement(IUIAutomationElement* element);
+    std::vector<std::string> getSelectedRangesFromElement(IUIAutomationElement* element, SelectionInfo* details = nullptr);
+    static std::string readAdjacentText(IUIAutomationTextRange* range, bool before, int chars);
+    static std::string joinRanges(const std::vector<std::string>& ranges);
     std::string getSelectedTextFromFocusedOrPoint();
     CComPtr<IUIAutomationElement> findAncestorWithTextPattern(IUIAutomationElement* start);
-    bool getSelectionCenter(IUIAutomationElement* element, int& outX, int& outY);
+    CComPtr<IUIAutomationElement> findFocusedDescendantWithTextPattern(IUIAutomationElement* start);
+    bool getSelectionBounds(IUIAutomationElement* element, SelectionInfo& info);
     bool isFromCurrentProcess(IUIAutomationElement* element);
+    bool isTriggerEnabled();
+    DWORD getProcessId(IUIAutomationElement* element);
+    std::string getProcessName(DWORD pid);
+    bool isAppAllowed(const std::string& processName);
+    bool isPasswordField(IUIAutomationElement* element);
+    bool isComposing(IUIAutomationElement* element);
+    bool isSecureOrForeignContext(DWORD pid);
+    void emitState(const std::string& state);
+    void reportCleared();
+    void reportCaret(IUIAutomationElement* element, const std::string& app);
+    void describeElement(IUIAutomationElement* element, SelectionInfo& info);
+    static void readRichRuns(IUIAutomationTextRange* range, std::vector<RichRun>& runs);
+    static std::string readFlowDirection(IUIAutomationTextRange* range);
+    static bool readCaretRect(IUIAutomationElement* element, RECT& rect);
+    void reportInitFailure(const std::string& reason);
+    void reportError(const std::string& message);
+    bool checkTimeout(HRESULT hr, const char* what);
+    void runGuarded(const char* threadName, void (UIAutomationSelectionMonitor::*body)());
 
     // Event handler class implementation remains inside the .cpp file
     class UIAutomationEventHandler : public IUIAutomationEventHandler {
     private:
         LONG refCount;
+        UIAutomationSelectionMonitor* owner; // Outlives the handler: removed before the monitor thread exits
     public:
-        UIAutomationEventHandler() : refCount(1) {}
+        explicit UIAutomationEventHandler(UIAutomationSelectionMonitor* owner) : refCount(1), owner(owner) {}
         ~UIAutomationEventHandler() {}
 
         // IUnknown methods
@@ -163,12 +672,31 @@ private:
         HRESULT STDMETHODCALLTYPE HandleAutomationEvent(IUIAutomationElement* sender, EVENTID eventId) override {
             // Treat multiple text-related events as potential selection changes.
             // Many apps/browsers fire different events; we debounce downstream.
-            if (UIAutomationSelectionMonitor::instance && (
+            if (
                 eventId == UIA_Text_TextSelectionChangedEventId ||
                 eventId == UIA_Text_TextChangedEventId ||
                 eventId == UIA_TextEdit_TextChangedEventId
-            )) {
-                UIAutomationSelectionMonitor::instance->handleSelectionChanged(sender);
+            ) {
+                // Exceptions must not cross the COM boundary
+                try {
+                    owner->handleSelectionChanged(sender);
+                } catch (const std::exception& e) {
+                    owner->reportError(std::string("Selection handler failed: ") + e.what());
+                } catch (...) {
+                    owner->reportError("Selection handler failed");
+                }
+            } else if (
+                eventId == UIA_SelectionItem_ElementSelectedEventId ||
+                eventId == UIA_SelectionItem_ElementAddedToSelectionEventId ||
+                eventId == UIA_SelectionItem_ElementRemovedFromSelectionEventId
+            ) {
+                try {
+                    owner->handleItemSelectionChanged(sender);
+                } catch (const std::exception& e) {
+                    owner->reportError(std::string("Item selection handler failed: ") + e.what());
+                } catch (...) {
+                    owner->reportError("Item selection handler failed");
+                }
             } else {
                 if (UIAutomationSelectionMonitor::debugEnabled) std::cout << "[UIA] ❓ UNKNOWN EVENT: " << eventId << std::endl;
             }
@@ -176,101 +704,240 @@ private:
         }
     };
 };
-bool UIAutomationSelectionMonitor::debugEnabled = false;
+const bool UIAutomationSelectionMonitor::debugEnabled = [] {
+    // Enable debug only when explicitly requested
+    const char* dbg = std::getenv("PHEVERE_DEBUG_UIA");
+    return dbg != nullptr && std::string(dbg) == "1";
+}();
 
-// Define the static instance pointer
-UIAutomationSelectionMonitor* UIAutomationSelectionMonitor::instance = nullptr;
+thread_local UIAutomationSelectionMonitor* UIAutomationSelectionMonitor::hook_owner = nullptr;
 
 // Implementation of the monitor loop
 void UIAutomationSelectionMonitor::monitorLoop() {
     if (debugEnabled) std::cout << "[UIA] THREAD: Starting dedicated UIA monitor thread..." << std::endl;
+
+    // Flag exit on every return path so stop() never waits on a dead thread
+    struct ExitFlag {
+        std::atomic<bool>& flag;
+        ~ExitFlag() { flag.store(true); }
+    } exitFlag{monitor_exited};
+
+    // Force creation of this thread's message queue before publishing the thread ID,
+    // so a WM_QUIT posted by stop() can never be lost
+    MSG queueInit;
+    PeekMessage(&queueInit, NULL, WM_USER, WM_USER, PM_NOREMOVE);
+    monitor_thread_id.store(GetCurrentThreadId());
+
+    // Report every coordinate (UIA rectangles, cursor, hook points) in physical pixels
+    // regardless of how the host process is DPI-virtualized; JS converts to DIP.
+    SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2);
     
     // Step 1: Initialize COM on this thread
     HRESULT hr = CoInitializeEx(nullptr, COINIT_APARTMENTTHREADED);
     if (FAILED(hr)) {
-        std::cerr << "[UIA] THREAD: Failed to initialize COM. HRESULT: " << hr << std::endl;
+        reportInitFailure(describeFailure("Failed to initialize COM", hr));
         return;
     }
     
-    monitor_thread_id = GetCurrentThreadId();
-    if (debugEnabled) std::cout << "[UIA] THREAD: COM initialized, thread ID: " << monitor_thread_id << std::endl;
+    if (debugEnabled) std::cout << "[UIA] THREAD: COM initialized, thread ID: " << monitor_thread_id.load() << std::endl;
     
     // Step 2: Create UIA objects on this thread
     hr = CoCreateInstance(__uuidof(CUIAutomation), NULL, CLSCTX_INPROC_SERVER, __uuidof(IUIAutomation), (void**)&pAutomation);
     if (FAILED(hr) || !pAutomation) {
-        std::cerr << "[UIA] THREAD: Failed to create UIA object. HRESULT: " << hr << std::endl;
+        reportInitFailure(describeFailure("Failed to create UIA object", hr));
         CoUninitialize();
         return;
     }
     if (debugEnabled) std::cout << "[UIA] THREAD: UIA object created successfully" << std::endl;
 
+    int callTimeout = call_timeout_ms.load();
+    if (callTimeout > 0) {
+        // IUIAutomation2 needs Windows 8; older systems keep the default (no timeout)
+        CComQIPtr<IUIAutomation2> automation2(pAutomation);
+        if (automation2) {
+            automation2->put_ConnectionTimeout((DWORD)callTimeout);
+            automation2->put_TransactionTimeout((DWORD)callTimeout);
+        }
+    }
+
     CComPtr<IUIAutomationElement> pDesktopElement;
     hr = pAutomation->GetRootElement(&pDesktopElement);
     if (FAILED(hr) || !pDesktopElement) {
-        std::cerr << "[UIA] THREAD: Failed to get root element. HRESULT: " << hr << std::endl;
+        reportInitFailure(describeFailure("Failed to get UIA root element", hr));
         pAutomation.Release();
         CoUninitialize();
         return;
     }
     if (debugEnabled) std::cout << "[UIA] THREAD: Desktop element obtained successfully" << std::endl;
 
-    // Step 3: Create and register the event handler
-    CComPtr<IUIAutomationEventHandler> pEventHandler = new UIAutomationEventHandler();
-    if (debugEnabled) std::cout << "[UIA] THREAD: Registering text-related event handlers..." << std::endl;
-    HRESULT hrSel = pAutomation->AddAutomationEventHandler(
-        UIA_Text_TextSelectionChangedEventId,
-        pDesktopElement,
-        TreeScope_Subtree,
-        nullptr,
-        pEventHandler
-    );
-
-    HRESULT hrChanged = pAutomation->AddAutomationEventHandler(
-        UIA_Text_TextChangedEventId,
-        pDesktopElement,
-        TreeScope_Subtree,
-        nullptr,
-        pEventHandler
-    );
-
-    // TextEdit-specific changes (some providers use this)
-    HRESULT hrEditChanged = pAutomation->AddAutomationEventHandler(
-        UIA_TextEdit_TextChangedEventId,
-        pDesktopElement,
-        TreeScope_Subtree,
-        nullptr,
-        pEventHandler
-    );
-
-    if (FAILED(hrSel)) {
-        std::cerr << "[UIA] THREAD: Failed to register TextSelectionChanged handler. HRESULT: " << hrSel << std::endl;
-    }
-    if (FAILED(hrChanged)) {
-        if (debugEnabled) std::cout << "[UIA] THREAD: TextChanged handler registration failed (may be unsupported). HRESULT: " << hrChanged << std::endl;
-    }
-    if (FAILED(hrEditChanged)) {
-        if (debugEnabled) std::cout << "[UIA] THREAD: TextEdit_TextChanged handler registration failed (may be unsupported). HRESULT: " << hrEditChanged << std::endl;
-    }
-    if (SUCCEEDED(hrSel) || SUCCEEDED(hrChanged) || SUCCEEDED(hrEditChanged)) {
+    // Step 3: Create and register the event handler. Hotkey mode registers nothing
+    // and only reads the selection when a capture is requested; polling mode only
+    // reads on its timer. The lightweight WinEvent source replaces the UIA
+    // handlers when requested.
+    bool hotkeyOnly = hotkey_only.load();
+    bool pollingOnly = !hotkeyOnly && poll_interval_ms.load() > 0;
+    hook_owner = this;
+    HWINEVENTHOOK winEventHook = NULL;
+    if (!hotkeyOnly && !pollingOnly && use_win_events.load()) {
+        winEventHook = installWinEventHook();
+        if (!winEventHook) {
+            reportError("Failed to install the WinEvent hook, using UIA events. Error: " + std::to_string(GetLastError()));
+        }
+    }
+    bool uiaHandlers = !hotkeyOnly && !pollingOnly && !winEventHook;
+    CComPtr<IUIAutomationEventHandler> pEventHandler = new UIAutomationEventHandler(this);
+    HRESULT hrSel = E_ABORT, hrChanged = E_ABORT, hrEditChanged = E_ABORT;
+    if (uiaHandlers) {
+        if (debugEnabled) std::cout << "[UIA] THREAD: Registering text-related event handlers..." << std::endl;
+        hrSel = pAutomation->AddAutomationEventHandler(
+            UIA_Text_TextSelectionChangedEventId,
+            pDesktopElement,
+            TreeScope_Subtree,
+            nullptr,
+            pEventHandler
+        );
+
+        hrChanged = pAutomation->AddAutomationEventHandler(
+            UIA_Text_TextChangedEventId,
+            pDesktopElement,
+            TreeScope_Subtree,
+            nullptr,
+            pEventHandler
+        );
+
+        // TextEdit-specific changes (some providers use this)
+        hrEditChanged = pAutomation->AddAutomationEventHandler(
+            UIA_TextEdit_TextChangedEventId,
+            pDesktopElement,
+            TreeScope_Subtree,
+            nullptr,
+            pEventHandler
+        );
+
+        // File selections in Explorer are list item selections, not text
+        for (EVENTID itemEvent : { UIA_SelectionItem_ElementSelectedEventId,
+                                   UIA_SelectionItem_ElementAddedToSelectionEventId,
+                                   UIA_SelectionItem_ElementRemovedFromSelectionEventId }) {
+            HRESULT hrItem = pAutomation->AddAutomationEventHandler(itemEvent, pDesktopElement, TreeScope_Subtree, nullptr, pEventHandler);
+            if (FAILED(hrItem) && debugEnabled) {
+                std::cout << "[UIA] THREAD: Item selection handler registration failed. HRESULT: " << hrItem << std::endl;
+            }
+        }
+
+        if (FAILED(hrSel)) {
+            reportError(describeFailure("Failed to register TextSelectionChanged handler", hrSel));
+        }
+        if (FAILED(hrChanged)) {
+            if (debugEnabled) std::cout << "[UIA] THREAD: TextChanged handler registration failed (may be unsupported). HRESULT: " << hrChanged << std::endl;
+        }
+        if (FAILED(hrEditChanged)) {
+            if (debugEnabled) std::cout << "[UIA] THREAD: TextEdit_TextChanged handler registration failed (may be unsupported). HRESULT: " << hrEditChanged << std::endl;
+        }
+    } else if (hotkeyOnly && debugEnabled) {
+        std::cout << "[UIA] THREAD: Hotkey capture mode, no event handlers registered" << std::endl;
+    } else if (pollingOnly && debugEnabled) {
+        std::cout << "[UIA] THREAD: Polling mode, no event handlers registered" << std::endl;
+    }
+
+    int pollInterval = hotkeyOnly ? 0 : poll_interval_ms.load();
+    if (winEventHook || SUCCEEDED(hrSel) || SUCCEEDED(hrChanged) || SUCCEEDED(hrEditChanged)) {
         if (debugEnabled) std::cout << "[UIA] THREAD: Event handlers registered. Waiting for events..." << std::endl;
-    } else {
-        std::cerr << "[UIA] THREAD: No text-related handlers could be registered." << std::endl;
+    } else if (!hotkeyOnly && !pollingOnly) {
+        reportError("No text-related UIA event handlers could be registered");
+        // Cheaper than polling and still event-driven
+        winEventHook = installWinEventHook();
+        if (winEventHook) {
+            std::cerr << "[UIA] THREAD: Falling back to WinEvent selection notifications" << std::endl;
+        } else if (pollInterval == 0) {
+            std::cerr << "[UIA] THREAD: Falling back to polling the focused element" << std::endl;
+            pollInterval = DEFAULT_POLL_INTERVAL_MS;
+        }
+    }
+
+    // Thread timer: WM_TIMER arrives with a NULL hwnd and is handled in the loop below
+    UINT_PTR pollTimer = 0;
+    if (pollInterval > 0) {
+        pollTimer = SetTimer(NULL, 0, (UINT)pollInterval, NULL);
+        polling_active.store(pollTimer != 0);
+        if (!pollTimer) {
+            reportError("Failed to create the polling timer. Error: " + std::to_string(GetLastError()));
+        }
+        last_polled_text.clear();
+        if (debugEnabled) std::cout << "[UIA] THREAD: Polling focused element every " << pollInterval << "ms" << std::endl;
+    }
+
+    // The hook procedure runs on this thread, inside GetMessage below
+    if (!hotkeyOnly && mouse_hook_enabled.load()) {
+        left_button_down = false;
+        mouse_hook = SetWindowsHookEx(WH_MOUSE_LL, &UIAutomationSelectionMonitor::lowLevelMouseProc, GetModuleHandle(NULL), 0);
+        if (!mouse_hook) {
+            reportError("Failed to install low-level mouse hook. Error: " + std::to_string(GetLastError()));
+        } else if (debugEnabled) {
+            std::cout << "[UIA] THREAD: Low-level mouse hook installed" << std::endl;
+        }
     }
 
     // Step 4: Run the message loop
     if (debugEnabled) std::cout << "[UIA] THREAD: Entering Windows message loop..." << std::endl;
+    reached_ready.store(true);
+    emitState("ready");
     MSG msg;
     while (running.load() && GetMessage(&msg, NULL, 0, 0)) {
+        if (msg.message == WM_TIMER && msg.hwnd == NULL && msg.wParam == pollTimer) {
+            pollFocusedSelection();
+            continue;
+        }
+        if (msg.message == WM_APP_CAPTURE) {
+            captureFocusedSelection();
+            continue;
+        }
+        if (msg.message == WM_APP_MOUSE_SELECTION) {
+            handleMouseSelection();
+            continue;
+        }
+        if (msg.message == WM_APP_READ) {
+            std::unique_ptr<std::shared_ptr<ReadRequest>> request(reinterpret_cast<std::shared_ptr<ReadRequest>*>(msg.lParam));
+            SelectionInfo info;
+            bool read = (*request)->atPoint
+                ? readTextAtPoint((*request)->point, (*request)->chars, info)
+                : readFocusedSelection(info);
+            if (!read) {
+                info = SelectionInfo();
+            }
+            (*request)->result.set_value(info);
+            continue;
+        }
         TranslateMessage(&msg);
         DispatchMessage(&msg);
     }
+
+    if (pollTimer) {
+        KillTimer(NULL, pollTimer);
+        polling_active.store(false);
+    }
+    if (mouse_hook) {
+        UnhookWindowsHookEx(mouse_hook);
+        mouse_hook = NULL;
+    }
+    if (winEventHook) {
+        UnhookWinEvent(winEventHook);
+        win_events_active.store(false);
+    }
+    hook_owner = nullptr;
     
     // Step 5: Clean up
     if (debugEnabled) std::cout << "[UIA] THREAD: Message loop exited. Cleaning up..." << std::endl;
     // Best-effort unregister (ignore failures)
-    pAutomation->RemoveAutomationEventHandler(UIA_Text_TextSelectionChangedEventId, pDesktopElement, pEventHandler);
-    pAutomation->RemoveAutomationEventHandler(UIA_Text_TextChangedEventId, pDesktopElement, pEventHandler);
-    pAutomation->RemoveAutomationEventHandler(UIA_TextEdit_TextChangedEventId, pDesktopElement, pEventHandler);
+    if (SUCCEEDED(hrSel)) pAutomation->RemoveAutomationEventHandler(UIA_Text_TextSelectionChangedEventId, pDesktopElement, pEventHandler);
+    if (SUCCEEDED(hrChanged)) pAutomation->RemoveAutomationEventHandler(UIA_Text_TextChangedEventId, pDesktopElement, pEventHandler);
+    if (SUCCEEDED(hrEditChanged)) pAutomation->RemoveAutomationEventHandler(UIA_TextEdit_TextChangedEventId, pDesktopElement, pEventHandler);
+    if (uiaHandlers) {
+        for (EVENTID itemEvent : { UIA_SelectionItem_ElementSelectedEventId,
+                                   UIA_SelectionItem_ElementAddedToSelectionEventId,
+                                   UIA_SelectionItem_ElementRemovedFromSelectionEventId }) {
+            pAutomation->RemoveAutomationEventHandler(itemEvent, pDesktopElement, pEventHandler);
+        }
+    }
     pEventHandler.Release();
     pDesktopElement.Release();
     pAutomation.Release();
@@ -280,7 +947,7 @@ void UIAutomationSelectionMonitor::monitorLoop() {
 
 // Implementation of the selection handler (now debounced)
 void UIAutomationSelectionMonitor::handleSelectionChanged(IUIAutomationElement* sender) {
-    if (!sender) return;
+    if (!sender || paused.load()) return;
 
     // Ignore events coming from our own Electron process to avoid self-triggering
     if (isFromCurrentProcess(sender)) {
@@ -288,30 +955,317 @@ void UIAutomationSelectionMonitor::handleSelectionChanged(IUIAutomationElement*
         return;
     }
 
-    std::string selectedText = getSelectedTextFromElement(sender);
+    if (!isTriggerEnabled()) {
+        if (debugEnabled) std::cout << "[UIA] IGNORE: Selection gesture not in trigger mask" << std::endl;
+        return;
+    }
+
+    DWORD senderPid = getProcessId(sender);
+
+    // UAC prompts and other users' sessions return garbage; don't read from them
+    if (updateSecureState(senderPid)) {
+        return;
+    }
+
+    // Filter by app before reading any text from the element
+    std::string processName = getProcessName(senderPid);
+    if (!isAppAllowed(processName)) {
+        if (debugEnabled) std::cout << "[UIA] IGNORE: Selection from filtered app " << processName << std::endl;
+        return;
+    }
+
+    // Never read text out of password fields
+    if (isPasswordField(sender)) {
+        if (debugEnabled) std::cout << "[UIA] IGNORE: Selection in password field" << std::endl;
+        return;
+    }
+
+    // The IME moves the selection over its pre-edit text while the user types
+    bool composing = isComposing(sender);
+    if (composing && suppress_composition.load()) {
+        if (debugEnabled) std::cout << "[UIA] IGNORE: Selection during IME composition" << std::endl;
+        return;
+    }
+
+    SelectionInfo info;
+    info.composing = composing;
+    std::vector<std::string> ranges = readLegacyEditSelection(sender, info)
+        ? info.ranges : getSelectedRangesFromElement(sender, &info);
+    std::string selectedText = joinRanges(ranges);
     if (selectedText.empty()) {
         // Fallback: try focused element or element under cursor
         selectedText = getSelectedTextFromFocusedOrPoint();
     }
-    int selX = 0, selY = 0;
-    if (!getSelectionCenter(sender, selX, selY)) {
+    info.text = selectedText;
+    info.ranges = ranges.empty() ? std::vector<std::string>{selectedText} : ranges;
+    info.app = processName;
+    info.granularity = currentGranularity();
+    describeElement(sender, info);
+    if (!getSelectionBounds(sender, info)) {
         // Fallback to current cursor if we cannot compute the rectangle
-        POINT pt; GetCursorPos(&pt); selX = pt.x; selY = pt.y;
+        POINT pt; GetCursorPos(&pt); info.x = pt.x; info.y = pt.y;
     }
 
     if (!selectedText.empty()) {
-        // Only log in debug mode - too verbose for normal operation
-        // std::cout << "[UIA] EVENT: Raw selection detected: \"" << selectedText << "\"" << std::endl;
-        updatePendingSelection(selectedText, selX, selY);
+        updatePendingSelection(info);
+        last_caret = RECT{0, 0, 0, 0};
+    } else {
+        reportCleared();
+        if (track_caret.load()) reportCaret(sender, processName);
+    }
+}
+
+// Read the focused control's selection, applying the same process, secure-desktop,
+// app and password filters as the event path. Monitor thread only.
+bool UIAutomationSelectionMonitor::readFocusedSelection(SelectionInfo& info, bool allowValueFallback) {
+    if (!pAutomation) return false;
+
+    CComPtr<IUIAutomationElement> focused;
+    HRESULT hr = pAutomation->GetFocusedElement(&focused);
+    if (FAILED(hr) || !focused) {
+        checkTimeout(hr, "Reading the focused element");
+        return false;
+    }
+    if (isFromCurrentProcess(focused)) return false;
+    enableChromiumAccessibility(focused);
+
+    DWORD pid = getProcessId(focused);
+    if (updateSecureState(pid)) return false;
+
+    std::string processName = getProcessName(pid);
+    if (!isAppAllowed(processName) || isPasswordField(focused)) return false;
+
+    info.composing = isComposing(focused);
+    describeElement(focused, info);
+    if (readFileSelection(focused, processName, info)) return true;
+    if (!readConsoleSelection(focused, info) && !readLegacyEditSelection(focused, info)) {
+        info.ranges = getSelectedRangesFromElement(focused, &info);
+    }
+    std::string value;
+    if (info.ranges.empty() && allowValueFallback && readValueFallback(focused, value)) {
+        info.ranges.push_back(value);
+    }
+    info.text = joinRanges(info.ranges);
+    info.app = processName;
+    if (!info.text.empty() && !getSelectionBounds(focused, info)) {
+        POINT pt; GetCursorPos(&pt); info.x = pt.x; info.y = pt.y;
+    }
+    return true;
+}
+
+// Text on either side of a screen point, for word-at-point lookups: the element
+// under the point (or its nearest ancestor with a text pattern) is read around
+// the degenerate range at the point. Same process, app and password filters as
+// a selection read. Monitor thread only.
+bool UIAutomationSelectionMonitor::readTextAtPoint(POINT pt, int chars, SelectionInfo& info) {
+    if (!pAutomation || chars <= 0) return false;
+
+    CComPtr<IUIAutomationElement> element;
+    HRESULT hr = pAutomation->ElementFromPoint(pt, &element);
+    if (FAILED(hr) || !element) {
+        checkTimeout(hr, "Reading the element under the point");
+        return false;
+    }
+    if (isFromCurrentProcess(element)) return false;
+
+    DWORD pid = getProcessId(element);
+    if (updateSecureState(pid)) return false;
+    std::string processName = getProcessName(pid);
+    if (!isAppAllowed(processName) || isPasswordField(element)) return false;
+
+    CComPtr<IUIAutomationElement> textElement = findAncestorWithTextPattern(element);
+    CComPtr<IUIAutomationTextPattern> pattern;
+    if (!textElement || FAILED(textElement->GetCurrentPatternAs(UIA_TextPatternId, IID_PPV_ARGS(&pattern))) || !pattern) {
+        return false;
+    }
+    CComPtr<IUIAutomationTextRange> range;
+    if (FAILED(pattern->RangeFromPoint(pt, &range)) || !range) return false;
+
+    info.contextBefore = readAdjacentText(range, true, chars);
+    info.contextAfter = readAdjacentText(range, false, chars);
+    info.app = processName;
+    info.x = pt.x;
+    info.y = pt.y;
+    return !info.contextBefore.empty() || !info.contextAfter.empty();
+}
+
+// Low-fidelity fallback: read the focused control's selection on each timer tick.
+// Only changes are forwarded, so a selection that stays put is reported once.
+void UIAutomationSelectionMonitor::pollFocusedSelection() {
+    if (paused.load()) return;
+
+    SelectionInfo info;
+    if (!readFocusedSelection(info)) return;
+    // Wait for the composition to be committed; the next tick reads the result
+    if (info.composing && suppress_composition.load()) return;
+    if (info.text.empty() && track_caret.load() && pAutomation) {
+        CComPtr<IUIAutomationElement> focused;
+        if (SUCCEEDED(pAutomation->GetFocusedElement(&focused)) && focused) reportCaret(focused, info.app);
+    }
+    if (info.text == last_polled_text) return;
+    last_polled_text = info.text;
+    if (info.text.empty()) {
+        reportCleared();
+        return;
+    }
+    last_caret = RECT{0, 0, 0, 0};
+
+    updatePendingSelection(info);
+}
+
+// Keep this fast: Windows drops hooks that take too long. The actual UIA read
+// happens on the message loop once WM_APP_MOUSE_SELECTION is dequeued.
+LRESULT CALLBACK UIAutomationSelectionMonitor::lowLevelMouseProc(int nCode, WPARAM wParam, LPARAM lParam) {
+    UIAutomationSelectionMonitor* self = hook_owner;
+    if (nCode == HC_ACTION && self) {
+        const MSLLHOOKSTRUCT* data = reinterpret_cast<const MSLLHOOKSTRUCT*>(lParam);
+        if (wParam == WM_LBUTTONDOWN) {
+            // Same rules the system uses to turn clicks into double clicks
+            bool sameSpot = std::abs(data->pt.x - self->last_click_pos.x) <= GetSystemMetrics(SM_CXDOUBLECLK) / 2 &&
+                std::abs(data->pt.y - self->last_click_pos.y) <= GetSystemMetrics(SM_CYDOUBLECLK) / 2;
+            bool inTime = data->time - self->last_click_time <= GetDoubleClickTime();
+            self->click_count = (sameSpot && inTime) ? self->click_count + 1 : 1;
+            self->last_click_time = data->time;
+            self->last_click_pos = data->pt;
+            self->left_button_down = true;
+            self->drag_start = data->pt;
+        } else if (wParam == WM_LBUTTONUP && self->left_button_down) {
+            self->left_button_down = false;
+            self->drag_end = data->pt;
+            bool dragged = std::abs(data->pt.x - self->drag_start.x) > GetSystemMetrics(SM_CXDRAG) ||
+                std::abs(data->pt.y - self->drag_start.y) > GetSystemMetrics(SM_CYDRAG);
+            int gesture = dragged ? GESTURE_DRAG
+                : self->click_count >= 3 ? GESTURE_TRIPLE_CLICK
+                : self->click_count == 2 ? GESTURE_DOUBLE_CLICK
+                : GESTURE_NONE;
+            if (gesture != GESTURE_NONE) {
+                self->last_gesture.store(gesture);
+                self->last_gesture_tick.store(GetTickCount64());
+                PostThreadMessage(GetCurrentThreadId(), WM_APP_MOUSE_SELECTION, 0, 0);
+            }
+        }
+    }
+    return CallNextHookEx(NULL, nCode, wParam, lParam);
+}
+
+// MSAA text selection notifications for every process, delivered out of context
+// to this thread's message loop. Far cheaper than UIA subtree subscriptions and
+// raised by older Win32 apps that never implemented UIA events.
+HWINEVENTHOOK UIAutomationSelectionMonitor::installWinEventHook() {
+    HWINEVENTHOOK hook = SetWinEventHook(EVENT_OBJECT_TEXTSELECTIONCHANGED, EVENT_OBJECT_TEXTSELECTIONCHANGED, NULL,
+        &UIAutomationSelectionMonitor::winEventProc, 0, 0, WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS);
+    win_events_active.store(hook != NULL);
+    if (hook && debugEnabled) std::cout << "[UIA] THREAD: WinEvent selection hook installed" << std::endl;
+    return hook;
+}
+
+// The event's accessible object is mapped to a UIA element, then handled exactly
+// like a UIA selection event (filters, reading, debounce)
+void CALLBACK UIAutomationSelectionMonitor::winEventProc(HWINEVENTHOOK, DWORD, HWND hwnd, LONG idObject, LONG idChild,
+                                                         DWORD, DWORD) {
+    UIAutomationSelectionMonitor* self = hook_owner;
+    if (!self || !self->pAutomation || !hwnd) return;
+
+    try {
+        CComPtr<IUIAutomationElement> element;
+        CComPtr<IAccessible> accessible;
+        VARIANT child;
+        VariantInit(&child);
+        if (SUCCEEDED(AccessibleObjectFromEvent(hwnd, idObject, idChild, &accessible, &child)) && accessible) {
+            self->pAutomation->ElementFromIAccessible(accessible, child.vt == VT_I4 ? child.lVal : CHILDID_SELF, &element);
+        }
+        VariantClear(&child);
+        if (!element && FAILED(self->pAutomation->ElementFromHandle(hwnd, &element))) return;
+        if (element) self->handleSelectionChanged(element);
+    } catch (const std::exception& e) {
+        self->reportError(std::string("WinEvent handler failed: ") + e.what());
+    } catch (...) {
+        self->reportError("WinEvent handler failed");
+    }
+}
+
+// Mouse selection finished: read the focused element's selection like an event would
+void UIAutomationSelectionMonitor::handleMouseSelection() {
+    if (paused.load() || (trigger_mask.load() & TRIGGER_MOUSE) == 0) return;
+
+    SelectionInfo info;
+    if (!readFocusedSelection(info)) return;
+    // A click inside the pre-edit text; info.composing flags it otherwise
+    if (info.composing && suppress_composition.load()) {
+        if (debugEnabled) std::cout << "[UIA] IGNORE: Mouse selection during IME composition" << std::endl;
+        return;
+    }
+    if (info.text.empty()) {
+        // Filters passed but the app exposes no text: hand the dragged area to JS
+        if (report_unreadable.load() && last_gesture.load() == GESTURE_DRAG) {
+            info.unreadable = true;
+            info.granularity = "drag";
+            info.hasBounds = true;
+            info.left = drag_start.x < drag_end.x ? drag_start.x : drag_end.x;
+            info.top = drag_start.y < drag_end.y ? drag_start.y : drag_end.y;
+            info.width = std::abs(drag_end.x - drag_start.x);
+            info.height = std::abs(drag_end.y - drag_start.y);
+            info.x = (int)info.left;
+            info.y = (int)info.top;
+            if (debugEnabled) std::cout << "[UIA] MOUSE: Drag over unreadable content" << std::endl;
+            deliverNow(info);
+        }
+        return;
+    }
+    info.granularity = currentGranularity();
+    if (debugEnabled) std::cout << "[UIA] MOUSE: Selection read after " << info.granularity << " gesture" << std::endl;
+    updatePendingSelection(info);
+}
+
+// Attribute a selection to the most recent mouse gesture, or to the keyboard when
+// Shift/Ctrl is held without the mouse
+std::string UIAutomationSelectionMonitor::currentGranularity() {
+    if (GetTickCount64() - last_gesture_tick.load() <= GESTURE_ATTRIBUTION_MS) {
+        switch (last_gesture.load()) {
+            case GESTURE_DRAG: return "drag";
+            case GESTURE_DOUBLE_CLICK: return "word";
+            case GESTURE_TRIPLE_CLICK: return "paragraph";
+        }
+    }
+    bool mouseDown = (GetAsyncKeyState(VK_LBUTTON) & 0x8000) != 0;
+    bool modifierDown = (GetAsyncKeyState(VK_SHIFT) & 0x8000) != 0 || (GetAsyncKeyState(VK_CONTROL) & 0x8000) != 0;
+    if (mouseDown) return "drag";
+    if (modifierDown) return "keyboard";
+    return "unknown";
+}
+
+// Explicit capture request (hotkey): bypasses pause and debounce
+void UIAutomationSelectionMonitor::captureFocusedSelection() {
+    SelectionInfo info;
+    // An explicit capture may fall back to the field's whole value
+    if (!readFocusedSelection(info, true) || info.text.empty()) {
+        if (debugEnabled) std::cout << "[UIA] CAPTURE: No selection in focused element" << std::endl;
+        return;
+    }
+    info.requested = true;
+    deliverNow(info);
+}
+
+void UIAutomationSelectionMonitor::deliverNow(const SelectionInfo& info) {
+    {
+        std::lock_guard<std::mutex> lock(debounce_mutex);
+        last_selection = info;
+    }
+    std::function<void(const SelectionInfo&)> notify;
+    {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        notify = callback;
+    }
+    if (notify) {
+        selection_reported.store(true);
+        notify(info);
     }
 }
 
 // Debouncing mechanism implementation
-void UIAutomationSelectionMonitor::updatePendingSelection(const std::string& newSelection, int x, int y) {
+void UIAutomationSelectionMonitor::updatePendingSelection(const SelectionInfo& newSelection) {
     std::lock_guard<std::mutex> lock(debounce_mutex);
     pending_selection = newSelection;
-    pending_x = x;
-    pending_y = y;
     last_selection_time = std::chrono::steady_clock::now();
     // Only log in debug mode - too verbose for normal operation
     // std::cout << "[UIA] DEBOUNCE: Updated pending selection: \"" << newSelection << "\"" << std::endl;
@@ -319,27 +1273,39 @@ void UIAutomationSelectionMonitor::updatePendingSelection(const std::string& new
 
 void UIAutomationSelectionMonitor::debounceLoop() {
     if (debugEnabled) std::cout << "[UIA] DEBOUNCE: Starting debounce thread..." << std::endl;
+
+    struct ExitFlag {
+        std::atomic<bool>& flag;
+        ~ExitFlag() { flag.store(true); }
+    } exitFlag{debounce_exited};
     
     while (debounce_running.load()) {
         std::this_thread::sleep_for(std::chrono::milliseconds(50)); // Check every 50ms
         
         std::lock_guard<std::mutex> lock(debounce_mutex);
         
-        if (!pending_selection.empty()) {
+        if (!pending_selection.text.empty()) {
             auto now = std::chrono::steady_clock::now();
             auto elapsed = std::chrono::duration_cast<std::chrono::milliseconds>(now - last_selection_time);
             
-            if (elapsed.count() >= DEBOUNCE_DELAY_MS) {
+            if (elapsed.count() >= debounce_delay_ms.load()) {
                 // Selection has settled, always notify (even if same text as before)
                 // This allows re-triggering the popup when the same word is selected again
                 last_selection = pending_selection;
-                if (debugEnabled) std::cout << "[UIA] DEBOUNCE: Selection settled after " << elapsed.count() << "ms: \"" << pending_selection << "\"" << std::endl;
+                if (debugEnabled) std::cout << "[UIA] DEBOUNCE: Selection settled after " << elapsed.count() << "ms: "
+                                            << pending_selection.text.size() << " bytes from " << pending_selection.app << std::endl;
 
-                if (callback) {
-                    callback(pending_selection, pending_x, pending_y);
+                std::function<void(const SelectionInfo&)> notify;
+                {
+                    std::lock_guard<std::mutex> callbackLock(callback_mutex);
+                    notify = callback;
+                }
+                if (notify) {
+                    selection_reported.store(true);
+                    notify(pending_selection);
                 }
 
-                pending_selection.clear(); // Clear pending selection
+                pending_selection = SelectionInfo(); // Clear pending selection
             }
         }
     }
@@ -349,65 +1315,749 @@ void UIAutomationSelectionMonitor::debounceLoop() {
 
 // Implementation of the text retrieval function
 std::string UIAutomationSelectionMonitor::getSelectedTextFromElement(IUIAutomationElement* element) {
-    if (!element) return "";
+    return joinRanges(getSelectedRangesFromElement(element));
+}
 
-    CComPtr<IUIAutomationTextPattern> pTextPattern;
-    HRESULT hr = element->GetCurrentPattern(UIA_TextPatternId, (IUnknown**)&pTextPattern);
-    if (FAILED(hr) || !pTextPattern) {
-        // Try to find an ancestor that supports TextPattern
-        CComPtr<IUIAutomationElement> withText = findAncestorWithTextPattern(element);
-        if (withText) {
-            element = withText;
-            pTextPattern.Release();
-            hr = element->GetCurrentPattern(UIA_TextPatternId, (IUnknown**)&pTextPattern);
-        }
-        if (FAILED(hr) || !pTextPattern) {
-            return ""; // No TextPattern available in chain
-        }
+std::string UIAutomationSelectionMonitor::joinRanges(const std::vector<std::string>& ranges) {
+    std::string result;
+    for (size_t i = 0; i < ranges.size(); ++i) {
+        if (i > 0) result += RANGE_SEPARATOR;
+        result += ranges[i];
     }
+    return result;
+}
 
-    CComPtr<IUIAutomationTextRangeArray> pSelection;
-    hr = pTextPattern->GetSelection(&pSelection);
-    if (FAILED(hr) || !pSelection) {
-        return "";
-    }
+// Selected files in an Explorer window or on the desktop, read from the shell
+// view rather than the item names UIA would give us
+bool UIAutomationSelectionMonitor::readExplorerSelection(HWND topLevel, std::vector<std::string>& files) {
+    wchar_t className[64] = {0};
+    if (!topLevel || GetClassNameW(topLevel, className, 64) <= 0) return false;
+    bool desktop = wcscmp(className, L"Progman") == 0 || wcscmp(className, L"WorkerW") == 0;
+    if (!desktop && wcscmp(className, L"CabinetWClass") != 0) return false;
+
+    CComPtr<IShellWindows> windows;
+    if (FAILED(windows.CoCreateInstance(CLSID_ShellWindows))) return false;
+
+    // Explorer tabs share one top-level window; the active tab's view is the visible one
+    CComPtr<IShellBrowser> browser;
+    auto acceptBrowser = [&browser](IDispatch* dispatch) {
+        CComQIPtr<IServiceProvider> provider(dispatch);
+        CComPtr<IShellBrowser> candidate;
+        if (!provider || FAILED(provider->QueryService(SID_STopLevelBrowser, IID_PPV_ARGS(&candidate))) || !candidate) return false;
+        HWND tab = NULL;
+        if (SUCCEEDED(candidate->GetWindow(&tab)) && tab && !IsWindowVisible(tab)) return false;
+        browser = candidate;
+        return true;
+    };
 
-    int selectionLength = 0;
-    pSelection->get_Length(&selectionLength);
-    if (selectionLength == 0) {
-        return "";
+    if (desktop) {
+        CComVariant location(CSIDL_DESKTOP);
+        CComVariant empty;
+        long handle = 0;
+        CComPtr<IDispatch> dispatch;
+        if (windows->FindWindowSW(&location, &empty, SWC_DESKTOP, &handle, SWFO_NEEDDISPATCH, &dispatch) != S_OK || !dispatch) return false;
+        acceptBrowser(dispatch);
+    } else {
+        long count = 0;
+        windows->get_Count(&count);
+        for (long i = 0; i < count && !browser; ++i) {
+            CComPtr<IDispatch> dispatch;
+            if (FAILED(windows->Item(CComVariant(i), &dispatch)) || !dispatch) continue;
+            CComQIPtr<IWebBrowserApp> app(dispatch);
+            SHANDLE_PTR handle = 0;
+            if (!app || FAILED(app->get_HWND(&handle)) || (HWND)handle != topLevel) continue;
+            acceptBrowser(dispatch);
+        }
+    }
+    if (!browser) return false;
+
+    CComPtr<IShellView> view;
+    if (FAILED(browser->QueryActiveShellView(&view)) || !view) return false;
+    CComQIPtr<IFolderView2> folderView(view);
+    CComPtr<IShellItemArray> items;
+    if (!folderView || folderView->GetSelection(FALSE, &items) != S_OK || !items) return true; // nothing selected
+
+    DWORD count = 0;
+    items->GetCount(&count);
+    for (DWORD i = 0; i < count; ++i) {
+        CComPtr<IShellItem> item;
+        PWSTR path = nullptr;
+        // Virtual items (Control Panel, libraries' roots) have no file-system path
+        if (FAILED(items->GetItemAt(i, &item)) || FAILED(item->GetDisplayName(SIGDN_FILESYSPATH, &path)) || !path) continue;
+        files.push_back(toUtf8(path, (int)wcslen(path)));
+        CoTaskMemFree(path);
     }
+    return true;
+}
 
-    CComPtr<IUIAutomationTextRange> pRange;
-    pSelection->GetElement(0, &pRange);
-    if (!pRange) return "";
+// Explorer's item views hold file selections; renaming (an Edit) stays a text selection
+bool UIAutomationSelectionMonitor::readFileSelection(IUIAutomationElement* element, const std::string& processName, SelectionInfo& info) {
+    if (normalizeAppName(processName) != "explorer") return false;
+    CONTROLTYPEID controlType = 0;
+    if (element && SUCCEEDED(element->get_CurrentControlType(&controlType)) && controlType == UIA_EditControlTypeId) return false;
+
+    std::vector<std::string> files;
+    HWND topLevel = GetAncestor(GetForegroundWindow(), GA_ROOT);
+    if (!readExplorerSelection(topLevel, files) || files.empty()) return false;
+
+    info.files = files;
+    info.ranges = files;
+    info.text = joinRanges(files);
+    info.app = processName;
+    POINT pt; GetCursorPos(&pt); info.x = pt.x; info.y = pt.y;
+    return true;
+}
+
+void UIAutomationSelectionMonitor::handleItemSelectionChanged(IUIAutomationElement* sender) {
+    if (!sender || paused.load() || isFromCurrentProcess(sender) || !isTriggerEnabled()) return;
+
+    DWORD senderPid = getProcessId(sender);
+    std::string processName = getProcessName(senderPid);
+    if (normalizeAppName(processName) != "explorer") return;
+    if (updateSecureState(senderPid) || !isAppAllowed(processName)) return;
+
+    SelectionInfo info;
+    if (readFileSelection(sender, processName, info)) {
+        info.granularity = currentGranularity();
+        describeElement(sender, info);
+        updatePendingSelection(info);
+    } else {
+        reportCleared();
+    }
+}
+
+// Classic console windows (conhost) expose their mark-mode selection only through
+// the console API, so attach to the window's console and read the selected cells.
+// Windows Terminal hosts its own UIA TextPattern and takes the normal path.
+// AttachConsole is process-wide: skip this when the app already has a console
+// (e.g. started from a terminal) rather than detaching from it.
+bool UIAutomationSelectionMonitor::readConsoleSelection(IUIAutomationElement* element, SelectionInfo& info) {
+    UIA_HWND handle = nullptr;
+    if (!element || FAILED(element->get_CurrentNativeWindowHandle(&handle)) || !handle) return false;
+    HWND hwnd = (HWND)handle;
+
+    wchar_t className[64] = {0};
+    if (GetClassNameW(hwnd, className, 64) <= 0 || wcscmp(className, L"ConsoleWindowClass") != 0) return false;
+
+    static std::mutex console_mutex;
+    std::lock_guard<std::mutex> lock(console_mutex);
+    if (GetConsoleWindow() != NULL) return false;
+
+    // For console windows this reports the attached client process, which is what AttachConsole needs
+    DWORD pid = 0;
+    GetWindowThreadProcessId(hwnd, &pid);
+    if (pid == 0 || !AttachConsole(pid)) return false;
+
+    bool ok = false;
+    CONSOLE_SELECTION_INFO selection = {};
+    HANDLE output = CreateFileW(L"CONOUT$", GENERIC_READ | GENERIC_WRITE, FILE_SHARE_READ | FILE_SHARE_WRITE,
+                                NULL, OPEN_EXISTING, 0, NULL);
+    if (output != INVALID_HANDLE_VALUE && GetConsoleSelectionInfo(&selection)) {
+        ok = true;
+        info.ranges.clear();
+        if (selection.dwFlags & CONSOLE_SELECTION_NOT_EMPTY) {
+            const SMALL_RECT& area = selection.srSelection;
+            int width = area.Right - area.Left + 1;
+            int maxLength = max_read_length.load();
+            std::wstring text;
+            std::vector<wchar_t> row(width > 0 ? width : 1);
+            for (SHORT y = area.Top; y <= area.Bottom && width > 0; ++y) {
+                DWORD read = 0;
+                COORD origin = { area.Left, y };
+                if (!ReadConsoleOutputCharacterW(output, row.data(), (DWORD)width, origin, &read)) break;
+                std::wstring line(row.data(), read);
+                line.erase(line.find_last_not_of(L' ') + 1); // cells are space-padded
+                if (y > area.Top) text += L"\n";
+                text += line;
+                if (maxLength > 0 && (int)text.size() > maxLength) {
+                    int cut = IS_HIGH_SURROGATE(text[maxLength - 1]) ? maxLength - 1 : maxLength;
+                    text.resize(cut);
+                    info.truncated = true;
+                    break;
+                }
+            }
+            std::string utf8 = toUtf8(text.c_str(), (int)text.size());
+            if (!utf8.empty()) info.ranges.push_back(utf8);
+        }
+    }
+    if (output != INVALID_HANDLE_VALUE) CloseHandle(output);
+    FreeConsole();
+    return ok;
+}
+
+// Fast path for classic Edit and RichEdit controls, which answer EM_GETSEL and
+// friends much faster and more reliably than their UIA proxies. Fills info.ranges
+// (empty for a bare caret), truncation and context; returns false so the caller
+// falls back to UIA for any other control or if the window does not respond.
+bool UIAutomationSelectionMonitor::readLegacyEditSelection(IUIAutomationElement* element, SelectionInfo& info) {
+    UIA_HWND handle = nullptr;
+    if (!element || FAILED(element->get_CurrentNativeWindowHandle(&handle)) || !handle) return false;
+    HWND hwnd = (HWND)handle;
+
+    wchar_t className[64] = {0};
+    int classLength = GetClassNameW(hwnd, className, 64);
+    if (classLength <= 0) return false;
+    // Only the Unicode RichEdit classes (RichEdit20W, RICHEDIT50W); EM_GETSELTEXT is ANSI otherwise
+    bool richEdit = _wcsnicmp(className, L"RichEdit", 8) == 0 && towupper(className[classLength - 1]) == L'W';
+    if (!richEdit && _wcsicmp(className, L"Edit") != 0) return false;
+
+    DWORD_PTR result = 0;
+
+    // EM_GETSEL's packed return value is 16-bit, so ask for both offsets by pointer.
+    // It is a system message, so the pointers are marshaled like WM_GETTEXT's buffer
+    DWORD bounds[2] = {0, 0};
+    if (!sendWithTimeout(hwnd, EM_GETSEL, (WPARAM)&bounds[0], (LPARAM)&bounds[1], &result)) return false;
+
+    info.ranges.clear();
+    if (bounds[1] <= bounds[0]) return true; // caret only
+
+    int length = (int)(bounds[1] - bounds[0]);
+    int maxLength = max_read_length.load();
+    int readLength = (maxLength > 0 && length > maxLength) ? maxLength : length;
+
+    std::wstring selected;
+    if (richEdit) {
+        // RichEdit counts a paragraph break as one character while WM_GETTEXT returns
+        // CRLF, so offsets into WM_GETTEXT would drift; read the selection directly.
+        // EM_GETSELTEXT is not marshaled and writes the whole selection with no size
+        // limit, so it needs a buffer in the app's process; selections over the read
+        // limit go to UIA, which can stop early, rather than allocating all of it
+        if (readLength < length) return false;
+        DWORD pid = 0;
+        GetWindowThreadProcessId(hwnd, &pid);
+        RemoteBuffer text(pid, (SIZE_T)(length + 1) * sizeof(wchar_t));
+        if (!text.valid()) return false;
+        if (!sendWithTimeout(hwnd, EM_GETSELTEXT, 0, (LPARAM)text.get(), &result)) return false;
+        std::vector<wchar_t> buffer(length + 1, 0);
+        if (!text.read(buffer.data(), buffer.size() * sizeof(wchar_t))) return false;
+        selected.assign(buffer.data(), result < (DWORD_PTR)length ? (size_t)result : (size_t)length);
+    } else {
+        // WM_GETTEXT is marshaled by the system; only read as far as the context needs
+        int contextChars = context_chars.load();
+        int wanted = (int)bounds[1] + (contextChars > 0 ? contextChars : 0);
+        std::vector<wchar_t> buffer(wanted + 1, 0);
+        if (!sendWithTimeout(hwnd, WM_GETTEXT, (WPARAM)buffer.size(), (LPARAM)buffer.data(), &result)) return false;
+        std::wstring content(buffer.data(), (size_t)result);
+        if (content.size() < bounds[1]) return false;
+        selected = content.substr(bounds[0], length);
+        if (contextChars > 0) {
+            size_t beforeStart = bounds[0] > (DWORD)contextChars ? bounds[0] - contextChars : 0;
+            std::wstring before = content.substr(beforeStart, bounds[0] - beforeStart);
+            std::wstring after = content.substr(bounds[1]);
+            info.contextBefore = toUtf8(before.c_str(), (int)before.size());
+            info.contextAfter = toUtf8(after.c_str(), (int)after.size());
+        }
+    }
+
+    if ((int)selected.size() > readLength) {
+        // Don't split a surrogate pair
+        if (readLength > 0 && IS_HIGH_SURROGATE(selected[readLength - 1])) readLength--;
+        selected.resize(readLength);
+        info.truncated = true;
+    }
+    std::string text = toUtf8(selected.c_str(), (int)selected.size());
+    if (!text.empty()) info.ranges.push_back(text);
+    return true;
+}
+
+// Text of every selected range; editors with multiple cursors/selections (Word,
+// VS Code, browsers) report more than one
+// When details is given, the surrounding text and truncation flag are stored in it as well
+std::vector<std::string> UIAutomationSelectionMonitor::getSelectedRangesFromElement(IUIAutomationElement* element, SelectionInfo* details) {
+    std::vector<std::string> ranges;
+    if (!element) return ranges;
+
+    CComPtr<IUIAutomationTextPattern> pTextPattern;
+    HRESULT hr = element->GetCurrentPattern(UIA_TextPatternId, (IUnknown**)&pTextPattern);
+    if (checkTimeout(hr, "Reading the text pattern")) return ranges;
+    if (FAILED(hr) || !pTextPattern) {
+        // Try to find an ancestor that supports TextPattern
+        CComPtr<IUIAutomationElement> withText = findAncestorWithTextPattern(element);
+        if (withText) {
+            element = withText;
+            pTextPattern.Release();
+            hr = element->GetCurrentPattern(UIA_TextPatternId, (IUnknown**)&pTextPattern);
+        }
+        if (FAILED(hr) || !pTextPattern) {
+            // UWP frames (ApplicationFrameHost) report the frame; the text lives in
+            // the hosted CoreWindow below it
+            CComPtr<IUIAutomationElement> inner = findFocusedDescendantWithTextPattern(element);
+            if (inner) {
+                element = inner;
+                pTextPattern.Release();
+                hr = element->GetCurrentPattern(UIA_TextPatternId, (IUnknown**)&pTextPattern);
+            }
+        }
+        if (FAILED(hr) || !pTextPattern) {
+            return ranges; // No TextPattern available in chain
+        }
+    }
+
+    CComPtr<IUIAutomationTextRangeArray> pSelection;
+    hr = pTextPattern->GetSelection(&pSelection);
+    if (FAILED(hr) || !pSelection) {
+        checkTimeout(hr, "Reading the selection");
+        return ranges;
+    }
+
+    int selectionLength = 0;
+    pSelection->get_Length(&selectionLength);
+    int maxLength = max_read_length.load();
+
+    for (int i = 0; i < selectionLength; ++i) {
+        CComPtr<IUIAutomationTextRange> pRange;
+        pSelection->GetElement(i, &pRange);
+        if (!pRange) continue;
+
+        // Ask for one unit more than the limit so truncation can be detected
+        BSTR bstr = nullptr;
+        pRange->GetText(maxLength > 0 ? maxLength + 1 : -1, &bstr);
+        if (!bstr) continue;
+
+        int length = (int)SysStringLen(bstr);
+        bool cut = false;
+        if (maxLength > 0 && length > maxLength) {
+            length = maxLength;
+            // Don't split a surrogate pair
+            if (IS_HIGH_SURROGATE(bstr[length - 1])) length--;
+            if (details) details->truncated = true;
+            cut = true;
+        }
+
+        // Convert BSTR (wide string) to std::string (UTF-8)
+        std::string text = toUtf8(bstr, length);
+        SysFreeString(bstr);
+        // Collapsed ranges (carets) carry no text
+        if (!text.empty()) {
+            ranges.push_back(text);
+            if (details && details->direction.empty()) {
+                details->direction = readFlowDirection(pRange);
+            }
+            // Runs of a cut range would not match its text; leave them out
+            if (details && capture_rich.load() && !cut) {
+                if (!details->runs.empty()) {
+                    RichRun separator;
+                    separator.text = RANGE_SEPARATOR;
+                    details->runs.push_back(separator);
+                }
+                readRichRuns(pRange, details->runs);
+            }
+        }
+    }
+
+    int contextChars = context_chars.load();
+    if (details && contextChars > 0 && !ranges.empty()) {
+        CComPtr<IUIAutomationTextRange> first, last;
+        pSelection->GetElement(0, &first);
+        pSelection->GetElement(selectionLength - 1, &last);
+        if (first) details->contextBefore = readAdjacentText(first, true, contextChars);
+        if (last) details->contextAfter = readAdjacentText(last, false, contextChars);
+    }
+    return ranges;
+}
+
+static bool readBoolAttribute(IUIAutomationTextRange* range, TEXTATTRIBUTEID attribute) {
+    VARIANT value;
+    VariantInit(&value);
+    bool result = SUCCEEDED(range->GetAttributeValue(attribute, &value)) && value.vt == VT_BOOL && value.boolVal == VARIANT_TRUE;
+    VariantClear(&value);
+    return result;
+}
+
+static int readIntAttribute(IUIAutomationTextRange* range, TEXTATTRIBUTEID attribute) {
+    VARIANT value;
+    VariantInit(&value);
+    // Mixed values come back as a reserved IUnknown and read as 0
+    int result = SUCCEEDED(range->GetAttributeValue(attribute, &value)) && value.vt == VT_I4 ? value.lVal : 0;
+    VariantClear(&value);
+    return result;
+}
+
+// Paragraph direction the control lays the range out in ("rtl" or "ltr"), or ""
+// when the provider does not report it or the range spans both
+std::string UIAutomationSelectionMonitor::readFlowDirection(IUIAutomationTextRange* range) {
+    VARIANT value;
+    VariantInit(&value);
+    std::string direction;
+    if (SUCCEEDED(range->GetAttributeValue(UIA_TextFlowDirectionsAttributeId, &value)) && value.vt == VT_I4) {
+        direction = (value.lVal & FlowDirections_RightToLeft) ? "rtl" : "ltr";
+    }
+    VariantClear(&value);
+    return direction;
+}
+
+// Walk the range one formatting unit at a time. Browsers and rich edits expose
+// links as Hyperlink elements enclosing the run, with the target as their value.
+void UIAutomationSelectionMonitor::readRichRuns(IUIAutomationTextRange* range, std::vector<RichRun>& runs) {
+    CComPtr<IUIAutomationTextRange> run;
+    if (FAILED(range->Clone(&run)) || !run) return;
+    run->MoveEndpointByRange(TextPatternRangeEndpoint_End, run, TextPatternRangeEndpoint_Start);
+
+    for (int count = 0; count < MAX_RICH_RUNS; ++count) {
+        int order = 0;
+        run->CompareEndpoints(TextPatternRangeEndpoint_Start, range, TextPatternRangeEndpoint_End, &order);
+        if (order >= 0) break;
+        int moved = 0;
+        if (FAILED(run->MoveEndpointByUnit(TextPatternRangeEndpoint_End, TextUnit_Format, 1, &moved)) || moved == 0) break;
+        run->CompareEndpoints(TextPatternRangeEndpoint_End, range, TextPatternRangeEndpoint_End, &order);
+        if (order > 0) run->MoveEndpointByRange(TextPatternRangeEndpoint_End, range, TextPatternRangeEndpoint_End);
+
+        BSTR text = nullptr;
+        run->GetText(-1, &text);
+        RichRun item;
+        if (text) {
+            item.text = toUtf8(text, (int)SysStringLen(text));
+            SysFreeString(text);
+        }
+        if (!item.text.empty()) {
+            item.bold = readIntAttribute(run, UIA_FontWeightAttributeId) >= FW_SEMIBOLD;
+            item.italic = readBoolAttribute(run, UIA_IsItalicAttributeId);
+            item.underline = readIntAttribute(run, UIA_UnderlineStyleAttributeId) != TextDecorationLineStyle_None;
+
+            CComPtr<IUIAutomationElement> enclosing;
+            CONTROLTYPEID type = 0;
+            if (SUCCEEDED(run->GetEnclosingElement(&enclosing)) && enclosing &&
+                SUCCEEDED(enclosing->get_CurrentControlType(&type)) && type == UIA_HyperlinkControlTypeId) {
+                CComPtr<IUIAutomationValuePattern> value;
+                BSTR target = nullptr;
+                if (SUCCEEDED(enclosing->GetCurrentPatternAs(UIA_ValuePatternId, IID_PPV_ARGS(&value))) && value &&
+                    SUCCEEDED(value->get_CurrentValue(&target)) && target) {
+                    item.link = toUtf8(target, (int)SysStringLen(target));
+                    SysFreeString(target);
+                }
+            }
+
+            // Neighbouring units with the same formatting are merged
+            RichRun* last = runs.empty() ? nullptr : &runs.back();
+            if (last && last->bold == item.bold && last->italic == item.italic &&
+                last->underline == item.underline && last->link == item.link) {
+                last->text += item.text;
+            } else {
+                runs.push_back(item);
+            }
+        }
+        run->MoveEndpointByRange(TextPatternRangeEndpoint_Start, run, TextPatternRangeEndpoint_End);
+    }
+}
+
+// Up to `chars` characters immediately before or after the range, clipped to the
+// element's document range
+std::string UIAutomationSelectionMonitor::readAdjacentText(IUIAutomationTextRange* range, bool before, int chars) {
+    CComPtr<IUIAutomationTextRange> adjacent;
+    if (FAILED(range->Clone(&adjacent)) || !adjacent) return "";
+
+    int moved = 0;
+    if (before) {
+        adjacent->MoveEndpointByRange(TextPatternRangeEndpoint_End, range, TextPatternRangeEndpoint_Start);
+        adjacent->MoveEndpointByUnit(TextPatternRangeEndpoint_Start, TextUnit_Character, -chars, &moved);
+    } else {
+        adjacent->MoveEndpointByRange(TextPatternRangeEndpoint_Start, range, TextPatternRangeEndpoint_End);
+        adjacent->MoveEndpointByUnit(TextPatternRangeEndpoint_End, TextUnit_Character, chars, &moved);
+    }
+    if (moved == 0) return "";
 
     BSTR bstr = nullptr;
-    pRange->GetText(-1, &bstr);
+    adjacent->GetText(-1, &bstr);
     if (!bstr) return "";
-
-    // Convert BSTR (wide string) to std::string (UTF-8)
-    std::wstring_view wsv(bstr, SysStringLen(bstr));
-    int size_needed = WideCharToMultiByte(CP_UTF8, 0, &wsv[0], (int)wsv.size(), NULL, 0, NULL, NULL);
-    std::string result(size_needed, 0);
-    WideCharToMultiByte(CP_UTF8, 0, &wsv[0], (int)wsv.size(), &result[0], size_needed, NULL, NULL);
-    
+    std::string text = toUtf8(bstr, (int)SysStringLen(bstr));
     SysFreeString(bstr);
-    return result;
+    return text;
 }
 
-bool UIAutomationSelectionMonitor::isFromCurrentProcess(IUIAutomationElement* element) {
-    if (!element) return false;
+DWORD UIAutomationSelectionMonitor::getProcessId(IUIAutomationElement* element) {
+    if (!element) return 0;
     VARIANT v; VariantInit(&v);
     HRESULT hr = element->GetCurrentPropertyValue(UIA_ProcessIdPropertyId, &v);
-    if (FAILED(hr)) { VariantClear(&v); return false; }
-    bool isCurrent = false;
+    if (FAILED(hr)) { VariantClear(&v); return 0; }
+    DWORD pid = 0;
     if (v.vt == VT_I4 || v.vt == VT_INT) {
-        DWORD pid = (v.vt == VT_I4) ? (DWORD)v.lVal : (DWORD)v.intVal;
-        isCurrent = (pid == GetCurrentProcessId());
+        pid = (v.vt == VT_I4) ? (DWORD)v.lVal : (DWORD)v.intVal;
+    }
+    VariantClear(&v);
+    return pid;
+}
+
+bool UIAutomationSelectionMonitor::isFromCurrentProcess(IUIAutomationElement* element) {
+    DWORD pid = getProcessId(element);
+    return pid != 0 && pid == GetCurrentProcessId();
+}
+
+// The input desktop is not "Default" while the secure desktop (UAC, Ctrl+Alt+Del) is
+// shown - and usually cannot even be opened. Elements owned by processes from another
+// terminal-services session are treated the same way.
+bool UIAutomationSelectionMonitor::isSecureOrForeignContext(DWORD pid) {
+    HDESK desktop = OpenInputDesktop(0, FALSE, DESKTOP_READOBJECTS);
+    if (!desktop) return true;
+
+    wchar_t name[256] = {0};
+    DWORD needed = 0;
+    bool isDefault = GetUserObjectInformationW(desktop, UOI_NAME, name, sizeof(name), &needed) &&
+        _wcsicmp(name, L"Default") == 0;
+    CloseDesktop(desktop);
+    if (!isDefault) return true;
+
+    if (pid == 0) return false;
+    DWORD ownSession = 0, senderSession = 0;
+    if (ProcessIdToSessionId(GetCurrentProcessId(), &ownSession) &&
+        ProcessIdToSessionId(pid, &senderSession)) {
+        return ownSession != senderSession;
+    }
+    return false;
+}
+
+// Track transitions into and out of the secure desktop; returns true while suppressed
+bool UIAutomationSelectionMonitor::updateSecureState(DWORD pid) {
+    if (isSecureOrForeignContext(pid)) {
+        if (!suppressed_secure.exchange(true)) {
+            if (debugEnabled) std::cout << "[UIA] SUPPRESS: Secure desktop or foreign session in foreground" << std::endl;
+            emitState("suppressed-secure-desktop");
+        }
+        return true;
+    }
+    if (suppressed_secure.exchange(false)) {
+        emitState("active");
+    }
+    return false;
+}
+
+void UIAutomationSelectionMonitor::reportInitFailure(const std::string& reason) {
+    {
+        std::lock_guard<std::mutex> lock(init_error_mutex);
+        init_error = reason;
+    }
+    reportError(reason);
+    emitState("failed");
+}
+
+void UIAutomationSelectionMonitor::reportError(const std::string& message) {
+    std::cerr << "[UIA] THREAD: " << message << std::endl;
+    std::function<void(const std::string&)> notify;
+    {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        notify = error_callback;
+    }
+    if (notify) {
+        notify(message);
+    }
+}
+
+// Thread entry point: an exception escaping a std::thread would terminate the whole
+// app, so report it instead and let the thread end
+void UIAutomationSelectionMonitor::runGuarded(const char* threadName, void (UIAutomationSelectionMonitor::*body)()) {
+    try {
+        (this->*body)();
+    } catch (const std::exception& e) {
+        reportError(std::string(threadName) + " thread stopped: " + e.what());
+    } catch (...) {
+        reportError(std::string(threadName) + " thread stopped: unknown exception");
+    }
+    // A worker ended without stop() after initialization succeeded (an exception
+    // above, or the message loop quit on its own); JS restarts the backend
+    if (running.load() && reached_ready.load()) {
+        std::cerr << "[UIA] " << threadName << " thread exited unexpectedly" << std::endl;
+        emitState("backend-exited");
+    }
+}
+
+void UIAutomationSelectionMonitor::emitState(const std::string& state) {
+    std::function<void(const std::string&)> notify;
+    {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        notify = state_callback;
+    }
+    if (notify) {
+        notify(state);
+    }
+}
+
+// A UIA call gave up because the target app did not answer within call_timeout_ms.
+// Reported as an error (at most every few seconds while an app stays hung) so the
+// event is dropped instead of blocking the pipeline.
+bool UIAutomationSelectionMonitor::checkTimeout(HRESULT hr, const char* what) {
+    if (hr != UIA_E_TIMEOUT) return false;
+    ULONGLONG now = GetTickCount64();
+    if (now - last_timeout_report.load() >= 5000) {
+        last_timeout_report.store(now);
+        reportError(describeFailure((std::string(what) + " timed out").c_str(), hr));
+    }
+    return true;
+}
+
+// The app deselected the text we last reported (or a pending one); overlays use
+// this to hide. Emitted once per reported selection.
+void UIAutomationSelectionMonitor::reportCleared() {
+    bool hadPending;
+    {
+        std::lock_guard<std::mutex> lock(debounce_mutex);
+        hadPending = !pending_selection.text.empty();
+        pending_selection = SelectionInfo();
+    }
+    if (selection_reported.exchange(false) || hadPending) {
+        if (debugEnabled) std::cout << "[UIA] EVENT: Selection cleared" << std::endl;
+        emitState("selection-cleared");
+    }
+}
+
+// Caret rectangle of the focused control: the system caret for Win32 controls,
+// otherwise TextPattern2's caret range (UWP, WPF, browsers)
+bool UIAutomationSelectionMonitor::readCaretRect(IUIAutomationElement* element, RECT& rect) {
+    HWND foreground = GetForegroundWindow();
+    GUITHREADINFO gui = { sizeof(GUITHREADINFO) };
+    if (foreground && GetGUIThreadInfo(GetWindowThreadProcessId(foreground, NULL), &gui) && gui.hwndCaret) {
+        rect = gui.rcCaret;
+        MapWindowPoints(gui.hwndCaret, NULL, reinterpret_cast<POINT*>(&rect), 2);
+        return true;
+    }
+
+    CComPtr<IUIAutomationTextPattern2> pattern;
+    if (!element || FAILED(element->GetCurrentPatternAs(UIA_TextPattern2Id, IID_PPV_ARGS(&pattern))) || !pattern) {
+        return false;
+    }
+    BOOL active = FALSE;
+    CComPtr<IUIAutomationTextRange> caret;
+    if (FAILED(pattern->GetCaretRange(&active, &caret)) || !caret || !active) return false;
+
+    // A degenerate range has no rectangle; measure the character after the caret
+    // and keep its leading edge
+    caret->ExpandToEnclosingUnit(TextUnit_Character);
+    SAFEARRAY* rects = nullptr;
+    if (FAILED(caret->GetBoundingRectangles(&rects)) || !rects) return false;
+    double* data = nullptr;
+    LONG upper = -1;
+    bool found = false;
+    SafeArrayGetUBound(rects, 1, &upper);
+    if (upper >= 3 && SUCCEEDED(SafeArrayAccessData(rects, reinterpret_cast<void**>(&data)))) {
+        rect.left = (LONG)data[0];
+        rect.top = (LONG)data[1];
+        rect.right = rect.left + 1;
+        rect.bottom = (LONG)(data[1] + data[3]);
+        found = true;
+        SafeArrayUnaccessData(rects);
+    }
+    SafeArrayDestroy(rects);
+    return found;
+}
+
+// Element handles for follow-up queries by advanced consumers. Only the window
+// handle and runtime ID cross into JS: the COM pointer itself belongs to this
+// thread's apartment and would dangle once the event is handled.
+void UIAutomationSelectionMonitor::describeElement(IUIAutomationElement* element, SelectionInfo& info) {
+    if (!expose_handles.load() || !element) return;
+
+    UIA_HWND handle = nullptr;
+    CComPtr<IUIAutomationElement> current = element;
+    CComPtr<IUIAutomationTreeWalker> walker;
+    if (pAutomation) pAutomation->get_ControlViewWalker(&walker);
+    // Windowless elements (browser content, WPF) report no handle; use their host's
+    for (int depth = 0; current && depth < 32; ++depth) {
+        if (SUCCEEDED(current->get_CurrentNativeWindowHandle(&handle)) && handle) break;
+        CComPtr<IUIAutomationElement> parent;
+        if (!walker || FAILED(walker->GetParentElement(current, &parent))) break;
+        current = parent;
+    }
+    info.hwnd = (HWND)handle;
+
+    SAFEARRAY* runtimeId = nullptr;
+    if (SUCCEEDED(element->GetRuntimeId(&runtimeId)) && runtimeId) {
+        LONG lower = 0, upper = -1;
+        SafeArrayGetLBound(runtimeId, 1, &lower);
+        SafeArrayGetUBound(runtimeId, 1, &upper);
+        for (LONG i = lower; i <= upper; ++i) {
+            int part = 0;
+            if (SUCCEEDED(SafeArrayGetElement(runtimeId, &i, &part))) info.runtimeId.push_back(part);
+        }
+        SafeArrayDestroy(runtimeId);
+    }
+}
+
+// Caret tracking: report the insertion point if it moved since the last report
+void UIAutomationSelectionMonitor::reportCaret(IUIAutomationElement* element, const std::string& app) {
+    CaretInfo caret;
+    if (!readCaretRect(element, caret.rect)) return;
+    if (EqualRect(&caret.rect, &last_caret)) return;
+    last_caret = caret.rect;
+    caret.app = app;
+
+    std::function<void(const CaretInfo&)> notify;
+    {
+        std::lock_guard<std::mutex> lock(callback_mutex);
+        notify = caret_callback;
+    }
+    if (notify) {
+        notify(caret);
+    }
+}
+
+// True while an IME composition (CJK pre-edit text) is active in the element.
+// Uses the TextEdit pattern's active composition (Windows 8.1+), which TSF-aware
+// controls such as RichEdit, XAML, WPF and Chromium expose; plain IMM32 controls
+// have no cross-process equivalent and always read as not composing.
+bool UIAutomationSelectionMonitor::isComposing(IUIAutomationElement* element) {
+    CComPtr<IUIAutomationTextEditPattern> pattern;
+    if (!element || FAILED(element->GetCurrentPatternAs(UIA_TextEditPatternId, IID_PPV_ARGS(&pattern))) || !pattern) {
+        return false;
+    }
+    CComPtr<IUIAutomationTextRange> composition;
+    if (FAILED(pattern->GetActiveComposition(&composition)) || !composition) return false;
+    BSTR text = nullptr;
+    bool active = SUCCEEDED(composition->GetText(1, &text)) && text && SysStringLen(text) > 0;
+    if (text) SysFreeString(text);
+    return active;
+}
+
+bool UIAutomationSelectionMonitor::isPasswordField(IUIAutomationElement* element) {
+    if (!element) return false;
+    VARIANT v; VariantInit(&v);
+    bool isPassword = false;
+    if (SUCCEEDED(element->GetCurrentPropertyValue(UIA_IsPasswordPropertyId, &v))) {
+        isPassword = (v.vt == VT_BOOL && v.boolVal == VARIANT_TRUE);
     }
     VariantClear(&v);
-    return isCurrent;
+    return isPassword;
+}
+
+// Executable file name (e.g. "notepad.exe") of a process, or "" if it cannot be queried
+std::string UIAutomationSelectionMonitor::getProcessName(DWORD pid) {
+    if (pid == 0) return "";
+    HANDLE process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid);
+    if (!process) return "";
+
+    wchar_t path[MAX_PATH];
+    DWORD size = MAX_PATH;
+    std::string name;
+    if (QueryFullProcessImageNameW(process, 0, path, &size)) {
+        std::wstring fullPath(path, size);
+        size_t slash = fullPath.find_last_of(L"\\/");
+        std::wstring fileName = (slash == std::wstring::npos) ? fullPath : fullPath.substr(slash + 1);
+        name = toUtf8(fileName.c_str(), (int)fileName.size());
+    }
+    CloseHandle(process);
+    return name;
+}
+
+bool UIAutomationSelectionMonitor::isAppAllowed(const std::string& processName) {
+    std::lock_guard<std::mutex> lock(filter_mutex);
+    if (include_apps.empty() && exclude_apps.empty()) return true;
+
+    // Fail closed when filters are configured but the process cannot be identified
+    if (processName.empty()) return false;
+
+    std::string name = normalizeAppName(processName);
+    if (std::find(exclude_apps.begin(), exclude_apps.end(), name) != exclude_apps.end()) return false;
+    if (include_apps.empty()) return true;
+    return std::find(include_apps.begin(), include_apps.end(), name) != include_apps.end();
+}
+
+// UIA events carry no input information, so attribute the selection to the keyboard
+// when a selection modifier (Shift/Ctrl) is held without the left mouse button.
+bool UIAutomationSelectionMonitor::isTriggerEnabled() {
+    int mask = trigger_mask.load();
+    if ((mask & (TRIGGER_MOUSE | TRIGGER_KEYBOARD)) == (TRIGGER_MOUSE | TRIGGER_KEYBOARD)) return true;
+
+    bool mouseDown = (GetAsyncKeyState(VK_LBUTTON) & 0x8000) != 0;
+    bool modifierDown = (GetAsyncKeyState(VK_SHIFT) & 0x8000) != 0 || (GetAsyncKeyState(VK_CONTROL) & 0x8000) != 0;
+    bool fromKeyboard = modifierDown && !mouseDown;
+    return fromKeyboard ? (mask & TRIGGER_KEYBOARD) != 0 : (mask & TRIGGER_MOUSE) != 0;
 }
 
 // Attempt to retrieve selection text from focused element or element under cursor
@@ -416,7 +2066,8 @@ std::string UIAutomationSelectionMonitor::getSelectedTextFromFocusedOrPoint() {
 
     // Try focused element first
     CComPtr<IUIAutomationElement> focused;
-    if (SUCCEEDED(pAutomation->GetFocusedElement(&focused)) && focused) {
+    if (SUCCEEDED(pAutomation->GetFocusedElement(&focused)) && focused && !isPasswordField(focused) &&
+        isAppAllowed(getProcessName(getProcessId(focused)))) {
         std::string text = getSelectedTextFromElement(focused);
         if (!text.empty()) return text;
     }
@@ -424,7 +2075,8 @@ std::string UIAutomationSelectionMonitor::getSelectedTextFromFocusedOrPoint() {
     // Try element under cursor
     POINT pt; GetCursorPos(&pt);
     CComPtr<IUIAutomationElement> atPoint;
-    if (SUCCEEDED(pAutomation->ElementFromPoint(pt, &atPoint)) && atPoint) {
+    if (SUCCEEDED(pAutomation->ElementFromPoint(pt, &atPoint)) && atPoint && !isPasswordField(atPoint) &&
+        isAppAllowed(getProcessName(getProcessId(atPoint)))) {
         std::string text = getSelectedTextFromElement(atPoint);
         if (!text.empty()) return text;
     }
@@ -457,9 +2109,88 @@ CComPtr<IUIAutomationElement> UIAutomationSelectionMonitor::findAncestorWithText
     return nullptr;
 }
 
-// Try to compute the geometric center of the selected text using UIA bounding rectangles
-bool UIAutomationSelectionMonitor::getSelectionCenter(IUIAutomationElement* element, int& outX, int& outY) {
-    outX = 0; outY = 0;
+// The focused element with a TextPattern somewhere below start, if any
+CComPtr<IUIAutomationElement> UIAutomationSelectionMonitor::findFocusedDescendantWithTextPattern(IUIAutomationElement* start) {
+    if (!start || !pAutomation) return nullptr;
+
+    VARIANT yes; VariantInit(&yes);
+    yes.vt = VT_BOOL;
+    yes.boolVal = VARIANT_TRUE;
+    CComPtr<IUIAutomationCondition> hasText, hasFocus, both;
+    if (FAILED(pAutomation->CreatePropertyCondition(UIA_IsTextPatternAvailablePropertyId, yes, &hasText)) ||
+        FAILED(pAutomation->CreatePropertyCondition(UIA_HasKeyboardFocusPropertyId, yes, &hasFocus)) ||
+        FAILED(pAutomation->CreateAndCondition(hasText, hasFocus, &both))) {
+        return nullptr;
+    }
+
+    CComPtr<IUIAutomationElement> found;
+    if (FAILED(start->FindFirst(TreeScope_Descendants, both, &found))) return nullptr;
+    return found;
+}
+
+// Last resort for controls without TextPattern: the whole value via ValuePattern
+// or MSAA (LegacyIAccessiblePattern). Only used for explicit captures, since the
+// value is not necessarily what the user selected.
+bool UIAutomationSelectionMonitor::readValueFallback(IUIAutomationElement* element, std::string& value) {
+    if (!element) return false;
+    BSTR bstr = nullptr;
+
+    CComPtr<IUIAutomationValuePattern> valuePattern;
+    if (SUCCEEDED(element->GetCurrentPattern(UIA_ValuePatternId, (IUnknown**)&valuePattern)) && valuePattern) {
+        valuePattern->get_CurrentValue(&bstr);
+    }
+    if (!bstr || SysStringLen(bstr) == 0) {
+        if (bstr) { SysFreeString(bstr); bstr = nullptr; }
+        CComPtr<IUIAutomationLegacyIAccessiblePattern> legacy;
+        if (SUCCEEDED(element->GetCurrentPattern(UIA_LegacyIAccessiblePatternId, (IUnknown**)&legacy)) && legacy) {
+            legacy->get_CurrentValue(&bstr);
+        }
+    }
+    if (!bstr) return false;
+    value = toUtf8(bstr, (int)SysStringLen(bstr));
+    SysFreeString(bstr);
+    return !value.empty();
+}
+
+// Chromium (Chrome, Edge, Electron apps) only builds its accessibility tree once an
+// assistive client asks for it. A WM_GETOBJECT to the top-level and renderer
+// windows switches it on, so later reads and UIA events carry text.
+void UIAutomationSelectionMonitor::enableChromiumAccessibility(IUIAutomationElement* element) {
+    UIA_HWND handle = nullptr;
+    HWND hwnd = NULL;
+    if (element && SUCCEEDED(element->get_CurrentNativeWindowHandle(&handle)) && handle) {
+        hwnd = GetAncestor((HWND)handle, GA_ROOT);
+    }
+    if (!hwnd) hwnd = GetForegroundWindow();
+    if (!hwnd) return;
+
+    wchar_t className[64] = {0};
+    if (GetClassNameW(hwnd, className, 64) <= 0 || wcsncmp(className, L"Chrome_WidgetWin_", 17) != 0) return;
+
+    {
+        std::lock_guard<std::mutex> lock(chromium_mutex);
+        chromium_windows.erase(std::remove_if(chromium_windows.begin(), chromium_windows.end(),
+            [](HWND window) { return !IsWindow(window); }), chromium_windows.end());
+        if (std::find(chromium_windows.begin(), chromium_windows.end(), hwnd) != chromium_windows.end()) return;
+        chromium_windows.push_back(hwnd);
+    }
+
+    DWORD_PTR result = 0;
+    sendWithTimeout(hwnd, WM_GETOBJECT, 0, (LPARAM)OBJID_CLIENT, &result);
+    EnumChildWindows(hwnd, [](HWND child, LPARAM) -> BOOL {
+        wchar_t childClass[64] = {0};
+        if (GetClassNameW(child, childClass, 64) > 0 && wcscmp(childClass, L"Chrome_RenderWidgetHostHWND") == 0) {
+            DWORD_PTR ignored = 0;
+            sendWithTimeout(child, WM_GETOBJECT, 0, (LPARAM)OBJID_CLIENT, &ignored);
+        }
+        return TRUE;
+    }, 0);
+    if (debugEnabled) std::cout << "[UIA] Enabled Chromium accessibility for window " << hwnd << std::endl;
+}
+
+// Compute the anchor point and bounding rectangle of the selected text using UIA bounding rectangles
+bool UIAutomationSelectionMonitor::getSelectionBounds(IUIAutomationElement* element, SelectionInfo& info) {
+    info.x = 0; info.y = 0; info.hasBounds = false;
     if (!element) return false;
 
     CComPtr<IUIAutomationTextPattern> pTextPattern;
@@ -508,7 +2239,7 @@ bool UIAutomationSelectionMonitor::getSelectionCenter(IUIAutomationElement* elem
 
     // Each rectangle: left, top, width, height
     double sumX = 0.0, sumY = 0.0; int rectCount = 0;
-    double minLeft = 1e12, minTop = 1e12;
+    double minLeft = 1e12, minTop = 1e12, maxRight = -1e12, maxBottom = -1e12;
     for (LONG i = 0; i + 3 < count; i += 4) {
         double left = data[i];
         double top = data[i+1];
@@ -520,6 +2251,8 @@ bool UIAutomationSelectionMonitor::getSelectionCenter(IUIAutomationElement* elem
         rectCount++;
         if (left < minLeft) minLeft = left;
         if (top < minTop) minTop = top;
+        if (left + width > maxRight) maxRight = left + width;
+        if (top + height > maxBottom) maxBottom = top + height;
     }
 
     SafeArrayUnaccessData(rects);
@@ -528,24 +2261,154 @@ bool UIAutomationSelectionMonitor::getSelectionCenter(IUIAutomationElement* elem
     if (rectCount == 0) return false;
 
     // Prefer left/top anchor for precise popup placement near selection start
-    outX = static_cast<int>(minLeft);
-    outY = static_cast<int>(minTop);
+    info.x = static_cast<int>(minLeft);
+    info.y = static_cast<int>(minTop);
+    info.hasBounds = true;
+    info.left = minLeft;
+    info.top = minTop;
+    info.width = maxRight - minLeft;
+    info.height = maxBottom - minTop;
     return true;
 }
 
+// Promise-returning variants of start/stop/getSelectionDetails run on the libuv
+// thread pool so a slow hook registration, thread join or accessibility query
+// never blocks the JS thread
+
+// Holds a reference to the JS wrapper so the monitor outlives the work
+class MonitorWorker : public Napi::AsyncWorker {
+public:
+    MonitorWorker(Napi::Object owner, UIAutomationSelectionMonitor* monitor)
+        : Napi::AsyncWorker(owner.Env()), deferred(Napi::Promise::Deferred::New(owner.Env())),
+          owner(Napi::Persistent(owner)), monitor(monitor) {}
+
+    Napi::Promise Promise() { return deferred.Promise(); }
+
+    void OnError(const Napi::Error& error) override { deferred.Reject(error.Value()); }
+
+protected:
+    Napi::Promise::Deferred deferred;
+    Napi::ObjectReference owner;
+    UIAutomationSelectionMonitor* monitor;
+};
+
+class StartWorker : public MonitorWorker {
+public:
+    StartWorker(Napi::Object owner, UIAutomationSelectionMonitor* monitor, int timeoutMs)
+        : MonitorWorker(owner, monitor), timeoutMs(timeoutMs) {}
+
+    void Execute() override {
+        bool started = monitor->start();
+        if (!started || !monitor->wait_until_ready(timeoutMs)) {
+            if (started) {
+                // Join the threads start() spawned before reporting the failure
+                monitor->stop();
+            }
+            std::string reason = monitor->get_init_error();
+            SetError(reason.empty() ? "UIAutomation did not become ready within " + std::to_string(timeoutMs) + "ms" : reason);
+        }
+    }
+
+    void OnOK() override { deferred.Resolve(Napi::Boolean::New(Env(), true)); }
+
+private:
+    int timeoutMs;
+};
+
+class StopWorker : public MonitorWorker {
+public:
+    StopWorker(Napi::Object owner, UIAutomationSelectionMonitor* monitor) : MonitorWorker(owner, monitor) {}
+
+    void Execute() override { stopped = monitor->stop(); }
+
+    void OnOK() override { deferred.Resolve(Napi::Boolean::New(Env(), stopped)); }
+
+private:
+    bool stopped = false;
+};
+
+class TextAtPointWorker : public MonitorWorker {
+public:
+    TextAtPointWorker(Napi::Object owner, UIAutomationSelectionMonitor* monitor, POINT point, int chars, int timeoutMs)
+        : MonitorWorker(owner, monitor), point(point), chars(chars), timeoutMs(timeoutMs) {}
+
+    void Execute() override {
+        if (!monitor->read_text_at_point(point.x, point.y, chars, timeoutMs, text)) {
+            SetError("Text read did not complete within " + std::to_string(timeoutMs) + "ms");
+        }
+    }
+
+    void OnOK() override {
+        Napi::Env env = Env();
+        if (text.contextBefore.empty() && text.contextAfter.empty()) {
+            deferred.Resolve(env.Null());
+            return;
+        }
+        Napi::Object payload = Napi::Object::New(env);
+        payload.Set("before", Napi::String::New(env, text.contextBefore));
+        payload.Set("after", Napi::String::New(env, text.contextAfter));
+        payload.Set("app", Napi::String::New(env, text.app));
+        deferred.Resolve(payload);
+    }
+
+private:
+    POINT point;
+    int chars;
+    int timeoutMs;
+    SelectionInfo text;
+};
+
+class ReadSelectionWorker : public MonitorWorker {
+public:
+    ReadSelectionWorker(Napi::Object owner, UIAutomationSelectionMonitor* monitor, int timeoutMs)
+        : MonitorWorker(owner, monitor), timeoutMs(timeoutMs) {}
+
+    void Execute() override {
+        if (!monitor->read_selection_now(timeoutMs, selection)) {
+            SetError("Selection read did not complete within " + std::to_string(timeoutMs) + "ms");
+        }
+    }
+
+    void OnOK() override;
+
+private:
+    int timeoutMs;
+    SelectionInfo selection;
+};
+
 // NAPI wrapper class
 class UIAutomationSelectionMonitorWrapper : public Napi::ObjectWrap<UIAutomationSelectionMonitorWrapper> {
 private:
+    // Each JS instance owns an independent monitor, so the addon can be loaded in
+    // several worker threads or Electron contexts at once
     UIAutomationSelectionMonitor* monitor;
+    napi_env env_;
     Napi::ThreadSafeFunction callback_tsfn;
+    Napi::ThreadSafeFunction state_tsfn;
+    Napi::ThreadSafeFunction error_tsfn;
+    Napi::ThreadSafeFunction caret_tsfn;
 
 public:
     static Napi::Object Init(Napi::Env env, Napi::Object exports) {
         Napi::Function func = DefineClass(env, "UIAutomationSelectionMonitor", {
             InstanceMethod("start", &UIAutomationSelectionMonitorWrapper::Start),
             InstanceMethod("stop", &UIAutomationSelectionMonitorWrapper::Stop),
+            InstanceMethod("startAsync", &UIAutomationSelectionMonitorWrapper::StartAsync),
+            InstanceMethod("stopAsync", &UIAutomationSelectionMonitorWrapper::StopAsync),
+            InstanceMethod("getSelectionAsync", &UIAutomationSelectionMonitorWrapper::GetSelectionAsync),
+            InstanceMethod("getTextAtPointAsync", &UIAutomationSelectionMonitorWrapper::GetTextAtPointAsync),
             InstanceMethod("getCurrentSelection", &UIAutomationSelectionMonitorWrapper::GetCurrentSelection),
+            InstanceMethod("getSelectionDetails", &UIAutomationSelectionMonitorWrapper::GetSelectionDetails),
             InstanceMethod("setCallback", &UIAutomationSelectionMonitorWrapper::SetCallback),
+            InstanceMethod("setStateCallback", &UIAutomationSelectionMonitorWrapper::SetStateCallback),
+            InstanceMethod("setErrorCallback", &UIAutomationSelectionMonitorWrapper::SetErrorCallback),
+            InstanceMethod("setCaretCallback", &UIAutomationSelectionMonitorWrapper::SetCaretCallback),
+            InstanceMethod("configure", &UIAutomationSelectionMonitorWrapper::Configure),
+            InstanceMethod("setPaused", &UIAutomationSelectionMonitorWrapper::SetPaused),
+            InstanceMethod("getCaptureMethod", &UIAutomationSelectionMonitorWrapper::GetCaptureMethod),
+            InstanceMethod("requestCapture", &UIAutomationSelectionMonitorWrapper::RequestCapture),
+            InstanceMethod("isSessionReady", &UIAutomationSelectionMonitorWrapper::IsSessionReady),
+            InstanceMethod("getInitError", &UIAutomationSelectionMonitorWrapper::GetInitError),
             InstanceMethod("testFocusedElement", &UIAutomationSelectionMonitorWrapper::TestFocusedElement),
         });
 
@@ -554,13 +2417,46 @@ public:
     }
 
     UIAutomationSelectionMonitorWrapper(const Napi::CallbackInfo& info) 
-        : Napi::ObjectWrap<UIAutomationSelectionMonitorWrapper>(info), monitor(nullptr) {
+        : Napi::ObjectWrap<UIAutomationSelectionMonitorWrapper>(info), monitor(nullptr), env_(info.Env()) {
         monitor = new UIAutomationSelectionMonitor();
+        napi_add_env_cleanup_hook(env_, &UIAutomationSelectionMonitorWrapper::StopOnTeardown, this);
+    }
+
+    // A worker thread or context is being torn down while this monitor runs:
+    // stop the native threads before the environment they call into goes away
+    static void StopOnTeardown(void* arg) {
+        auto* self = static_cast<UIAutomationSelectionMonitorWrapper*>(arg);
+        if (self->monitor) {
+            self->monitor->stop();
+        }
     }
 
     ~UIAutomationSelectionMonitorWrapper() {
+        napi_remove_env_cleanup_hook(env_, &UIAutomationSelectionMonitorWrapper::StopOnTeardown, this);
+        // Join the native threads first so nothing can call into the
+        // thread-safe function after it has been released
         if (monitor) {
+            monitor->stop();
+            if (monitor->abandoned_threads()) {
+                // A hung thread still references the monitor and the callbacks;
+                // leak both rather than free them under it
+                monitor = nullptr;
+                return;
+            }
             delete monitor;
+            monitor = nullptr;
+        }
+        if (callback_tsfn) {
+            callback_tsfn.Release();
+        }
+        if (state_tsfn) {
+            state_tsfn.Release();
+        }
+        if (error_tsfn) {
+            error_tsfn.Release();
+        }
+        if (caret_tsfn) {
+            caret_tsfn.Release();
         }
     }
 
@@ -581,8 +2477,82 @@ public:
             return env.Null();
         }
 
-        monitor->stop();
-        return env.Null();
+        return Napi::Boolean::New(env, monitor->stop());
+    }
+
+    static int TimeoutArg(const Napi::CallbackInfo& info, int fallback) {
+        if (info.Length() > 0 && info[0].IsNumber()) {
+            int ms = info[0].As<Napi::Number>().Int32Value();
+            return ms < 0 ? 0 : ms;
+        }
+        return fallback;
+    }
+
+    // startAsync(readyTimeoutMs?): resolves once the monitor thread is ready,
+    // rejects with the initialization error otherwise
+    Napi::Value StartAsync(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (!monitor) {
+            auto deferred = Napi::Promise::Deferred::New(env);
+            deferred.Reject(Napi::Error::New(env, "Monitor not initialized").Value());
+            return deferred.Promise();
+        }
+        auto* worker = new StartWorker(info.This().As<Napi::Object>(), monitor, TimeoutArg(info, 5000));
+        Napi::Promise promise = worker->Promise();
+        worker->Queue();
+        return promise;
+    }
+
+    // stopAsync(): resolves to stop()'s result
+    Napi::Value StopAsync(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (!monitor) {
+            auto deferred = Napi::Promise::Deferred::New(env);
+            deferred.Resolve(Napi::Boolean::New(env, true));
+            return deferred.Promise();
+        }
+        auto* worker = new StopWorker(info.This().As<Napi::Object>(), monitor);
+        Napi::Promise promise = worker->Promise();
+        worker->Queue();
+        return promise;
+    }
+
+    // getSelectionAsync(timeoutMs?): reads the focused element's selection now
+    // (without delivering it); resolves to the same shape as getSelectionDetails()
+    Napi::Value GetSelectionAsync(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (!monitor) {
+            auto deferred = Napi::Promise::Deferred::New(env);
+            deferred.Reject(Napi::Error::New(env, "Monitor not initialized").Value());
+            return deferred.Promise();
+        }
+        auto* worker = new ReadSelectionWorker(info.This().As<Napi::Object>(), monitor, TimeoutArg(info, 2000));
+        Napi::Promise promise = worker->Promise();
+        worker->Queue();
+        return promise;
+    }
+
+    // getTextAtPointAsync(x, y, chars?, timeoutMs?): text on either side of a
+    // screen point (physical pixels) as { before, after, app }, or null
+    Napi::Value GetTextAtPointAsync(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        auto deferred = Napi::Promise::Deferred::New(env);
+        if (!monitor) {
+            deferred.Reject(Napi::Error::New(env, "Monitor not initialized").Value());
+            return deferred.Promise();
+        }
+        if (info.Length() < 2 || !info[0].IsNumber() || !info[1].IsNumber()) {
+            deferred.Reject(Napi::TypeError::New(env, "Expected screen coordinates (x, y)").Value());
+            return deferred.Promise();
+        }
+        POINT point = { info[0].As<Napi::Number>().Int32Value(), info[1].As<Napi::Number>().Int32Value() };
+        int chars = info.Length() > 2 && info[2].IsNumber() ? info[2].As<Napi::Number>().Int32Value() : 100;
+        int timeoutMs = info.Length() > 3 && info[3].IsNumber() ? info[3].As<Napi::Number>().Int32Value() : 2000;
+        auto* worker = new TextAtPointWorker(info.This().As<Napi::Object>(), monitor, point,
+            chars < 1 ? 1 : chars, timeoutMs < 0 ? 0 : timeoutMs);
+        Napi::Promise promise = worker->Promise();
+        worker->Queue();
+        return promise;
     }
 
     Napi::Value GetCurrentSelection(const Napi::CallbackInfo& info) {
@@ -595,6 +2565,100 @@ public:
         return Napi::String::New(env, selection);
     }
 
+    Napi::Value GetSelectionDetails(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (!monitor) {
+            return env.Null();
+        }
+
+        SelectionInfo selection = monitor->get_selection_details();
+        if (selection.text.empty()) {
+            return env.Null();
+        }
+        return ToJsObject(env, selection);
+    }
+
+    static std::vector<std::string> ToStringList(Napi::Value value) {
+        std::vector<std::string> list;
+        if (!value.IsArray()) return list;
+        Napi::Array array = value.As<Napi::Array>();
+        for (uint32_t i = 0; i < array.Length(); ++i) {
+            Napi::Value item = array.Get(i);
+            if (item.IsString()) list.push_back(item.As<Napi::String>().Utf8Value());
+        }
+        return list;
+    }
+
+    static Napi::Object ToJsObject(Napi::Env env, const SelectionInfo& selection) {
+        Napi::Object payload = Napi::Object::New(env);
+        payload.Set("text", Napi::String::New(env, selection.text));
+        payload.Set("x", Napi::Number::New(env, selection.x));
+        payload.Set("y", Napi::Number::New(env, selection.y));
+        payload.Set("app", Napi::String::New(env, selection.app));
+        payload.Set("requested", Napi::Boolean::New(env, selection.requested));
+        payload.Set("granularity", Napi::String::New(env, selection.granularity));
+        payload.Set("truncated", Napi::Boolean::New(env, selection.truncated));
+        payload.Set("contextBefore", Napi::String::New(env, selection.contextBefore));
+        payload.Set("contextAfter", Napi::String::New(env, selection.contextAfter));
+        Napi::Array ranges = Napi::Array::New(env, selection.ranges.size());
+        for (size_t i = 0; i < selection.ranges.size(); ++i) {
+            ranges.Set((uint32_t)i, Napi::String::New(env, selection.ranges[i]));
+        }
+        payload.Set("ranges", ranges);
+        if (selection.unreadable) {
+            payload.Set("unreadable", Napi::Boolean::New(env, true));
+        }
+        if (selection.composing) {
+            payload.Set("composing", Napi::Boolean::New(env, true));
+        }
+        if (!selection.direction.empty()) {
+            payload.Set("direction", Napi::String::New(env, selection.direction));
+        }
+        if (!selection.runs.empty()) {
+            Napi::Array runs = Napi::Array::New(env, selection.runs.size());
+            for (size_t i = 0; i < selection.runs.size(); ++i) {
+                const RichRun& run = selection.runs[i];
+                Napi::Object item = Napi::Object::New(env);
+                item.Set("text", Napi::String::New(env, run.text));
+                if (run.bold) item.Set("bold", Napi::Boolean::New(env, true));
+                if (run.italic) item.Set("italic", Napi::Boolean::New(env, true));
+                if (run.underline) item.Set("underline", Napi::Boolean::New(env, true));
+                if (!run.link.empty()) item.Set("link", Napi::String::New(env, run.link));
+                runs.Set((uint32_t)i, item);
+            }
+            payload.Set("runs", runs);
+        }
+        if (selection.hwnd || !selection.runtimeId.empty()) {
+            // A handle is pointer-sized; as a decimal string it survives the trip through a JS number
+            Napi::Object element = Napi::Object::New(env);
+            element.Set("hwnd", Napi::String::New(env, std::to_string(reinterpret_cast<uintptr_t>(selection.hwnd))));
+            Napi::Array runtimeId = Napi::Array::New(env, selection.runtimeId.size());
+            for (size_t i = 0; i < selection.runtimeId.size(); ++i) {
+                runtimeId.Set((uint32_t)i, Napi::Number::New(env, selection.runtimeId[i]));
+            }
+            element.Set("runtimeId", runtimeId);
+            payload.Set("element", element);
+        }
+        if (!selection.files.empty()) {
+            Napi::Array files = Napi::Array::New(env, selection.files.size());
+            for (size_t i = 0; i < selection.files.size(); ++i) {
+                files.Set((uint32_t)i, Napi::String::New(env, selection.files[i]));
+            }
+            payload.Set("files", files);
+        }
+        if (selection.hasBounds) {
+            Napi::Object bounds = Napi::Object::New(env);
+            bounds.Set("x", Napi::Number::New(env, selection.left));
+            bounds.Set("y", Napi::Number::New(env, selection.top));
+            bounds.Set("width", Napi::Number::New(env, selection.width));
+            bounds.Set("height", Napi::Number::New(env, selection.height));
+            payload.Set("bounds", bounds);
+        } else {
+            payload.Set("bounds", env.Null());
+        }
+        return payload;
+    }
+
     Napi::Value SetCallback(const Napi::CallbackInfo& info) {
         Napi::Env env = info.Env();
         if (info.Length() < 1 || !info[0].IsFunction()) {
@@ -603,22 +2667,198 @@ public:
         }
 
         Napi::Function callback = info[0].As<Napi::Function>();
-        callback_tsfn = Napi::ThreadSafeFunction::New(env, callback, "UIAutomationCallback", 0, 1);
-
-        monitor->set_callback([this](std::string text, int x, int y) {
-            auto callback = [text, x, y](Napi::Env env, Napi::Function jsCallback) {
-                jsCallback.Call({
-                    Napi::String::New(env, text),
-                    Napi::Number::New(env, x),
-                    Napi::Number::New(env, y)
-                });
+        Napi::ThreadSafeFunction tsfn = Napi::ThreadSafeFunction::New(env, callback, "UIAutomationCallback", 0, 1);
+        // Don't keep the Node/Electron event loop alive just because a callback is registered
+        tsfn.Unref(env);
+
+        monitor->set_callback([tsfn](const SelectionInfo& selection) mutable {
+            auto callback = [selection](Napi::Env env, Napi::Function jsCallback) {
+                jsCallback.Call({ ToJsObject(env, selection) });
             };
-            callback_tsfn.BlockingCall(callback);
+            tsfn.BlockingCall(callback);
+        });
+
+        // Release the previously registered callback, if any
+        if (callback_tsfn) {
+            callback_tsfn.Release();
+        }
+        callback_tsfn = tsfn;
+
+        return env.Null();
+    }
+
+    Napi::Value SetStateCallback(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (info.Length() < 1 || !info[0].IsFunction()) {
+            Napi::Error::New(env, "Function expected").ThrowAsJavaScriptException();
+            return env.Null();
+        }
+
+        Napi::ThreadSafeFunction tsfn = Napi::ThreadSafeFunction::New(env, info[0].As<Napi::Function>(), "UIAutomationStateCallback", 0, 1);
+        tsfn.Unref(env);
+
+        monitor->set_state_callback([tsfn](const std::string& state) mutable {
+            tsfn.BlockingCall([state](Napi::Env env, Napi::Function jsCallback) {
+                jsCallback.Call({ Napi::String::New(env, state) });
+            });
+        });
+
+        if (state_tsfn) {
+            state_tsfn.Release();
+        }
+        state_tsfn = tsfn;
+
+        return env.Null();
+    }
+
+    Napi::Value SetErrorCallback(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (info.Length() < 1 || !info[0].IsFunction()) {
+            Napi::Error::New(env, "Function expected").ThrowAsJavaScriptException();
+            return env.Null();
+        }
+
+        Napi::ThreadSafeFunction tsfn = Napi::ThreadSafeFunction::New(env, info[0].As<Napi::Function>(), "UIAutomationErrorCallback", 0, 1);
+        tsfn.Unref(env);
+
+        monitor->set_error_callback([tsfn](const std::string& message) mutable {
+            tsfn.BlockingCall([message](Napi::Env env, Napi::Function jsCallback) {
+                jsCallback.Call({ Napi::String::New(env, message) });
+            });
         });
 
+        if (error_tsfn) {
+            error_tsfn.Release();
+        }
+        error_tsfn = tsfn;
+
         return env.Null();
     }
 
+    Napi::Value SetCaretCallback(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (info.Length() < 1 || !info[0].IsFunction()) {
+            Napi::Error::New(env, "Function expected").ThrowAsJavaScriptException();
+            return env.Null();
+        }
+
+        Napi::ThreadSafeFunction tsfn = Napi::ThreadSafeFunction::New(env, info[0].As<Napi::Function>(), "UIAutomationCaretCallback", 0, 1);
+        tsfn.Unref(env);
+
+        monitor->set_caret_callback([tsfn](const CaretInfo& caret) mutable {
+            tsfn.BlockingCall([caret](Napi::Env env, Napi::Function jsCallback) {
+                Napi::Object payload = Napi::Object::New(env);
+                payload.Set("x", Napi::Number::New(env, caret.rect.left));
+                payload.Set("y", Napi::Number::New(env, caret.rect.top));
+                payload.Set("width", Napi::Number::New(env, caret.rect.right - caret.rect.left));
+                payload.Set("height", Napi::Number::New(env, caret.rect.bottom - caret.rect.top));
+                payload.Set("app", Napi::String::New(env, caret.app));
+                jsCallback.Call({ payload });
+            });
+        });
+
+        if (caret_tsfn) {
+            caret_tsfn.Release();
+        }
+        caret_tsfn = tsfn;
+
+        return env.Null();
+    }
+
+    Napi::Value SetPaused(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (info.Length() < 1 || !info[0].IsBoolean()) {
+            Napi::Error::New(env, "Boolean expected").ThrowAsJavaScriptException();
+            return env.Null();
+        }
+
+        monitor->set_paused(info[0].As<Napi::Boolean>().Value());
+        return env.Null();
+    }
+
+    Napi::Value Configure(const Napi::CallbackInfo& info) {
+        Napi::Env env = info.Env();
+        if (info.Length() < 1 || !info[0].IsObject()) {
+            Napi::Error::New(env, "Options object expected").ThrowAsJavaScriptException();
+            return env.Null();
+        }
+
+        Napi::Object options = info[0].As<Napi::Object>();
+        if (options.Has("debounceMs") && options.Get("debounceMs").IsNumber()) {
+            monitor->set_debounce_delay(options.Get("debounceMs").As<Napi::Number>().Int32Value());
+        }
+        if (options.Has("triggers") && options.Get("triggers").IsString()) {
+            std::string triggers = options.Get("triggers").As<Napi::String>().Utf8Value();
+            int mask = UIAutomationSelectionMonitor::TRIGGER_MOUSE | UIAutomationSelectionMonitor::TRIGGER_KEYBOARD;
+            if (triggers == "mouse-up") mask = UIAutomationSelectionMonitor::TRIGGER_MOUSE;
+            else if (triggers == "key-up") mask = UIAutomationSelectionMonitor::TRIGGER_KEYBOARD;
+            monitor->set_trigger_mask(mask);
+        }
+        if (options.Has("mouseHook") && options.Get("mouseHook").IsBoolean()) {
+            monitor->set_mouse_hook_enabled(options.Get("mouseHook").As<Napi::Boolean>().Value());
+        }
+        if (options.Has("maxReadLength") && options.Get("maxReadLength").IsNumber()) {
+            monitor->set_max_read_length(options.Get("maxReadLength").As<Napi::Number>().Int32Value());
+        }
+        if (options.Has("contextChars") && options.Get("contextChars").IsNumber()) {
+            monitor->set_context_chars(options.Get("contextChars").As<Napi::Number>().Int32Value());
+        }
+        if (options.Has("trackCaret") && options.Get("trackCaret").IsBoolean()) {
+            monitor->set_track_caret(options.Get("trackCaret").As<Napi::Boolean>().Value());
+        }
+        if (options.Has("eventSource") && options.Get("eventSource").IsString()) {
+            monitor->set_use_win_events(options.Get("eventSource").As<Napi::String>().Utf8Value() == "winevent");
+        }
+        if (options.Has("captureRichText") && options.Get("captureRichText").IsBoolean()) {
+            monitor->set_capture_rich(options.Get("captureRichText").As<Napi::Boolean>().Value());
+        }
+        if (options.Has("exposeElementHandles") && options.Get("exposeElementHandles").IsBoolean()) {
+            monitor->set_expose_handles(options.Get("exposeElementHandles").As<Napi::Boolean>().Value());
+        }
+        if (options.Has("reportUnreadableDrags") && options.Get("reportUnreadableDrags").IsBoolean()) {
+            monitor->set_report_unreadable(options.Get("reportUnreadableDrags").As<Napi::Boolean>().Value());
+        }
+        if (options.Has("imeComposition") && options.Get("imeComposition").IsString()) {
+            monitor->set_suppress_composition(options.Get("imeComposition").As<Napi::String>().Utf8Value() != "flag");
+        }
+        if (options.Has("callTimeoutMs") && options.Get("callTimeoutMs").IsNumber()) {
+            monitor->set_call_timeout(options.Get("callTimeoutMs").As<Napi::Number>().Int32Value());
+        }
+        if (options.Has("stopTimeoutMs") && options.Get("stopTimeoutMs").IsNumber()) {
+            monitor->set_stop_timeout(options.Get("stopTimeoutMs").As<Napi::Number>().Int32Value());
+        }
+        if (options.Has("captureMode") && options.Get("captureMode").IsString()) {
+            monitor->set_hotkey_only(options.Get("captureMode").As<Napi::String>().Utf8Value() == "hotkey");
+        }
+        if (options.Has("pollIntervalMs") && options.Get("pollIntervalMs").IsNumber()) {
+            monitor->set_poll_interval(options.Get("pollIntervalMs").As<Napi::Number>().Int32Value());
+        }
+        if (options.Has("includeApps") || options.Has("excludeApps")) {
+            monitor->set_app_filters(ToStringList(options.Get("includeApps")), ToStringList(options.Get("excludeApps")));
+        }
+
+        return env.Null();
+    }
+
+    Napi::Value GetInitError(const Napi::CallbackInfo& info) {
+        return Napi::String::New(info.Env(), monitor->get_init_error());
+    }
+
+    Napi::Value IsSessionReady(const Napi::CallbackInfo& info) {
+        return Napi::Boolean::New(info.Env(), monitor->is_session_ready());
+    }
+
+    Napi::Value RequestCapture(const Napi::CallbackInfo& info) {
+        return Napi::Boolean::New(info.Env(), monitor->request_capture());
+    }
+
+    Napi::Value GetCaptureMethod(const Napi::CallbackInfo& info) {
+        const char* method = monitor->is_hotkey_only() ? "ui-automation-hotkey"
+            : monitor->is_polling() ? "ui-automation-polling"
+            : monitor->is_win_events() ? "winevent" : "ui-automation";
+        return Napi::String::New(info.Env(), method);
+    }
+
     Napi::Value TestFocusedElement(const Napi::CallbackInfo& info) {
         Napi::Env env = info.Env();
         if (!monitor) {
@@ -631,6 +2871,11 @@ public:
     }
 };
 
+void ReadSelectionWorker::OnOK() {
+    Napi::Env env = Env();
+    deferred.Resolve(selection.text.empty() ? env.Null() : Napi::Value(UIAutomationSelectionMonitorWrapper::ToJsObject(env, selection)));
+}
+
 Napi::Object Init(Napi::Env env, Napi::Object exports) {
     return UIAutomationSelectionMonitorWrapper::Init(env, exports);
 }
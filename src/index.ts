@@ -5,6 +5,13 @@ import { dictionaryService } from './services/dictionary';
 import { wikipediaService } from './services/wikipedia';
 import { searchService } from './services/search';
 import { createNativeSelectionService, SelectionEvent } from './services/native-selection';
+import * as permissions from './services/permissions';
+import { createSupportBundle, formatSupportBundle } from './services/support-bundle';
+import { SelectionBroadcastServer } from './services/selection-server';
+import { SelectionWebSocketServer } from './services/selection-websocket';
+import { BrowserBridgeServer } from './services/browser-bridge';
+import { HistoryQuery } from './services/selection-store';
+import { DEFAULT_REDACTION_OPTIONS, RedactionOptions, redactEvent, redactSelection } from './services/selection-filters';
 
 // This allows TypeScript to pick up the magic constants that's auto-generated by Forge's Webpack
 // plugin that tells the Electron app where to look for the Webpack-bundled app code (depending on
@@ -18,17 +25,6 @@ if (require('electron-squirrel-startup')) {
   app.quit();
 }
 
-// Check administrator privileges for UIAutomation
-function checkAdminPrivileges(): boolean {
-  try {
-    const { execSync } = require('child_process');
-    execSync('net session', { stdio: 'ignore' });
-    return true;
-  } catch (error) {
-    return false;
-  }
-}
-
 // Global variables
 let mainWindow: BrowserWindow | null = null;
 let popupWindows: BrowserWindow[] = [];
@@ -41,7 +37,44 @@ let lastPopupText: string = '';
 let lastPopupAt: number = 0;
 
 // Native selection service
-const nativeSelectionService = createNativeSelectionService();
+const nativeSelectionService = createNativeSelectionService({ historyCapacity: 50 });
+nativeSelectionService.onError((error) => {
+  console.error(`[ERROR] Selection backend error (${error.code}):`, error.message);
+});
+
+// Redaction applied only where selection text leaves the main process (IPC to
+// renderer windows); the main process keeps the full text. Off by default.
+const rendererRedaction: RedactionOptions = { ...DEFAULT_REDACTION_OPTIONS };
+
+const toRendererText = (text: string): string => redactSelection(text, rendererRedaction) ?? '';
+
+const toRendererEvent = (event: SelectionEvent | null): SelectionEvent | null =>
+  event ? redactEvent(event, rendererRedaction) : null;
+
+// Opt-in: PHEVERE_SELECTION_SOCKET=1 (default path) or a pipe/socket path streams
+// selections to other local processes as JSON lines. Clients of this, the
+// WebSocket server and the browser bridge authenticate with the token in
+// <userData>/selection-token
+const selectionSocket = process.env.PHEVERE_SELECTION_SOCKET;
+const selectionServer = selectionSocket
+  ? new SelectionBroadcastServer(nativeSelectionService, {
+      redaction: rendererRedaction,
+      ...(selectionSocket !== '1' ? { socketPath: selectionSocket } : {}),
+    })
+  : null;
+
+// Opt-in: PHEVERE_SELECTION_WS=<port> streams selections over ws://127.0.0.1:<port>
+const selectionWsPort = Number(process.env.PHEVERE_SELECTION_WS);
+const selectionWebSocket = selectionWsPort > 0
+  ? new SelectionWebSocketServer(nativeSelectionService, { port: selectionWsPort, redaction: rendererRedaction })
+  : null;
+
+// Opt-in: PHEVERE_BROWSER_BRIDGE=1 (default path) or a pipe/socket path accepts
+// page selections relayed by the browser extension's native-messaging host
+const browserBridgeSocket = process.env.PHEVERE_BROWSER_BRIDGE;
+const browserBridge = browserBridgeSocket
+  ? new BrowserBridgeServer(nativeSelectionService, browserBridgeSocket !== '1' ? { socketPath: browserBridgeSocket } : {})
+  : null;
 
 // Open custom wiki window in standalone web window (not inside dictionary popup)
 ipcMain.on('open-wiki', (_e, term: string) => {
@@ -100,19 +133,18 @@ const createMainWindow = (): void => {
 const createPopupWindow = (x: number, y: number): void => {
   console.log(`[DEBUG] Creating popup window at position (${x}, ${y})`);
 
-  // Get screen dimensions and scale
+  // Get screen dimensions
   const primaryDisplay = screen.getPrimaryDisplay();
   const { width: screenWidth, height: screenHeight } = primaryDisplay.workAreaSize;
-  const scaleFactor = primaryDisplay.scaleFactor || 1;
 
                 // Calculate popup position and size - razor-slim toolbar initially
               const popupWidth = 360;
               const popupHeight = 48; // slightly taller so toolbar is fully visible
   
                 // Position popup near the selected text, but ensure it stays within screen bounds
-              // Convert native (physical px) coordinates to Electron's DIP coordinates
-              let popupX = Math.round(x / scaleFactor);
-              let popupY = Math.round(y / scaleFactor);
+              // Selection events are already in Electron's DIP coordinates
+              let popupX = Math.round(x);
+              let popupY = Math.round(y);
 
               // Use provided coordinates if valid, otherwise use cursor as fallback
               if (popupX < 0 || popupX > screenWidth || popupY < 0 || popupY > screenHeight || isNaN(popupX) || isNaN(popupY)) {
@@ -165,7 +197,10 @@ const createPopupWindow = (x: number, y: number): void => {
       existing.setBounds({ x: popupX, y: popupY, width: currW, height: currH }, false);
       existing.show();
       try { existing.focus(); } catch {}
-      if (textForPopup) existing.webContents.send('popup-text', textForPopup);
+      if (textForPopup) {
+        const rendererText = toRendererText(textForPopup);
+        if (rendererText) existing.webContents.send('popup-text', rendererText);
+      }
       lastPopupText = textForPopup;
       lastPopupAt = nowTs;
       console.log('[DEBUG] Reused existing popup window (moved/updated)');
@@ -239,8 +274,9 @@ const createPopupWindow = (x: number, y: number): void => {
   newPopupWindow.webContents.on('did-finish-load', () => {
     try {
       const textToSend = (lastSelectionEvent && lastSelectionEvent.text) || lastSelectedText || '';
-      if (textToSend) {
-        newPopupWindow.webContents.send('popup-text', textToSend);
+      const rendererText = toRendererText(textToSend);
+      if (rendererText) {
+        newPopupWindow.webContents.send('popup-text', rendererText);
       }
       lastPopupText = textToSend;
       lastPopupAt = Date.now();
@@ -312,7 +348,7 @@ const startSelectionMonitoring = (): void => {
   }
 
   // Check administrator privileges for UIAutomation
-  const isAdmin = checkAdminPrivileges();
+  const isAdmin = permissions.isElevated();
 
   if (!isAdmin) {
     console.warn('[WARNING] ⚠️ NOT RUNNING AS ADMINISTRATOR!');
@@ -324,8 +360,9 @@ const startSelectionMonitoring = (): void => {
 
   try {
     // Start pure native selection service
-    nativeSelectionService.start().then(() => {
+    nativeSelectionService.start().then((report) => {
       console.log('[DEBUG] Pure native selection service started successfully');
+      console.log('[DEBUG] Selection start report:', JSON.stringify(report));
       
       // Register selection callback
       nativeSelectionService.onSelection((event: SelectionEvent) => {
@@ -341,13 +378,17 @@ const startSelectionMonitoring = (): void => {
         }
         lastSelectionEvent = event; // Store the full event
         // Notify main window to update recent selections list
-        try { mainWindow?.webContents.send('selection-changed', event.text); } catch (e) { console.warn('[MAIN] send selection-changed failed', e);}
+        try {
+          const rendererText = toRendererText(event.text);
+          if (rendererText) mainWindow?.webContents.send('selection-changed', rendererText);
+        } catch (e) { console.warn('[MAIN] send selection-changed failed', e);}
         // Ensure popup shows the exact selected text. Avoid trimming chars.
         createPopupWindow(event.x, event.y);
       });
       
     }).catch((error) => {
       console.error('[ERROR] Failed to start native selection service:', error);
+      console.error('[ERROR] Selection start report:', JSON.stringify(nativeSelectionService.getStartReport()));
       if (!isAdmin) {
         console.error('[ERROR] This is likely due to insufficient privileges');
         console.error('[ERROR] Try running the app as Administrator');
@@ -417,6 +458,55 @@ ipcMain.handle('reset-selection-state', () => {
   return { success: true };
 });
 
+ipcMain.handle('get-selection-start-report', () => {
+  return nativeSelectionService.getStartReport();
+});
+
+ipcMain.handle('get-selection-status', () => {
+  return nativeSelectionService.getStatus();
+});
+
+ipcMain.handle('permissions-check', () => {
+  return permissions.check();
+});
+
+ipcMain.handle('permissions-request', () => {
+  return permissions.request();
+});
+
+ipcMain.handle('selection-get-history', () => {
+  return nativeSelectionService.history().map(toRendererEvent).filter((event) => event !== null);
+});
+
+ipcMain.handle('selection-query-history', async (event, query: HistoryQuery) => {
+  const events = await nativeSelectionService.queryHistory(query);
+  return events.map(toRendererEvent).filter((selection) => selection !== null);
+});
+
+ipcMain.handle('selection-at', (event, timestamp: number) => {
+  return toRendererEvent(nativeSelectionService.selectionAt(timestamp));
+});
+
+// Pollers pass the generation they last saw and get null while nothing changed
+ipcMain.handle('selection-if-newer', (event, generation: number) => {
+  const latest = nativeSelectionService.selectionIfNewer(generation);
+  const redacted = latest && toRendererEvent(latest.event);
+  return redacted ? { generation: latest.generation, event: redacted } : null;
+});
+
+ipcMain.handle('selection-clear-history', () => {
+  nativeSelectionService.clearHistory();
+  return { success: true };
+});
+
+ipcMain.handle('selection-get-metrics', () => {
+  return nativeSelectionService.metrics();
+});
+
+ipcMain.handle('selection-support-bundle', () => {
+  return formatSupportBundle(createSupportBundle(nativeSelectionService));
+});
+
 ipcMain.handle('stop-monitoring', () => {
   stopSelectionMonitoring();
   return { success: true };
@@ -646,7 +736,7 @@ ipcMain.handle('wikipedia-get-cache-stats', () => {
 
 // Search service IPC handlers
 ipcMain.handle('get-last-selection', () => {
-  return lastSelectionEvent;
+  return toRendererEvent(lastSelectionEvent);
 });
 
 ipcMain.handle('search-get-suggestions', async (event, query: string) => {
@@ -714,6 +804,17 @@ const registerGlobalShortcuts = (): void => {
     }
   });
 
+  // Register Ctrl+Shift+L to look up the current selection immediately
+  globalShortcut.register('CommandOrControl+Shift+L', () => {
+    try {
+      if (!nativeSelectionService.captureNow()) {
+        console.log('[DEBUG] Capture hotkey pressed but no selection is available');
+      }
+    } catch (error) {
+      console.error('[DEBUG] Capture hotkey could not read the selection:', error);
+    }
+  });
+
   // Register Ctrl+Shift+O to trigger OCR (placeholder for future implementation)
   globalShortcut.register('CommandOrControl+Shift+O', () => {
     console.log('OCR shortcut triggered - not implemented yet');
@@ -727,16 +828,51 @@ const registerGlobalShortcuts = (): void => {
   });
 };
 
+// `npm run doctor`: self-test the selection backend, print a support bundle and exit
+const runDoctor = async (): Promise<void> => {
+  try {
+    try {
+      await nativeSelectionService.start();
+    } catch (error) {
+      // The failure is part of the report
+    }
+    console.log(formatSupportBundle(createSupportBundle(nativeSelectionService)));
+  } finally {
+    await nativeSelectionService.stop();
+  }
+};
+
 // This method will be called when Electron has finished
 // initialization and is ready to create browser windows.
 // Some APIs can only be used after this event occurs.
 app.on('ready', () => {
   console.log('[DEBUG] ===== APP READY EVENT =====');
 
+  if (process.argv.includes('--doctor')) {
+    runDoctor()
+      .then(() => app.quit())
+      .catch((error) => {
+        console.error('[ERROR] Doctor run failed:', error);
+        process.exitCode = 1;
+        // app.quit() would exit with 0
+        app.exit(1);
+      });
+    return;
+  }
+
   createMainWindow();
   registerGlobalShortcuts();
   startSelectionMonitoring();
   clipboardService.startMonitoring();
+  selectionServer?.listen().catch((error) => {
+    console.error('[ERROR] Failed to start the selection socket:', error);
+  });
+  selectionWebSocket?.listen().catch((error) => {
+    console.error('[ERROR] Failed to start the selection WebSocket server:', error);
+  });
+  browserBridge?.listen().catch((error) => {
+    console.error('[ERROR] Failed to start the browser bridge:', error);
+  });
 
   console.log('[DEBUG] ===== APP INITIALIZATION COMPLETE =====');
 });
@@ -924,6 +1060,9 @@ app.on('will-quit', () => {
   stopSelectionMonitoring();
   clipboardService.stopMonitoring();
   globalShortcut.unregisterAll();
+  selectionServer?.close();
+  selectionWebSocket?.close();
+  browserBridge?.close();
 });
 
 // Handle app quit
@@ -21,7 +21,50 @@ interface SelectionEvent {
   x: number;
   y: number;
   timestamp: number;
-  source: 'native' | 'manual';
+  source: 'native' | 'manual' | 'hotkey' | 'clipboard' | 'browser' | 'own-app';
+  app?: string;
+}
+
+interface HistoryQuery {
+  app?: string;
+  from?: number;
+  to?: number;
+  search?: string;
+  limit?: number;
+}
+
+interface SelectionMetrics {
+  received: number;
+  delivered: number;
+  dropped: Record<string, number>;
+  latency: {
+    buckets: { le: number; count: number }[];
+    count: number;
+    sumMs: number;
+    maxMs: number;
+  };
+}
+
+interface PermissionStatus {
+  backend: string;
+  state: 'granted' | 'limited' | 'denied' | 'unknown';
+  detail: string;
+}
+
+interface StartReport {
+  backend: string;
+  capabilities: string[];
+  failures: { backend: string; reason: string }[];
+  remediation: string[];
+}
+
+interface SelectionStatus {
+  isRunning: boolean;
+  isPaused: boolean;
+  platform: string;
+  method: string;
+  lastEventAt: number | null;
+  lastError: string | null;
 }
 
 interface NativeAPI {
@@ -200,6 +243,39 @@ contextBridge.exposeInMainWorld('electronAPI', {
   resetSelectionState: () => {
     return ipcRenderer.invoke('reset-selection-state');
   },
+  getSelectionStartReport: () => {
+    return ipcRenderer.invoke('get-selection-start-report');
+  },
+  getSelectionStatus: () => {
+    return ipcRenderer.invoke('get-selection-status');
+  },
+  getSelectionHistory: (): Promise<SelectionEvent[]> => {
+    return ipcRenderer.invoke('selection-get-history');
+  },
+  querySelectionHistory: (query: HistoryQuery): Promise<SelectionEvent[]> => {
+    return ipcRenderer.invoke('selection-query-history', query);
+  },
+  getSelectionAt: (timestamp: number): Promise<SelectionEvent | null> => {
+    return ipcRenderer.invoke('selection-at', timestamp);
+  },
+  getSelectionIfNewer: (generation: number): Promise<{ generation: number; event: SelectionEvent } | null> => {
+    return ipcRenderer.invoke('selection-if-newer', generation);
+  },
+  clearSelectionHistory: (): Promise<{ success: boolean }> => {
+    return ipcRenderer.invoke('selection-clear-history');
+  },
+  getSelectionMetrics: (): Promise<SelectionMetrics> => {
+    return ipcRenderer.invoke('selection-get-metrics');
+  },
+  getSupportBundle: (): Promise<string> => {
+    return ipcRenderer.invoke('selection-support-bundle');
+  },
+  checkPermissions: (): Promise<PermissionStatus[]> => {
+    return ipcRenderer.invoke('permissions-check');
+  },
+  requestPermissions: (): Promise<PermissionStatus[]> => {
+    return ipcRenderer.invoke('permissions-request');
+  },
   startMonitoring: (): Promise<{ success: boolean }> => {
     return ipcRenderer.invoke('start-monitoring');
   },
@@ -360,6 +436,17 @@ declare global {
       showSettingsWindow: () => Promise<void>;
       getLastSelection: () => Promise<SelectionEvent>;
       resetSelectionState: () => Promise<{ success: boolean }>;
+      getSelectionStartReport?: () => Promise<StartReport | null>;
+      getSelectionStatus?: () => Promise<SelectionStatus>;
+      getSelectionHistory?: () => Promise<SelectionEvent[]>;
+      querySelectionHistory?: (query: HistoryQuery) => Promise<SelectionEvent[]>;
+      getSelectionAt?: (timestamp: number) => Promise<SelectionEvent | null>;
+      getSelectionIfNewer?: (generation: number) => Promise<{ generation: number; event: SelectionEvent } | null>;
+      clearSelectionHistory?: () => Promise<{ success: boolean }>;
+      getSelectionMetrics?: () => Promise<SelectionMetrics>;
+      getSupportBundle?: () => Promise<string>;
+      checkPermissions?: () => Promise<PermissionStatus[]>;
+      requestPermissions?: () => Promise<PermissionStatus[]>;
       openExternal: (url: string) => void;
       minimizeWindow: () => void;
       maximizeWindow: () => void;
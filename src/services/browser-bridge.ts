@@ -0,0 +1,184 @@
+/**
+ * Browser Bridge - accept in-page selections from the companion extension
+ *
+ * Browsers can only talk to native code through a native-messaging host, a
+ * separate process the browser starts on demand (browser-bridge/host.js). The
+ * host relays each message to this server as one line of JSON over a named
+ * pipe (Windows) or Unix domain socket, and the selection is submitted into the
+ * service's pipeline like any native event. Page selections read through the
+ * DOM are exact even where the browser's accessibility tree is not. The host
+ * authenticates with the token from local-auth.ts before its first message.
+ */
+
+import { screen } from 'electron';
+import * as net from 'net';
+import { authenticateLineClient, ClientAuthOptions, ClientAuthorizer, DEFAULT_CLIENT_AUTH_OPTIONS } from './local-auth';
+import { listenLocal, userSocketPath } from './local-socket';
+import { NativeSelectionService, SelectionEvent } from './native-selection';
+import { richTextFromHtml } from './rich-text';
+
+/**
+ * A selection message as sent by the extension
+ */
+export interface BrowserSelectionMessage {
+  type: 'selection';
+  text: string;
+  /** Selected markup, when the extension serialized the range */
+  html?: string;
+  url?: string;
+  title?: string;
+  /** Screen position of the selection in DIP; the cursor position is used when absent */
+  x?: number;
+  y?: number;
+}
+
+export interface BrowserBridgeOptions {
+  /** Empty = the per-user default (see defaultBridgeSocketPath()) */
+  socketPath: string;
+  /** Upper bound for one message line; longer lines drop the connection */
+  maxMessageBytes: number;
+  /** Which clients may submit selections */
+  auth: ClientAuthOptions;
+}
+
+export function defaultBridgeSocketPath(): string {
+  return userSocketPath('phevere-browser-bridge');
+}
+
+export const DEFAULT_BROWSER_BRIDGE_OPTIONS: BrowserBridgeOptions = {
+  socketPath: '',
+  maxMessageBytes: 4 * 1024 * 1024,
+  auth: DEFAULT_CLIENT_AUTH_OPTIONS,
+};
+
+/**
+ * Turn a validated extension message into a selection event
+ */
+export function browserMessageToEvent(message: BrowserSelectionMessage, timestamp: number): SelectionEvent {
+  const cursor = message.x === undefined || message.y === undefined ? screen.getCursorScreenPoint() : null;
+  const extensions: Record<string, unknown> = {};
+  if (message.url) extensions['browser.url'] = message.url;
+  if (message.title) extensions['browser.title'] = message.title;
+  if (message.html) extensions['browser.html'] = message.html;
+
+  return {
+    text: message.text,
+    x: cursor ? cursor.x : (message.x as number),
+    y: cursor ? cursor.y : (message.y as number),
+    timestamp,
+    source: 'browser',
+    ...(message.html ? { rich: richTextFromHtml(message.html) } : {}),
+    ...(Object.keys(extensions).length > 0 ? { extensions } : {}),
+  };
+}
+
+function parseMessage(line: string): BrowserSelectionMessage | null {
+  const data = JSON.parse(line);
+  if (!data || data.type !== 'selection' || typeof data.text !== 'string') {
+    return null;
+  }
+  for (const field of ['html', 'url', 'title']) {
+    if (data[field] !== undefined && typeof data[field] !== 'string') {
+      return null;
+    }
+  }
+  if ((data.x !== undefined && typeof data.x !== 'number') || (data.y !== undefined && typeof data.y !== 'number')) {
+    return null;
+  }
+  return data as BrowserSelectionMessage;
+}
+
+export class BrowserBridgeServer {
+  private server: net.Server | null = null;
+  private clients = new Set<net.Socket>();
+  private authorizer: ClientAuthorizer | null = null;
+  private options: BrowserBridgeOptions;
+
+  constructor(private service: NativeSelectionService, options: Partial<BrowserBridgeOptions> = {}) {
+    this.options = { ...DEFAULT_BROWSER_BRIDGE_OPTIONS, ...options };
+  }
+
+  async listen(): Promise<void> {
+    if (this.server) {
+      return;
+    }
+
+    const socketPath = this.options.socketPath || defaultBridgeSocketPath();
+    this.authorizer = new ClientAuthorizer(this.options.auth);
+    const server = net.createServer((socket) => this.accept(socket));
+    this.server = server;
+
+    try {
+      await listenLocal(server, socketPath);
+    } catch (error) {
+      this.server = null;
+      throw error;
+    }
+    server.on('error', (error) => console.error('[BROWSER-BRIDGE] Server error:', error));
+    console.log(`[BROWSER-BRIDGE] Listening on ${socketPath}`);
+  }
+
+  async close(): Promise<void> {
+    const server = this.server;
+    if (!server) {
+      return;
+    }
+    this.server = null;
+    this.clients.forEach((client) => client.destroy());
+    this.clients.clear();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  }
+
+  private accept(socket: net.Socket): void {
+    this.clients.add(socket);
+    socket.on('error', (error) => {
+      console.warn('[BROWSER-BRIDGE] Client error:', error.message);
+    });
+    socket.on('close', () => this.clients.delete(socket));
+    if (!this.authorizer) {
+      socket.destroy();
+      return;
+    }
+    authenticateLineClient(socket, this.authorizer, (rest) => this.readMessages(socket, rest));
+  }
+
+  private readMessages(socket: net.Socket, initial: string): void {
+    let pending = '';
+    const read = (chunk: string) => {
+      pending += chunk;
+      if (Buffer.byteLength(pending) > this.options.maxMessageBytes && !pending.includes('\n')) {
+        console.warn('[BROWSER-BRIDGE] Message too large, dropping connection');
+        socket.destroy();
+        return;
+      }
+      let newline = pending.indexOf('\n');
+      while (newline !== -1) {
+        const line = pending.slice(0, newline).trim();
+        pending = pending.slice(newline + 1);
+        if (line) {
+          this.handleLine(line);
+        }
+        newline = pending.indexOf('\n');
+      }
+    };
+    read(initial);
+    socket.on('data', read);
+  }
+
+  private handleLine(line: string): void {
+    let message: BrowserSelectionMessage | null;
+    try {
+      message = parseMessage(line);
+    } catch {
+      message = null;
+    }
+    if (!message) {
+      console.warn('[BROWSER-BRIDGE] Ignoring malformed message');
+      return;
+    }
+    if (!message.text.trim()) {
+      return;
+    }
+    this.service.submit(browserMessageToEvent(message, Date.now()));
+  }
+}
@@ -7,15 +7,63 @@ export interface ClipboardEntry {
   type: 'text' | 'image' | 'file';
 }
 
+/**
+ * How history entries are compared when removing duplicates. Only the comparison
+ * key is normalized; entries always keep their original text.
+ */
+export interface DedupeOptions {
+  /** Treat "Hello" and "hello" as the same entry */
+  caseFold: boolean;
+  /** Ignore differences in runs of whitespace */
+  whitespaceFold: boolean;
+  /** Compare text in Unicode NFC form */
+  unicodeNormalize: boolean;
+  /** Locale used for case folding (e.g. 'tr' so that "I" and "ı" match) */
+  locale?: string;
+}
+
+export const DEFAULT_DEDUPE_OPTIONS: DedupeOptions = {
+  caseFold: false,
+  whitespaceFold: false,
+  unicodeNormalize: false,
+};
+
+/**
+ * Build the key used to decide whether two texts are duplicates
+ */
+export function dedupeKey(text: string, options: DedupeOptions): string {
+  let key = text;
+  if (options.unicodeNormalize) {
+    key = key.normalize('NFC');
+  }
+  if (options.whitespaceFold) {
+    key = key.replace(/\s+/g, ' ').trim();
+  }
+  if (options.caseFold) {
+    key = options.locale ? key.toLocaleLowerCase(options.locale) : key.toLowerCase();
+  }
+  return key;
+}
+
 export class ClipboardService {
   private history: ClipboardEntry[] = [];
   private maxHistorySize: number = 50;
+  private dedupeOptions: DedupeOptions = DEFAULT_DEDUPE_OPTIONS;
   private isMonitoring: boolean = false;
   private lastClipboardText: string = '';
   private checkInterval: NodeJS.Timeout | null = null;
 
-  constructor(maxHistorySize: number = 50) {
+  constructor(maxHistorySize: number = 50, dedupeOptions: Partial<DedupeOptions> = {}) {
     this.maxHistorySize = maxHistorySize;
+    this.dedupeOptions = { ...DEFAULT_DEDUPE_OPTIONS, ...dedupeOptions };
+  }
+
+  /**
+   * Change how duplicates are detected; existing history is re-deduplicated
+   */
+  setDedupeOptions(options: Partial<DedupeOptions>): void {
+    this.dedupeOptions = { ...this.dedupeOptions, ...options };
+    this.history = this.removeDuplicates(this.history);
   }
 
   /**
@@ -97,9 +145,7 @@ export class ClipboardService {
     this.history.unshift(entry);
 
     // Remove duplicates (keep the most recent)
-    this.history = this.history.filter((item, index, self) => 
-      index === self.findIndex(t => t.text === item.text)
-    );
+    this.history = this.removeDuplicates(this.history);
 
     // Limit history size
     if (this.history.length > this.maxHistorySize) {
@@ -198,6 +244,21 @@ export class ClipboardService {
     }
   }
 
+  /**
+   * Keep the first (most recent) entry for each dedupe key
+   */
+  private removeDuplicates(entries: ClipboardEntry[]): ClipboardEntry[] {
+    const seen = new Set<string>();
+    return entries.filter(entry => {
+      const key = dedupeKey(entry.text, this.dedupeOptions);
+      if (seen.has(key)) {
+        return false;
+      }
+      seen.add(key);
+      return true;
+    });
+  }
+
   /**
    * Generate unique ID for clipboard entries
    */
@@ -243,9 +304,7 @@ export class ClipboardService {
       this.history = [...this.history, ...importedHistory];
       
       // Remove duplicates and limit size
-      this.history = this.history.filter((item, index, self) => 
-        index === self.findIndex(t => t.text === item.text)
-      );
+      this.history = this.removeDuplicates(this.history);
       
       if (this.history.length > this.maxHistorySize) {
         this.history = this.history.slice(0, this.maxHistorySize);
@@ -0,0 +1,93 @@
+/**
+ * Clock - the time source behind debouncing, dedupe, timestamps and retries
+ *
+ * Services use systemClock unless a ListenerConfig supplies another one.
+ * ManualClock only moves when told to, so debounce/dedupe behaviour can be
+ * exercised deterministically (e.g. with MockNativeSelectionService) instead of
+ * with real sleeps.
+ */
+
+export type TimerHandle = unknown;
+
+export interface Clock {
+  /** Milliseconds since epoch */
+  now(): number;
+  setTimeout(callback: () => void, ms: number): TimerHandle;
+  clearTimeout(handle: TimerHandle): void;
+}
+
+export const systemClock: Clock = {
+  now: () => Date.now(),
+  setTimeout: (callback, ms) => setTimeout(callback, ms),
+  clearTimeout: (handle) => clearTimeout(handle as NodeJS.Timeout),
+};
+
+/**
+ * Resolve after ms on the given clock
+ */
+export function sleep(ms: number, clock: Clock = systemClock): Promise<void> {
+  return new Promise((resolve) => {
+    clock.setTimeout(resolve, ms);
+  });
+}
+
+interface ManualTimer {
+  id: number;
+  dueAt: number;
+  callback: () => void;
+}
+
+/**
+ * Controllable clock: time stands still until advance() is called, which fires
+ * due timers in order
+ */
+export class ManualClock implements Clock {
+  private time: number;
+  private timers: ManualTimer[] = [];
+  private nextId = 1;
+
+  constructor(startAt = 0) {
+    this.time = startAt;
+  }
+
+  now(): number {
+    return this.time;
+  }
+
+  setTimeout(callback: () => void, ms: number): TimerHandle {
+    const timer = { id: this.nextId++, dueAt: this.time + Math.max(0, ms), callback };
+    this.timers.push(timer);
+    return timer.id;
+  }
+
+  clearTimeout(handle: TimerHandle): void {
+    this.timers = this.timers.filter((timer) => timer.id !== handle);
+  }
+
+  /**
+   * Move time forward, running every timer that falls due on the way (including
+   * timers scheduled by those callbacks)
+   */
+  advance(ms: number): void {
+    const target = this.time + Math.max(0, ms);
+    for (;;) {
+      const due = this.timers
+        .filter((timer) => timer.dueAt <= target)
+        .sort((a, b) => a.dueAt - b.dueAt || a.id - b.id)[0];
+      if (!due) {
+        break;
+      }
+      this.timers = this.timers.filter((timer) => timer !== due);
+      this.time = due.dueAt;
+      due.callback();
+    }
+    this.time = target;
+  }
+
+  /**
+   * Number of timers still waiting to fire
+   */
+  pendingTimers(): number {
+    return this.timers.length;
+  }
+}
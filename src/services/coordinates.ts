@@ -0,0 +1,46 @@
+/**
+ * Screen coordinate conversion
+ *
+ * Selection events use Electron's screen coordinate space: device-independent
+ * pixels (DIP), the same units as BrowserWindow bounds and screen.getCursorScreenPoint().
+ * Native Windows APIs (UI Automation, low-level hooks) report physical pixels;
+ * these helpers convert between the two. On macOS and Linux the native APIs
+ * already use logical points, so the conversion is the identity.
+ */
+
+import { Point, Rectangle, screen } from 'electron';
+
+/**
+ * Physical screen pixels -> DIP
+ */
+export function physicalToDip(point: Point): Point {
+  if (process.platform !== 'win32') {
+    return point;
+  }
+  return screen.screenToDipPoint(point);
+}
+
+/**
+ * DIP -> physical screen pixels
+ */
+export function dipToPhysical(point: Point): Point {
+  if (process.platform !== 'win32') {
+    return point;
+  }
+  return screen.dipToScreenPoint(point);
+}
+
+export function physicalRectToDip(rect: Rectangle): Rectangle {
+  if (process.platform !== 'win32') {
+    return rect;
+  }
+  // Scale with the display the rectangle is on, not the primary display
+  return screen.screenToDipRect(null, rect);
+}
+
+export function dipRectToPhysical(rect: Rectangle): Rectangle {
+  if (process.platform !== 'win32') {
+    return rect;
+  }
+  return screen.dipToScreenRect(null, rect);
+}
@@ -0,0 +1,157 @@
+/**
+ * Enrichment Scheduler - background work attached to delivered selections
+ *
+ * Enrichers (OCR, entity extraction, icon lookup, ...) run one at a time after a
+ * selection has been delivered, yielding to the event loop between tasks so
+ * capture is never delayed. Each task gets a time budget, and everything still
+ * queued or running for a selection is cancelled as soon as a newer selection
+ * arrives.
+ */
+
+import type { SelectionEvent } from './native-selection';
+
+export type Enricher = (event: SelectionEvent, signal: AbortSignal) => Promise<unknown>;
+
+export interface EnricherOptions {
+  /** The task is aborted and its result discarded after this long */
+  budgetMs: number;
+  /** Only run while the service is in precision mode */
+  precisionOnly: boolean;
+  /** Never run automatically; only when a consumer asks for it via run() */
+  onDemand: boolean;
+}
+
+export const DEFAULT_ENRICHER_OPTIONS: EnricherOptions = {
+  budgetMs: 2000,
+  precisionOnly: false,
+  onDemand: false,
+};
+
+interface RegisteredEnricher {
+  name: string;
+  task: Enricher;
+  options: EnricherOptions;
+}
+
+export class EnrichmentScheduler {
+  private enrichers: RegisteredEnricher[] = [];
+  private listeners: ((event: SelectionEvent, name: string, value: unknown) => void)[] = [];
+  private current: AbortController | null = null;
+  private precision = false;
+
+  /**
+   * Register a task; its result is reported under the given name
+   */
+  register(name: string, task: Enricher, options: Partial<EnricherOptions> = {}): void {
+    this.enrichers = this.enrichers.filter((enricher) => enricher.name !== name);
+    this.enrichers.push({ name, task, options: { ...DEFAULT_ENRICHER_OPTIONS, ...options } });
+  }
+
+  unregister(name: string): void {
+    this.enrichers = this.enrichers.filter((enricher) => enricher.name !== name);
+  }
+
+  /**
+   * Enable or disable the precisionOnly tasks
+   */
+  setPrecision(active: boolean): void {
+    this.precision = active;
+  }
+
+  onResult(listener: (event: SelectionEvent, name: string, value: unknown) => void): void {
+    this.listeners.push(listener);
+  }
+
+  /**
+   * Start enriching a freshly delivered selection, superseding the previous one
+   */
+  schedule(event: SelectionEvent): void {
+    this.cancel();
+    const queue = this.enrichers.filter((enricher) =>
+      !enricher.options.onDemand && (this.precision || !enricher.options.precisionOnly));
+    if (queue.length === 0) {
+      return;
+    }
+
+    const controller = new AbortController();
+    this.current = controller;
+    void this.run(event, queue, controller.signal);
+  }
+
+  /**
+   * Run one task for a selection on request, within its budget. Resolves
+   * undefined if the task is unknown, fails, runs out of time or the caller's
+   * signal aborts it. Not cancelled by newer selections.
+   */
+  async run(name: string, event: SelectionEvent, signal?: AbortSignal): Promise<unknown> {
+    const enricher = this.enrichers.find((candidate) => candidate.name === name);
+    if (!enricher) {
+      return undefined;
+    }
+    const parent = signal || new AbortController().signal;
+    if (parent.aborted) {
+      return undefined;
+    }
+    return this.runWithBudget(enricher, event, parent);
+  }
+
+  /**
+   * Abort everything queued or running
+   */
+  cancel(): void {
+    if (this.current) {
+      this.current.abort();
+      this.current = null;
+    }
+  }
+
+  private async run(event: SelectionEvent, queue: RegisteredEnricher[], signal: AbortSignal): Promise<void> {
+    for (const enricher of queue) {
+      // Yield so pending I/O and capture callbacks run first
+      await new Promise<void>((resolve) => setImmediate(resolve));
+      if (signal.aborted) {
+        return;
+      }
+
+      const value = await this.runWithBudget(enricher, event, signal);
+      if (signal.aborted) {
+        return;
+      }
+      if (value !== undefined) {
+        this.report(event, enricher.name, value);
+      }
+    }
+  }
+
+  private async runWithBudget(enricher: RegisteredEnricher, event: SelectionEvent, parent: AbortSignal): Promise<unknown> {
+    const controller = new AbortController();
+    const onParentAbort = () => controller.abort();
+    parent.addEventListener('abort', onParentAbort);
+    const timer = setTimeout(() => controller.abort(), enricher.options.budgetMs);
+
+    try {
+      const timeout = new Promise<undefined>((resolve) => {
+        controller.signal.addEventListener('abort', () => resolve(undefined));
+      });
+      return await Promise.race([enricher.task(event, controller.signal), timeout]);
+    } catch (error) {
+      if (!controller.signal.aborted) {
+        console.error(`[ENRICHMENT] Enricher "${enricher.name}" failed:`, error);
+      }
+      return undefined;
+    } finally {
+      clearTimeout(timer);
+      parent.removeEventListener('abort', onParentAbort);
+    }
+  }
+
+  private report(event: SelectionEvent, name: string, value: unknown): void {
+    this.listeners.forEach((listener) => {
+      try {
+        listener(event, name, value);
+      } catch (error) {
+        console.error('[ENRICHMENT] Error in result listener:', error);
+      }
+    });
+  }
+}
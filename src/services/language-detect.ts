@@ -0,0 +1,78 @@
+/**
+ * Language Detection - offline guess of a selection's language
+ *
+ * Script ranges decide non-Latin languages outright; Latin text is scored
+ * against short stopword lists, then characteristic letters, and defaults to
+ * English. Good enough to pick a translation direction without a network
+ * round trip; not a substitute for a real classifier on mixed-language text.
+ */
+
+import { words } from './text-segment';
+
+const SCRIPTS: [RegExp, string][] = [
+  [/[぀-ゟ゠-ヿ]/, 'ja'], // kana before Han: Japanese mixes both
+  [/[가-힯]/, 'ko'],
+  [/[一-鿿]/, 'zh'],
+  [/[іїєґ]/i, 'uk'],
+  [/[Ѐ-ӿ]/, 'ru'],
+  [/[پچژگ]/, 'fa'],
+  [/[؀-ۿ]/, 'ar'],
+  [/[֐-׿]/, 'he'],
+  [/[฀-๿]/, 'th'],
+  [/[Ͱ-Ͽ]/, 'el'],
+  [/[ऀ-ॿ]/, 'hi'],
+];
+
+const STOPWORDS: Record<string, string[]> = {
+  en: ['the', 'and', 'of', 'to', 'is', 'in', 'that', 'it', 'for', 'with', 'was', 'are', 'this', 'be', 'on', 'not'],
+  es: ['el', 'la', 'de', 'que', 'y', 'en', 'los', 'las', 'del', 'por', 'con', 'una', 'para', 'es', 'está', 'pero'],
+  fr: ['le', 'la', 'les', 'de', 'et', 'des', 'est', 'un', 'une', 'pour', 'dans', 'que', 'pas', 'sur', 'avec', 'ce'],
+  de: ['der', 'die', 'das', 'und', 'ist', 'nicht', 'ein', 'eine', 'zu', 'mit', 'den', 'von', 'sich', 'auf', 'für', 'auch'],
+  it: ['il', 'di', 'che', 'e', 'la', 'per', 'un', 'non', 'sono', 'con', 'del', 'della', 'gli', 'una', 'anche', 'è'],
+  pt: ['o', 'de', 'que', 'e', 'do', 'da', 'em', 'um', 'para', 'não', 'uma', 'os', 'com', 'mais', 'dos', 'é'],
+  nl: ['de', 'het', 'een', 'en', 'van', 'is', 'dat', 'niet', 'op', 'te', 'zijn', 'met', 'voor', 'ook', 'maar', 'wordt'],
+};
+
+const LETTERS: [RegExp, string][] = [
+  [/[ñ¿¡]/i, 'es'],
+  [/[ßäöü]/i, 'de'],
+  [/[ãõ]/i, 'pt'],
+  [/[çœèêëîï]/i, 'fr'],
+  [/[ìò]/i, 'it'],
+];
+
+/**
+ * BCP 47 primary language tag for the text, or null when it has no letters
+ */
+export function detectLanguage(text: string): string | null {
+  for (const [pattern, language] of SCRIPTS) {
+    if (pattern.test(text)) {
+      return language;
+    }
+  }
+
+  const tokens = words(text.toLowerCase()).map((word) => word.text).slice(0, 50);
+  if (tokens.length === 0 || !tokens.some((token) => /[a-zÀ-ɏ]/i.test(token))) {
+    return null;
+  }
+
+  let best: string | null = null;
+  let bestScore = 0;
+  for (const language of Object.keys(STOPWORDS)) {
+    const score = tokens.filter((token) => STOPWORDS[language].includes(token)).length;
+    if (score > bestScore) {
+      best = language;
+      bestScore = score;
+    }
+  }
+  if (best) {
+    return best;
+  }
+
+  for (const [pattern, language] of LETTERS) {
+    if (pattern.test(text)) {
+      return language;
+    }
+  }
+  return 'en';
+}
@@ -0,0 +1,167 @@
+/**
+ * Local Auth - per-client authorization for the local selection servers
+ *
+ * The selection socket, the WebSocket server and the browser bridge only talk
+ * to clients that present a shared token. The token lives in a file in the
+ * app's userData directory that only the current user can read, so other users
+ * on a shared machine (who can still reach a loopback port, or open a Windows
+ * pipe, whose default security descriptor Node cannot change) are disconnected
+ * before they see or submit a selection. It is generated on first use and kept
+ * across restarts, so clients only need to be configured once.
+ *
+ * Line-based clients send {"type":"auth","token":"..."} as their first line;
+ * WebSocket clients pass the token as a bearer Authorization header or a
+ * `token` query parameter.
+ */
+
+import { app } from 'electron';
+import * as crypto from 'crypto';
+import * as fs from 'fs';
+import * as net from 'net';
+import * as path from 'path';
+
+export interface ClientAuthOptions {
+  /** Refuse clients that do not present the token */
+  required: boolean;
+  /** Token file; empty = selection-token in the app's userData directory */
+  tokenFile: string;
+}
+
+export const DEFAULT_CLIENT_AUTH_OPTIONS: ClientAuthOptions = {
+  required: true,
+  tokenFile: '',
+};
+
+/** Clients that have not authenticated by then are disconnected */
+const AUTH_TIMEOUT_MS = 5000;
+/** Upper bound for the auth line; anything longer is not a token */
+const MAX_AUTH_LINE_BYTES = 1024;
+
+export function defaultTokenFile(): string {
+  return path.join(app.getPath('userData'), 'selection-token');
+}
+
+/**
+ * Whether the file may hold the token: owned by the current user and not
+ * readable or writable by anyone else. Windows has no such modes; the file
+ * relies on the userData directory's ACL there.
+ */
+function isPrivate(stat: fs.Stats): boolean {
+  if (process.platform === 'win32') {
+    return true;
+  }
+  const ownedByUs = typeof process.getuid !== 'function' || stat.uid === process.getuid();
+  return ownedByUs && (stat.mode & 0o077) === 0;
+}
+
+function readPrivateToken(file: string): string | null {
+  const fd = fs.openSync(file, 'r');
+  try {
+    // fstat the opened file, so a swap between the check and the read is not missed
+    if (!isPrivate(fs.fstatSync(fd))) {
+      console.warn(`[LOCAL-AUTH] ${file} is accessible to other users; replacing the token`);
+      return null;
+    }
+    const token = fs.readFileSync(fd, 'utf8').trim();
+    return token.length >= 32 ? token : null;
+  } finally {
+    fs.closeSync(fd);
+  }
+}
+
+/**
+ * Read the token, creating the file (owner-only) with a random one if needed.
+ * A token someone else could have read or planted is replaced.
+ */
+export function loadToken(file: string): string {
+  try {
+    const token = readPrivateToken(file);
+    if (token !== null) {
+      return token;
+    }
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== 'ENOENT') {
+      throw error;
+    }
+  }
+  const token = crypto.randomBytes(32).toString('hex');
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+  // A new file rather than rewriting the old one, whose owner and mode we may not control
+  fs.rmSync(file, { force: true });
+  fs.writeFileSync(file, `${token}\n`, { mode: 0o600, flag: 'wx' });
+  return token;
+}
+
+export class ClientAuthorizer {
+  private token: string | null;
+
+  constructor(options: ClientAuthOptions) {
+    this.token = options.required ? loadToken(options.tokenFile || defaultTokenFile()) : null;
+  }
+
+  get required(): boolean {
+    return this.token !== null;
+  }
+
+  accepts(candidate: unknown): boolean {
+    if (this.token === null) {
+      return true;
+    }
+    if (typeof candidate !== 'string') {
+      return false;
+    }
+    const given = Buffer.from(candidate, 'utf8');
+    const expected = Buffer.from(this.token, 'utf8');
+    return given.length === expected.length && crypto.timingSafeEqual(given, expected);
+  }
+}
+
+function tokenFromLine(line: string): unknown {
+  try {
+    const data = JSON.parse(line);
+    return data && data.type === 'auth' ? data.token : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Wait for a line-protocol client to authenticate. The socket is destroyed on
+ * a missing or wrong token, or when none arrives in time; otherwise
+ * onAuthorized gets whatever the client sent after the auth line.
+ */
+export function authenticateLineClient(
+  socket: net.Socket,
+  authorizer: ClientAuthorizer,
+  onAuthorized: (rest: string) => void
+): void {
+  socket.setEncoding('utf8');
+  if (!authorizer.required) {
+    onAuthorized('');
+    return;
+  }
+
+  let pending = '';
+  const timer = setTimeout(() => socket.destroy(), AUTH_TIMEOUT_MS);
+  const onData = (chunk: string) => {
+    pending += chunk;
+    const newline = pending.indexOf('\n');
+    if (newline === -1) {
+      if (Buffer.byteLength(pending) > MAX_AUTH_LINE_BYTES) {
+        clearTimeout(timer);
+        socket.destroy();
+      }
+      return;
+    }
+    clearTimeout(timer);
+    socket.off('data', onData);
+    if (!authorizer.accepts(tokenFromLine(pending.slice(0, newline).trim()))) {
+      console.warn('[LOCAL-AUTH] Refused a client with a missing or wrong token');
+      socket.destroy();
+      return;
+    }
+    onAuthorized(pending.slice(newline + 1));
+  };
+  socket.on('data', onData);
+  socket.on('close', () => clearTimeout(timer));
+}
@@ -0,0 +1,109 @@
+/**
+ * Local Socket - per-user endpoints for the selection server and browser bridge
+ *
+ * Names are scoped to the current user so two sessions on one machine never
+ * share (or squat) an endpoint: a pipe suffixed with the user's SID on Windows,
+ * a socket in XDG_RUNTIME_DIR (or the temp directory, suffixed with the uid)
+ * elsewhere. Unix sockets are created owner-only (0600), and a socket file that
+ * is already there is only removed once a connect attempt shows that nothing
+ * is listening on it any more. Node cannot change a Windows pipe's default
+ * security descriptor, so the servers also authenticate every client before
+ * sending or accepting anything (see local-auth.ts).
+ *
+ * browser-bridge/host.js derives the same names; keep the two in sync.
+ */
+
+import { execFileSync } from 'child_process';
+import * as fs from 'fs';
+import * as net from 'net';
+import * as os from 'os';
+import * as path from 'path';
+
+let userTag: string | null = null;
+
+// SID of the current user, or DOMAIN.user when whoami is unavailable
+function windowsUserTag(): string {
+  if (userTag === null) {
+    try {
+      const output = execFileSync('whoami', ['/user', '/fo', 'csv', '/nh'], { encoding: 'utf8', windowsHide: true });
+      const sid = /"(S-[\d-]+)"/.exec(output);
+      userTag = sid ? sid[1] : null;
+    } catch {
+      userTag = null;
+    }
+    if (userTag === null) {
+      userTag = `${process.env.USERDOMAIN || ''}.${os.userInfo().username}`.replace(/[^\w.-]/g, '_');
+    }
+  }
+  return userTag;
+}
+
+/**
+ * Default pipe or socket path for one endpoint, e.g. userSocketPath('phevere-selection')
+ */
+export function userSocketPath(name: string): string {
+  if (process.platform === 'win32') {
+    return `\\\\.\\pipe\\${name}-${windowsUserTag()}`;
+  }
+  // The runtime directory is private to the user already
+  const runtimeDir = process.env.XDG_RUNTIME_DIR;
+  if (runtimeDir && fs.existsSync(runtimeDir)) {
+    return path.join(runtimeDir, `${name}.sock`);
+  }
+  return path.join(os.tmpdir(), `${name}-${os.userInfo().uid}.sock`);
+}
+
+/**
+ * Whether a process is accepting connections on the socket. Only a refused
+ * connection counts as stale; anything else (e.g. a socket owned by another
+ * user) is left for listen() to report.
+ */
+function probeSocket(socketPath: string): Promise<'live' | 'stale' | 'unknown'> {
+  return new Promise((resolve) => {
+    const probe = net.createConnection(socketPath);
+    probe.once('connect', () => {
+      probe.destroy();
+      resolve('live');
+    });
+    probe.once('error', (error: NodeJS.ErrnoException) => {
+      resolve(error.code === 'ECONNREFUSED' ? 'stale' : 'unknown');
+    });
+  });
+}
+
+/**
+ * Listen on a local pipe or socket. Rejects with EADDRINUSE when another
+ * instance is already serving the path.
+ */
+export async function listenLocal(server: net.Server, socketPath: string): Promise<void> {
+  if (process.platform !== 'win32' && fs.existsSync(socketPath)) {
+    const state = await probeSocket(socketPath);
+    if (state === 'live') {
+      throw Object.assign(new Error(`${socketPath} is in use by another process`), { code: 'EADDRINUSE' });
+    }
+    if (state === 'stale') {
+      // Left behind by a crashed instance; listen() would fail on it
+      await fs.promises.unlink(socketPath);
+    }
+  }
+
+  await new Promise<void>((resolve, reject) => {
+    server.once('error', reject);
+    // The socket file is created during listen(); no window where it is group or world accessible
+    const umask = process.platform !== 'win32' ? process.umask(0o177) : null;
+    try {
+      server.listen(socketPath, () => {
+        server.off('error', reject);
+        resolve();
+      });
+    } finally {
+      if (umask !== null) {
+        process.umask(umask);
+      }
+    }
+  });
+
+  if (process.platform !== 'win32') {
+    await fs.promises.chmod(socketPath, 0o600);
+  }
+}
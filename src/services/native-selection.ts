@@ -8,36 +8,1262 @@
  * or additional actions. Uses intelligent debouncing like Youdao Dictionary.
  */
 
-import { screen } from 'electron';
+import { BrowserWindow, clipboard, globalShortcut, Point, screen } from 'electron';
+import { SelectionDispatcher } from './selection-dispatcher';
+import { SelectionHistory } from './selection-history';
+import { DEFAULT_HISTORY_STORE_OPTIONS, HistoryQuery, HistoryStoreOptions, SelectionStore } from './selection-store';
+import { BackpressurePolicy, SelectionReceiver } from './selection-receiver';
+import { DEFAULT_REDACTION_OPTIONS, RedactionOptions, redactSelection } from './selection-filters';
+import { MetricsSnapshot, SelectionMetrics, mergeDrops } from './selection-metrics';
+import { dipToPhysical, physicalRectToDip, physicalToDip } from './coordinates';
+import { Enricher, EnricherOptions, EnrichmentScheduler } from './enrichment-scheduler';
+import { Clock, systemClock, TimerHandle } from './clock';
+import { RetryOptions, waitUntil, withRetries } from './readiness';
+import { SessionAware, SessionChange, watchSession } from './session-watcher';
+import { captureRegion, shrink } from './screen-capture';
+import { SelectionDiff } from './selection-diff';
+import { EnclosingUnit, enclosingSegment, WordDictionary, wordAt } from './text-segment';
+import { SelectionEntity } from './selection-entities';
+import { compileMatcher, SelectionMatcher } from './selection-rules';
+import {
+  bidiProcessor,
+  diffProcessor,
+  entityProcessor,
+  languageProcessor,
+  ProcessorPosition,
+  redactionProcessor,
+  sanitizeProcessor,
+  SelectionPipeline,
+  SelectionProcessor,
+} from './selection-processor';
+import { BidiInfo, TextDirection } from './text-bidi';
+import { plainRichText, RichText, richTextFromHtml } from './rich-text';
+import { DEFAULT_SANITIZE_OPTIONS, isSanitizeEnabled, SanitizeOptions } from './text-sanitize';
+import { DEFAULT_OCR_OPTIONS, OcrEngine, OcrOptions, recognizeRegion, TesseractEngine } from './ocr';
+import type NativeAddon from '../../native-addon';
+
+/**
+ * Screen rectangle in DIP (Electron screen coordinates); see coordinates.ts to
+ * convert to physical pixels
+ */
+export interface SelectionBounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/**
+ * One of several discontiguous selected ranges
+ */
+export interface SelectedRange {
+  text: string;
+}
+
+/**
+ * Text around a selection within the same text element
+ */
+export interface SelectionContext {
+  before: string;
+  after: string;
+}
+
+/**
+ * Opaque identity of the element a selection came from, for follow-up queries
+ * with platform APIs. Windows: the nearest window handle (decimal string, since
+ * a handle can exceed a JS number) and the UI Automation runtime ID, which
+ * finds the element again via a UIA_RuntimeIdPropertyId condition. Valid only
+ * while the source app keeps the element alive.
+ */
+export interface NativeElementHandle {
+  platform: 'windows';
+  hwnd: string;
+  runtimeId: number[];
+}
+
+export interface SelectionWithContext extends SelectionContext {
+  text: string;
+}
+
+/**
+ * The word under a screen point, with the text read around it
+ */
+export interface WordAtPoint extends SelectionWithContext {
+  app?: string;
+}
+
+/**
+ * How the user made the selection
+ * - 'word': double-click
+ * - 'paragraph': triple-click
+ * - 'drag': mouse drag
+ * - 'keyboard': Shift/Ctrl + arrow keys and similar
+ */
+export type SelectionGranularity = 'word' | 'paragraph' | 'drag' | 'keyboard' | 'unknown';
 
 export interface SelectionEvent {
+  /** Selected text; multiple ranges are joined with '\n' */
   text: string;
   x: number;
   y: number;
   timestamp: number;
-  source: 'native' | 'manual';
+  source: 'native' | 'manual' | 'hotkey' | 'clipboard' | 'browser' | 'own-app';
+  /** Bounding rectangle of the selected text, when the backend can measure it */
+  bounds?: SelectionBounds;
+  /** Executable name of the source application, e.g. "notepad.exe" */
+  app?: string;
+  /** Gesture that produced the selection, when the backend can tell */
+  granularity?: SelectionGranularity;
+  /** Individual ranges, present only when the app reported more than one */
+  ranges?: SelectedRange[];
+  /** Surrounding text (up to contextChars on each side), when enabled */
+  context?: SelectionContext;
+  /** The selection was longer than maxReadLength and the text was cut */
+  truncated?: boolean;
+  /**
+   * Read while an IME composition was active, so the text may be half-composed
+   * input; only delivered with imeComposition 'flag' or for explicit captures
+   */
+  composing?: boolean;
+  /**
+   * File-system paths when the selection is a set of files in a file manager
+   * (Windows Explorer); text then lists the same paths one per line
+   */
+  files?: string[];
+  /** Detected language tag (BCP 47, e.g. "en", "zh") when detectLanguage is on */
+  language?: string;
+  /** Source element's native identity, when exposeElementHandles is on */
+  element?: NativeElementHandle;
+  /**
+   * What changed since the previous selection in the same element, when
+   * selectionDiffs is on and the user extended or shrank that selection
+   */
+  diff?: SelectionDiff;
+  /** Formatted content (emphasis, links), when captureRichText is on and the source has it */
+  rich?: RichText;
+  /** Paragraph direction the source app lays the text out in, when it reports one */
+  direction?: TextDirection;
+  /**
+   * Base direction, direction runs and their display order, when detectDirection
+   * is on and the text contains right-to-left script. text stays in logical order.
+   */
+  bidi?: BidiInfo;
+  /** URLs, email addresses, phone numbers and file paths in text, when detectEntities is on */
+  entities?: SelectionEntity[];
+  /**
+   * Namespaced extra data attached by backends or plugins, e.g. "browser.url".
+   * Values must be structured-clone/JSON safe so they survive IPC unchanged.
+   */
+  extensions?: Record<string, unknown>;
 }
 
-export interface NativeSelectionService {
-  start(): Promise<void>;
-  stop(): Promise<void>;
+/**
+ * Return a copy of the event with an extension field set
+ */
+export function withExtension(event: SelectionEvent, key: string, value: unknown): SelectionEvent {
+  return { ...event, extensions: { ...event.extensions, [key]: value } };
+}
+
+/**
+ * Monitor state transitions reported alongside selections
+ * - 'ready': the backend finished initializing and is capturing
+ * - 'failed': the backend could not initialize (start() rejects with the reason)
+ * - 'suppressed-secure-desktop': the UAC secure desktop or another user's session
+ *   has the foreground, so capture is paused
+ * - 'active': capture resumed after a suppression
+ * - 'session-suspended': the session locked or the machine went to sleep; the
+ *   backend is stopped until it comes back
+ * - 'session-resumed': the backend was started again after unlock/wake
+ * - 'backend-exited': the backend's worker died while running; the watchdog
+ *   restarts it with exponential backoff
+ * - 'backend-restarted': the watchdog brought the backend back
+ * - 'selection-cleared': the previously reported selection was deselected (or a
+ *   pending one vanished before it settled); overlays should hide. Only backends
+ *   that observe the selection itself emit it.
+ */
+export type SelectionState =
+  | 'ready'
+  | 'failed'
+  | 'suppressed-secure-desktop'
+  | 'active'
+  | 'session-suspended'
+  | 'session-resumed'
+  | 'selection-cleared'
+  | 'backend-exited'
+  | 'backend-restarted';
+
+/**
+ * Why a selection operation failed
+ * - 'PERMISSION_DENIED': the OS refused access (run elevated / grant accessibility)
+ * - 'BACKEND_UNAVAILABLE': no usable backend (addon not built, platform unsupported)
+ * - 'ALREADY_RUNNING' / 'NOT_RUNNING': the call does not fit the service state
+ * - 'TIMEOUT': the backend did not answer in time
+ * - 'PLATFORM_ERROR': a native call failed; platformCode holds e.g. the HRESULT
+ */
+export type SelectionErrorCode =
+  | 'PERMISSION_DENIED'
+  | 'BACKEND_UNAVAILABLE'
+  | 'ALREADY_RUNNING'
+  | 'NOT_RUNNING'
+  | 'TIMEOUT'
+  | 'PLATFORM_ERROR';
+
+export class SelectionError extends Error {
+  constructor(
+    message: string,
+    public code: SelectionErrorCode,
+    public platformCode?: number
+  ) {
+    super(message);
+    this.name = 'SelectionError';
+  }
+
+  /**
+   * Plain-data form, so errors survive JSON.stringify (see selection-serialization)
+   */
+  toJSON(): { code: SelectionErrorCode; message: string; platformCode?: number } {
+    return this.platformCode === undefined
+      ? { code: this.code, message: this.message }
+      : { code: this.code, message: this.message, platformCode: this.platformCode };
+  }
+}
+
+const E_ACCESSDENIED = 0x80070005;
+const UIA_E_TIMEOUT = 0x80131505;
+
+/**
+ * Classify a failure reported by the native addon, which formats COM failures
+ * as "<what> (HRESULT 0x...)"
+ */
+function nativeFailure(message: string): SelectionError {
+  const match = /HRESULT 0x([0-9a-f]+)/i.exec(message);
+  if (!match) {
+    return new SelectionError(message, 'PLATFORM_ERROR');
+  }
+  const hr = parseInt(match[1], 16);
+  const code = hr === E_ACCESSDENIED ? 'PERMISSION_DENIED' : hr === UIA_E_TIMEOUT ? 'TIMEOUT' : 'PLATFORM_ERROR';
+  return new SelectionError(message, code, hr);
+}
+
+/**
+ * Insertion point reported in caret tracking mode while nothing is selected
+ */
+export interface CaretEvent {
+  x: number;
+  y: number;
+  /** Caret rectangle (usually 1-2px wide, one line tall) */
+  bounds: SelectionBounds;
+  app?: string;
+  timestamp: number;
+}
+
+/**
+ * A delivered selection tagged with its position in the delivery sequence
+ */
+export interface VersionedSelection {
+  generation: number;
+  event: SelectionEvent;
+}
+
+export interface SelectionStateEvent {
+  state: SelectionState;
+  timestamp: number;
+}
+
+/**
+ * Shape of the payload delivered by the UIAutomation native addon (declared in
+ * native-addon/index.d.ts). Coordinates are physical pixels and are converted
+ * to DIP before they reach an event.
+ */
+type NativeSelectionPayload = NativeAddon.SelectionPayload;
+type TextAround = NativeAddon.TextAtPointPayload;
+
+/**
+ * Restarts a backend whose worker died after start() succeeded
+ */
+export interface WatchdogOptions {
+  enabled: boolean;
+  /** Delay before the first restart; doubles per consecutive failure */
+  initialDelayMs: number;
+  maxDelayMs: number;
+}
+
+export const DEFAULT_WATCHDOG_OPTIONS: WatchdogOptions = {
+  enabled: true,
+  initialDelayMs: 1000,
+  maxDelayMs: 60000,
+};
+
+/**
+ * Opt-in screenshot of the area around each selection, attached by the enrichment
+ * scheduler as extensions['screen.thumbnail'] (a PNG data URL)
+ */
+export interface ThumbnailOptions {
+  enabled: boolean;
+  /** At most one thumbnail per this interval */
+  minIntervalMs: number;
+  /** Margin captured around the selection bounds, in DIP */
+  padding: number;
+  /** Thumbnails wider than this are scaled down */
+  maxWidth: number;
+}
+
+export const DEFAULT_THUMBNAIL_OPTIONS: ThumbnailOptions = {
+  enabled: false,
+  minIntervalMs: 5000,
+  padding: 40,
+  maxWidth: 320,
+};
+
+export type SelectionTrigger = 'mouse-up' | 'key-up' | 'both';
+
+/**
+ * - 'always': monitor selections continuously
+ * - 'hotkey': only read the selection when captureHotkey is pressed
+ */
+export type CaptureMode = 'always' | 'hotkey';
+
+/**
+ * - 'auto': the platform's native backend, falling back to clipboard mode if it cannot start
+ * - 'ui-automation': Windows UI Automation only
+ * - 'polling': low-fidelity UI Automation fallback that reads the focused control's
+ *   selection every pollIntervalMs instead of waiting for events (Windows only)
+ * - 'clipboard': treat every copied text as a selection (works everywhere, no position)
+ * - 'winevent': lightweight Windows backend driven by SetWinEventHook text selection
+ *   notifications instead of UI Automation subscriptions; suits older Win32 apps.
+ *   'ui-automation' also falls back to it when no UIA event handler can be registered.
+ * - 'own-app': only selections inside this app's own windows, read from their pages;
+ *   no global hooks and no accessibility permission (works everywhere)
+ */
+export type SelectionBackend = 'auto' | 'ui-automation' | 'polling' | 'winevent' | 'clipboard' | 'own-app';
+
+/**
+ * Tunables for a native selection service
+ */
+export interface ListenerConfig {
+  /** Time the selection must stay unchanged before it is reported */
+  debounceMs: number;
+  /** Repeats of the previous text within this window are suppressed (0 = never) */
+  dedupeWindowMs: number;
+  /** Input gestures that may produce a selection event */
+  triggers: SelectionTrigger;
+  /** Continuous monitoring, or read the selection only on a global hotkey */
+  captureMode: CaptureMode;
+  /** Electron accelerator used in 'hotkey' capture mode */
+  captureHotkey: string;
+  /** Selections longer than this are ignored (0 = no limit) */
+  maxSelectionLength: number;
+  /** At most this many characters are read from the app; longer selections are
+   * truncated and flagged with `truncated` (0 = read everything) */
+  maxReadLength: number;
+  /** Preferred backend; 'auto' picks the best one for the platform and falls back */
+  backend: SelectionBackend;
+  /** Only capture from these apps (process names, ".exe" optional); empty = all apps */
+  includeApps: string[];
+  /** Never capture (or even read text) from these apps */
+  excludeApps: string[];
+  /** Windows: also read the selection after a mouse drag (low-level mouse hook) */
+  mouseHook: boolean;
+  /** Characters of surrounding text captured on each side of a selection (0 = off) */
+  contextChars: number;
+  /** Interval of the 'polling' and 'own-app' backends */
+  pollIntervalMs: number;
+  /** Accessibility calls into a busy app give up after this long and raise a TIMEOUT error (0 = OS default) */
+  callTimeoutMs: number;
+  /** How long start() waits for the backend to report it is ready */
+  readyTimeoutMs: number;
+  /** How long stop() waits for the backend's threads to exit before giving up with a TIMEOUT error */
+  stopTimeoutMs: number;
+  /** Retries while waiting for the desktop session and starting the backend (login items) */
+  startRetries: number;
+  /** Delay before the first start retry; doubles per retry up to 8x */
+  startRetryDelayMs: number;
+  /** Restart the backend with backoff if it dies while running */
+  watchdog: WatchdogOptions;
+  /** Sensitive-content filtering applied before events are stored or delivered */
+  redaction: RedactionOptions;
+  /** Number of recent selections kept for history() (0 = history disabled) */
+  historyCapacity: number;
+  /** Persist delivered selections to a local SQLite database for queryHistory() */
+  historyStore: HistoryStoreOptions;
+  /** Screenshot thumbnails of the selection's surroundings */
+  thumbnail: ThumbnailOptions;
+  /** Time source for timestamps, debounce and retries (replaceable in tests) */
+  clock: Clock;
+  /** Stop capture while the session is locked or asleep and restart afterwards */
+  pauseOnSessionLock: boolean;
+  /** What subscribe() receivers do when their reader falls behind */
+  backpressure: BackpressurePolicy;
+  /** Also report the caret position via onCaret() when nothing is selected */
+  trackCaret: boolean;
+  /**
+   * Attach the source element's native identity (event.element) for follow-up
+   * platform queries. Off by default: handles let consumers reach into other apps.
+   */
+  exposeElementHandles: boolean;
+  /** OCR the dragged area when an app exposes no text (see setOcrEngine()) */
+  ocr: OcrOptions;
+  /** Tag delivered selections with their detected language */
+  detectLanguage: boolean;
+  /** Attach the URLs, email addresses, phone numbers and paths found in the text */
+  detectEntities: boolean;
+  /** Attach bidi runs to right-to-left and mixed-direction selections */
+  detectDirection: boolean;
+  /** Attach a diff against the previous selection when only one edge moved */
+  selectionDiffs: boolean;
+  /**
+   * Selections read while an IME composition (CJK pre-edit text) is active:
+   * 'suppress' drops them, 'flag' delivers them with composing set
+   */
+  imeComposition: 'suppress' | 'flag';
+  /** Also read formatting (bold/italic/underline/links) where the source exposes it */
+  captureRichText: boolean;
+  /** Normalization and clean-up applied to every backend's text before dispatch */
+  sanitize: SanitizeOptions;
+}
+
+export const DEFAULT_LISTENER_CONFIG: ListenerConfig = {
+  debounceMs: 500,
+  dedupeWindowMs: 800,
+  triggers: 'both',
+  captureMode: 'always',
+  captureHotkey: 'CommandOrControl+Shift+C',
+  maxSelectionLength: 0,
+  maxReadLength: 0,
+  backend: 'auto',
+  includeApps: [],
+  excludeApps: [],
+  mouseHook: true,
+  contextChars: 0,
+  pollIntervalMs: 500,
+  callTimeoutMs: 2000,
+  readyTimeoutMs: 5000,
+  stopTimeoutMs: 5000,
+  startRetries: 3,
+  startRetryDelayMs: 1000,
+  watchdog: DEFAULT_WATCHDOG_OPTIONS,
+  redaction: DEFAULT_REDACTION_OPTIONS,
+  historyCapacity: 0,
+  historyStore: DEFAULT_HISTORY_STORE_OPTIONS,
+  thumbnail: DEFAULT_THUMBNAIL_OPTIONS,
+  clock: systemClock,
+  pauseOnSessionLock: true,
+  backpressure: 'drop-oldest',
+  trackCaret: false,
+  exposeElementHandles: false,
+  ocr: DEFAULT_OCR_OPTIONS,
+  detectLanguage: false,
+  detectEntities: false,
+  detectDirection: false,
+  selectionDiffs: false,
+  imeComposition: 'suppress',
+  captureRichText: false,
+  sanitize: DEFAULT_SANITIZE_OPTIONS,
+};
+
+export type SelectionCapability = 'selection-events' | 'selection-position';
+
+export interface BackendFailure {
+  backend: string;
+  reason: string;
+}
+
+/**
+ * Summary of what ended up available after start() - meant to be rendered as-is
+ * in a diagnostics screen.
+ */
+export interface StartReport {
+  backend: string;
+  capabilities: SelectionCapability[];
+  failures: BackendFailure[];
+  remediation: string[];
+}
+
+/**
+ * Diagnostics snapshot returned by getStatus()
+ */
+export interface SelectionStatus {
+  isRunning: boolean;
+  isPaused: boolean;
+  platform: string;
+  /** Active backend, e.g. 'ui-automation', or why there is none ('not-available') */
+  method: string;
+  /** Time the last selection was delivered to callbacks */
+  lastEventAt: number | null;
+  /** Most recent backend error, cleared by a successful start */
+  lastError: string | null;
+  /** Times the watchdog restarted the backend since the service was created */
+  restarts: number;
+}
+
+/**
+ * Read/observe half of a selection service
+ */
+export interface SelectionSource {
   onSelection(callback: (event: SelectionEvent) => void): void;
+  onMatch(matcher: SelectionMatcher, action: (event: SelectionEvent) => void): void;
+  onStateChange(callback: (event: SelectionStateEvent) => void): void;
+  onError(callback: (error: SelectionError) => void): void;
+  onCaret(callback: (event: CaretEvent) => void): void;
+  history(): SelectionEvent[];
+  queryHistory(query?: HistoryQuery): Promise<SelectionEvent[]>;
+  selectionAt(timestamp: number): SelectionEvent | null;
+  clearHistory(): void;
+  captureNow(): SelectionEvent | null;
+  metrics(): MetricsSnapshot;
+  selectionWithContext(charsBefore: number, charsAfter: number): SelectionWithContext | null;
+  expandSelection(unit: EnclosingUnit, locale?: string): SelectionWithContext | null;
+  wordAtPoint(x: number, y: number, locale?: string): Promise<WordAtPoint | null>;
+  setWordDictionary(dictionary: WordDictionary | null): void;
+  getSelectionRich(): RichText | null;
+  addEnricher(name: string, task: Enricher, options?: Partial<EnricherOptions>): void;
+  addProcessor(processor: SelectionProcessor, position?: ProcessorPosition): void;
+  removeProcessor(name: string): void;
+  onEnrichment(callback: (event: SelectionEvent) => void): void;
+  enrich(event: SelectionEvent, name: string, signal?: AbortSignal): Promise<unknown>;
+  submit(event: SelectionEvent): void;
+  subscribe(capacity?: number, backpressure?: BackpressurePolicy): SelectionReceiver;
+  generation(): number;
+  selectionIfNewer(generation: number): VersionedSelection | null;
+}
+
+/**
+ * Lifecycle half of a selection service
+ */
+export interface SelectionControl {
+  start(): Promise<StartReport>;
+  stop(): Promise<void>;
+  pause(): void;
+  resume(): void;
   isSupported(): boolean;
-  getStatus(): { isRunning: boolean; platform: string; method: string };
+  getStatus(): SelectionStatus;
+  getStartReport(): StartReport | null;
+  enterPrecisionMode(durationMs: number): void;
+  exitPrecisionMode(): void;
+  setOcrEngine(engine: OcrEngine): void;
+}
+
+export interface NativeSelectionService extends SelectionSource, SelectionControl {}
+
+/**
+ * Build a service from independently supplied halves, e.g. a scripted source
+ * driven by a real backend's lifecycle.
+ */
+export function composeSelectionService(source: SelectionSource, control: SelectionControl): NativeSelectionService {
+  return {
+    onSelection: (callback) => source.onSelection(callback),
+    onMatch: (matcher, action) => source.onMatch(matcher, action),
+    onStateChange: (callback) => source.onStateChange(callback),
+    onError: (callback) => source.onError(callback),
+    onCaret: (callback) => source.onCaret(callback),
+    history: () => source.history(),
+    selectionAt: (timestamp) => source.selectionAt(timestamp),
+    clearHistory: () => source.clearHistory(),
+    queryHistory: (query) => source.queryHistory(query),
+    captureNow: () => source.captureNow(),
+    metrics: () => source.metrics(),
+    selectionWithContext: (charsBefore, charsAfter) => source.selectionWithContext(charsBefore, charsAfter),
+    expandSelection: (unit, locale) => source.expandSelection(unit, locale),
+    wordAtPoint: (x, y, locale) => source.wordAtPoint(x, y, locale),
+    setWordDictionary: (dictionary) => source.setWordDictionary(dictionary),
+    getSelectionRich: () => source.getSelectionRich(),
+    addEnricher: (name, task, options) => source.addEnricher(name, task, options),
+    addProcessor: (processor, position) => source.addProcessor(processor, position),
+    removeProcessor: (name) => source.removeProcessor(name),
+    onEnrichment: (callback) => source.onEnrichment(callback),
+    enrich: (event, name, signal) => source.enrich(event, name, signal),
+    submit: (event) => source.submit(event),
+    subscribe: (capacity, backpressure) => source.subscribe(capacity, backpressure),
+    generation: () => source.generation(),
+    selectionIfNewer: (generation) => source.selectionIfNewer(generation),
+    start: () => control.start(),
+    stop: () => control.stop(),
+    pause: () => control.pause(),
+    resume: () => control.resume(),
+    isSupported: () => control.isSupported(),
+    getStatus: () => control.getStatus(),
+    getStartReport: () => control.getStartReport(),
+    enterPrecisionMode: (durationMs) => control.enterPrecisionMode(durationMs),
+    exitPrecisionMode: () => control.exitPrecisionMode(),
+    setOcrEngine: (engine) => control.setOcrEngine(engine),
+  };
+}
+
+function createStartReport(
+  backend: string,
+  capabilities: SelectionCapability[],
+  failures: BackendFailure[],
+  remediation: string[]
+): StartReport {
+  return { backend, capabilities, failures, remediation };
+}
+
+/**
+ * Shared plumbing for every platform service: callback registration,
+ * debouncing/deduplication and the optional selection history.
+ */
+export abstract class BaseNativeSelectionService implements NativeSelectionService, SessionAware {
+  protected isRunning = false;
+  protected isPaused = false;
+  protected lastEventAt: number | null = null;
+  private lastDelivered: SelectionEvent | null = null;
+  private deliveredGeneration = 0;
+  protected lastError: string | null = null;
+  protected config: ListenerConfig;
+  private selectionCallbacks: ((event: SelectionEvent) => void)[] = [];
+  private stateCallbacks: ((event: SelectionStateEvent) => void)[] = [];
+  private errorCallbacks: ((error: SelectionError) => void)[] = [];
+  private caretCallbacks: ((event: CaretEvent) => void)[] = [];
+  private enrichmentCallbacks: ((event: SelectionEvent) => void)[] = [];
+  private receivers = new Set<SelectionReceiver>();
+  protected ocrEngine: OcrEngine;
+  private enrichment = new EnrichmentScheduler();
+  private precisionTimer: TimerHandle | null = null;
+  private sessionHolds = new Set<'locked' | 'asleep'>();
+  private restartAfterSession = false;
+  private watchdogTimer: TimerHandle | null = null;
+  private watchdogFailures = 0;
+  private restartCount = 0;
+  private dispatcher: SelectionDispatcher;
+  private wordDictionary: WordDictionary | null = null;
+  private pipeline = new SelectionPipeline();
+  private redactor: SelectionProcessor | null;
+  private sanitizer: SelectionProcessor | null;
+  private selectionHistory: SelectionHistory;
+  private historyStore: Promise<SelectionStore | null> | null = null;
+  protected selectionMetrics: SelectionMetrics;
+
+  constructor(config: ListenerConfig) {
+    this.config = config;
+    this.selectionHistory = new SelectionHistory(config.historyCapacity);
+    this.selectionMetrics = new SelectionMetrics(config.clock);
+    this.ocrEngine = new TesseractEngine(config.ocr.languages);
+    this.dispatcher = new SelectionDispatcher({
+      debounceMs: config.debounceMs,
+      dedupeWindowMs: config.dedupeWindowMs,
+    }, config.clock);
+    this.dispatcher.onEvent((event) => this.deliver(event));
+    this.dispatcher.onDrop((_event, reason) => this.selectionMetrics.recordDropped(reason));
+    this.enrichment.onResult((event, name, value) => this.deliverEnrichment(withExtension(event, name, value)));
+    if (config.thumbnail.enabled) {
+      this.addThumbnailEnricher(config.thumbnail);
+    }
+    this.redactor = config.redaction.enabled ? redactionProcessor(config.redaction) : null;
+    this.sanitizer = isSanitizeEnabled(config.sanitize) ? sanitizeProcessor(config.sanitize) : null;
+    if (config.selectionDiffs) {
+      this.pipeline.add(diffProcessor());
+    }
+    if (config.detectEntities) {
+      this.pipeline.add(entityProcessor());
+    }
+    if (config.detectLanguage) {
+      this.pipeline.add(languageProcessor());
+    }
+    if (config.detectDirection) {
+      this.pipeline.add(bidiProcessor());
+    }
+  }
+
+  abstract start(): Promise<StartReport>;
+  abstract stop(): Promise<void>;
+  abstract isSupported(): boolean;
+  abstract getStatus(): SelectionStatus;
+  abstract getStartReport(): StartReport | null;
+
+  onSelection(callback: (event: SelectionEvent) => void): void {
+    this.selectionCallbacks.push(callback);
+  }
+
+  /**
+   * Call action only for delivered selections that meet the matcher, e.g.
+   * { all: [{ language: 'ja' }, { minLength: 3 }, { source: 'browser' }] }
+   */
+  onMatch(matcher: SelectionMatcher, action: (event: SelectionEvent) => void): void {
+    const matches = compileMatcher(matcher);
+    this.selectionCallbacks.push((event) => {
+      if (matches(event)) {
+        action(event);
+      }
+    });
+  }
+
+  onStateChange(callback: (event: SelectionStateEvent) => void): void {
+    this.stateCallbacks.push(callback);
+  }
+
+  /**
+   * An independent pull-based stream of delivered selections, buffering up to
+   * `capacity` events for this subscriber; the config's backpressure policy
+   * applies unless overridden. close() the receiver when done.
+   */
+  subscribe(capacity = 32, backpressure = this.config.backpressure): SelectionReceiver {
+    const receiver = new SelectionReceiver(capacity, backpressure, (closed) => this.receivers.delete(closed));
+    this.receivers.add(receiver);
+    return receiver;
+  }
+
+  /**
+   * Runtime failures inside the backend (native threads, hooks, polling) that do
+   * not surface through start() or a selection read
+   */
+  onError(callback: (error: SelectionError) => void): void {
+    this.errorCallbacks.push(callback);
+  }
+
+  /**
+   * Caret moves while nothing is selected, for assistive and autocomplete
+   * tools; only reported with trackCaret enabled and by backends that can
+   * locate the caret
+   */
+  onCaret(callback: (event: CaretEvent) => void): void {
+    this.caretCallbacks.push(callback);
+  }
+
+  /**
+   * The last delivered selection with up to the requested amount of surrounding
+   * text. Context is captured with the selection, so at most contextChars per
+   * side is available.
+   */
+  selectionWithContext(charsBefore: number, charsAfter: number): SelectionWithContext | null {
+    const event = this.lastDelivered;
+    if (!event) {
+      return null;
+    }
+    const before = event.context ? event.context.before : '';
+    const after = event.context ? event.context.after : '';
+    return {
+      text: event.text,
+      before: charsBefore > 0 ? before.slice(-charsBefore) : '',
+      after: after.slice(0, Math.max(0, charsAfter)),
+    };
+  }
+
+  /**
+   * The last delivered selection widened to the enclosing word, sentence or
+   * paragraph, e.g. a partially selected word to the whole word. Works on the
+   * context captured with the selection (contextChars), so a unit longer than
+   * that is cut at the edge of the context; without context the selection is
+   * returned unchanged.
+   */
+  expandSelection(unit: EnclosingUnit, locale: string | undefined = this.lastDelivered?.language): SelectionWithContext | null {
+    const event = this.lastDelivered;
+    if (!event) {
+      return null;
+    }
+    const before = event.context ? event.context.before : '';
+    const after = event.context ? event.context.after : '';
+    const whole = before + event.text + after;
+    const expanded = enclosingSegment(whole, before.length, before.length + event.text.length, unit, locale);
+    return {
+      text: expanded.text,
+      before: whole.slice(0, expanded.start),
+      after: whole.slice(expanded.end),
+    };
+  }
+
+  /**
+   * The word under a screen point (DIP), e.g. for hover lookups without a
+   * selection. Words follow Unicode segmentation for the locale, refined with
+   * the dictionary from setWordDictionary() for Chinese and Japanese, so every
+   * app yields the same units whatever its own double-click rules are. Null when
+   * the backend cannot read text at a point or there is no word there.
+   */
+  async wordAtPoint(x: number, y: number, locale?: string): Promise<WordAtPoint | null> {
+    const around = await this.readTextAround(dipToPhysical({ x, y }), 100);
+    if (!around) {
+      return null;
+    }
+    const whole = around.before + around.after;
+    if (this.config.redaction.enabled && redactSelection(whole, this.config.redaction) !== whole) {
+      return null;
+    }
+    const word = wordAt(whole, around.before.length, locale, this.wordDictionary ?? undefined);
+    if (!word) {
+      return null;
+    }
+    return { text: word.text, before: whole.slice(0, word.start), after: whole.slice(word.end), app: around.app };
+  }
+
+  /**
+   * Custom word list for segmenting Chinese and Japanese in wordAtPoint(),
+   * or null for the platform's built-in segmentation
+   */
+  setWordDictionary(dictionary: WordDictionary | null): void {
+    this.wordDictionary = dictionary;
+  }
+
+  /**
+   * Text on either side of a point in physical pixels; backends that cannot
+   * read text at a point return null
+   */
+  protected async readTextAround(_point: Point, _chars: number): Promise<TextAround | null> {
+    return null;
+  }
+
+  /**
+   * Formatted content of the last delivered selection; a single plain run when
+   * the source reported no formatting (or captureRichText is off)
+   */
+  getSelectionRich(): RichText | null {
+    const event = this.lastDelivered;
+    if (!event) {
+      return null;
+    }
+    return event.rich ?? plainRichText(event.text);
+  }
+
+  /**
+   * Incremented on every delivered selection; 0 until the first one
+   */
+  generation(): number {
+    return this.deliveredGeneration;
+  }
+
+  /**
+   * The last delivered selection if it arrived after the given generation, so
+   * pollers can detect "nothing new" without copying or transferring the text
+   */
+  selectionIfNewer(generation: number): VersionedSelection | null {
+    if (!this.lastDelivered || this.deliveredGeneration <= generation) {
+      return null;
+    }
+    return { generation: this.deliveredGeneration, event: this.lastDelivered };
+  }
+
+  /**
+   * Run a background task for every delivered selection. Its result arrives via
+   * onEnrichment() as a copy of the event with extensions[name] set; the task is
+   * cancelled if a newer selection is delivered first.
+   */
+  addEnricher(name: string, task: Enricher, options: Partial<EnricherOptions> = {}): void {
+    this.enrichment.register(name, task, options);
+  }
+
+  /**
+   * Add a stage to the processing pipeline every settled selection goes through
+   * before delivery. Built-in stages are named 'diff', 'entities', 'language'
+   * and 'bidi' (when enabled in the config); new stages go last unless placed
+   * before one of those. A stage with the same name is replaced.
+   */
+  addProcessor(processor: SelectionProcessor, position?: ProcessorPosition): void {
+    this.pipeline.add(processor, position);
+  }
+
+  removeProcessor(name: string): void {
+    this.pipeline.remove(name);
+  }
+
+  onEnrichment(callback: (event: SelectionEvent) => void): void {
+    this.enrichmentCallbacks.push(callback);
+  }
+
+  /**
+   * Compute one enrichment for a selection now and hand back its value, e.g.
+   * for enrichers registered with { onDemand: true } that are too costly to run
+   * for every event. Abort the signal to cancel; the result is undefined then.
+   */
+  enrich(event: SelectionEvent, name: string, signal?: AbortSignal): Promise<unknown> {
+    return this.enrichment.run(name, event, signal);
+  }
+
+  /**
+   * Stop delivering selections without tearing the backend down
+   */
+  pause(): void {
+    if (this.isPaused) {
+      return;
+    }
+    this.isPaused = true;
+    this.resetDispatcher();
+    this.onPausedChanged(true);
+  }
+
+  resume(): void {
+    if (!this.isPaused) {
+      return;
+    }
+    this.isPaused = false;
+    this.onPausedChanged(false);
+  }
+
+  /**
+   * Temporarily capture at the highest fidelity, e.g. while a popup is open:
+   * debounce is disabled and precisionOnly enrichers run. The configured
+   * steady state returns after durationMs, or earlier via exitPrecisionMode().
+   * Calling it again extends the window.
+   */
+  enterPrecisionMode(durationMs: number): void {
+    if (this.precisionTimer) {
+      this.config.clock.clearTimeout(this.precisionTimer);
+    } else {
+      this.dispatcher.setDebounceMs(0);
+      this.enrichment.setPrecision(true);
+      this.onPrecisionModeChanged(true);
+    }
+    this.precisionTimer = this.config.clock.setTimeout(() => this.exitPrecisionMode(), Math.max(0, durationMs));
+  }
+
+  exitPrecisionMode(): void {
+    if (!this.precisionTimer) {
+      return;
+    }
+    this.config.clock.clearTimeout(this.precisionTimer);
+    this.precisionTimer = null;
+    this.dispatcher.setDebounceMs(this.config.debounceMs);
+    this.enrichment.setPrecision(false);
+    this.onPrecisionModeChanged(false);
+  }
+
+  /**
+   * Replace the engine used by the OCR fallback (tesseract CLI by default)
+   */
+  setOcrEngine(engine: OcrEngine): void {
+    this.ocrEngine = engine;
+  }
+
+  /**
+   * Hook for backends that can raise their own fidelity in precision mode
+   */
+  protected onPrecisionModeChanged(_active: boolean): void {
+    // Default: only the shared debounce and enrichment change
+  }
+
+  /**
+   * The session locked or the machine is going to sleep: stop the backend so it
+   * does not keep handles that are stale after wake
+   */
+  async suspendForSession(change: SessionChange): Promise<void> {
+    const wasHeld = this.sessionHolds.size > 0;
+    this.sessionHolds.add(change === 'lock' ? 'locked' : 'asleep');
+    if (wasHeld) {
+      return;
+    }
+    this.restartAfterSession = this.isRunning;
+    this.resetDispatcher();
+    this.emitState('session-suspended');
+    if (this.restartAfterSession) {
+      await this.stop();
+    }
+  }
+
+  /**
+   * Restart the backend once the session is both unlocked and awake
+   */
+  async resumeAfterSession(change: SessionChange): Promise<void> {
+    if (!this.sessionHolds.delete(change === 'unlock' ? 'locked' : 'asleep') || this.sessionHolds.size > 0) {
+      return;
+    }
+    if (this.restartAfterSession) {
+      this.restartAfterSession = false;
+      await this.start();
+    }
+    this.emitState('session-resumed');
+  }
+
+  /**
+   * Watchdog: called by a backend whose worker died while running. Restarts it
+   * after a delay that doubles with each consecutive failed attempt.
+   */
+  protected restartAfterExit(reason: string): void {
+    if (!this.config.watchdog.enabled || this.watchdogTimer) {
+      return;
+    }
+    const { initialDelayMs, maxDelayMs } = this.config.watchdog;
+    const delay = Math.min(initialDelayMs * Math.pow(2, this.watchdogFailures), maxDelayMs);
+    this.emitError(new SelectionError(`${reason}; restarting in ${delay}ms`, 'PLATFORM_ERROR'));
+    this.watchdogTimer = this.config.clock.setTimeout(async () => {
+      this.watchdogTimer = null;
+      try {
+        await this.stop();
+        await this.start();
+        this.watchdogFailures = 0;
+        this.restartCount++;
+        this.emitState('backend-restarted');
+      } catch (error) {
+        this.watchdogFailures++;
+        this.restartAfterExit(`Restart failed: ${error instanceof Error ? error.message : String(error)}`);
+      }
+    }, delay);
+  }
+
+  /**
+   * Drop a pending watchdog restart, e.g. because the backend was stopped on purpose
+   */
+  protected cancelRestart(): void {
+    if (this.watchdogTimer) {
+      this.config.clock.clearTimeout(this.watchdogTimer);
+      this.watchdogTimer = null;
+    }
+  }
+
+  /**
+   * Hook for backends that can stop work at the source while paused
+   */
+  protected onPausedChanged(_paused: boolean): void {
+    // Default: events are simply dropped in emitSelection()
+  }
+
+  /**
+   * Recent selections, most recent first (empty unless historyCapacity > 0)
+   */
+  history(): SelectionEvent[] {
+    return this.selectionHistory.entries();
+  }
+
+  /**
+   * What was selected at the given time (ms since epoch), answered from history
+   */
+  selectionAt(timestamp: number): SelectionEvent | null {
+    return this.selectionHistory.at(timestamp);
+  }
+
+  /**
+   * Search the persistent history (historyStore), most recent first; empty when
+   * the store is disabled or could not be opened
+   */
+  async queryHistory(query: HistoryQuery = {}): Promise<SelectionEvent[]> {
+    const store = await this.openHistoryStore();
+    return store ? store.query(query) : [];
+  }
+
+  /**
+   * Forget recorded selections, in memory and in the persistent store
+   */
+  clearHistory(): void {
+    this.selectionHistory.clear();
+    this.openHistoryStore()
+      .then((store) => store?.clear())
+      .catch((error) => console.error('[NATIVE-SERVICE] Error clearing the history store:', error));
+  }
+
+  // Opened on first use; a failure is reported once and disables the store
+  private openHistoryStore(): Promise<SelectionStore | null> {
+    if (!this.config.historyStore.enabled) {
+      return Promise.resolve(null);
+    }
+    if (!this.historyStore) {
+      this.historyStore = SelectionStore.open(this.config.historyStore).catch((error) => {
+        console.error('[NATIVE-SERVICE] Selection history store unavailable:', error);
+        return null;
+      });
+    }
+    return this.historyStore;
+  }
+
+  /**
+   * Event counters and delivery latency since the service was created
+   */
+  metrics(): MetricsSnapshot {
+    return this.selectionMetrics.snapshot();
+  }
+
+  /**
+   * Read the current selection right now and deliver it through the priority
+   * lane, skipping debounce and dedupe. Meant for explicit user actions.
+   * Returns null when nothing is selected; throws a SelectionError if the
+   * backend could not be queried.
+   */
+  captureNow(): SelectionEvent | null {
+    const event = this.readCurrentSelection();
+    if (!event) {
+      return null;
+    }
+    const hotkeyEvent = this.prepare({ ...event, source: 'hotkey', timestamp: this.config.clock.now() });
+    if (!hotkeyEvent) {
+      return null;
+    }
+    this.dispatchSelection(hotkeyEvent);
+    return hotkeyEvent;
+  }
+
+  /**
+   * Feed a selection captured outside the backend (e.g. by a browser extension)
+   * through the same debounce, dedupe, history and enrichment as native events
+   */
+  submit(event: SelectionEvent): void {
+    this.emitSelection(event);
+  }
+
+  /**
+   * Current selection as seen by the backend; backends that cannot query on
+   * demand return null.
+   */
+  protected readCurrentSelection(): SelectionEvent | null {
+    return null;
+  }
+
+  /**
+   * Hand a raw selection to the shared debounce/dedupe layer. Every source,
+   * including submit(), is sanitized, redacted and length-checked here before
+   * the event is buffered.
+   */
+  protected emitSelection(raw: SelectionEvent): void {
+    const event = this.prepare(raw);
+    if (event) {
+      this.dispatchSelection(event);
+    }
+  }
+
+  // Sanitize first: full-width digits or zero-width separators would hide a card
+  // number from redaction and only turn back into one after normalization
+  private prepare(raw: SelectionEvent): SelectionEvent | null {
+    const sanitized = this.sanitizer ? this.sanitizer.process(raw) ?? raw : raw;
+    const event = this.redactor ? this.redactor.process(sanitized) : sanitized;
+    if (!event) {
+      this.selectionMetrics.recordDropped('redacted');
+      return null;
+    }
+    if (this.config.maxSelectionLength > 0 && event.text.length > this.config.maxSelectionLength) {
+      this.selectionMetrics.recordDropped('too-long');
+      return null;
+    }
+    return event;
+  }
+
+  /**
+   * Queue a sanitized and redacted selection. Hotkey captures are explicit user
+   * actions and take the priority lane.
+   */
+  protected dispatchSelection(event: SelectionEvent): void {
+    if (event.source === 'hotkey') {
+      this.dispatcher.pushImmediate(event);
+      return;
+    }
+    if (this.isPaused) {
+      this.selectionMetrics.recordDropped('paused');
+      return;
+    }
+    this.dispatcher.push(event);
+  }
+
+  protected emitState(state: SelectionState): void {
+    const event: SelectionStateEvent = { state, timestamp: this.config.clock.now() };
+    this.stateCallbacks.forEach((callback) => {
+      try {
+        callback(event);
+      } catch (error) {
+        console.error('[NATIVE-SERVICE] Error in state callback:', error);
+      }
+    });
+  }
+
+  protected emitError(error: SelectionError): void {
+    this.lastError = error.message;
+    this.errorCallbacks.forEach((callback) => {
+      try {
+        callback(error);
+      } catch (callbackError) {
+        console.error('[NATIVE-SERVICE] Error in error callback:', callbackError);
+      }
+    });
+  }
+
+  protected emitCaret(event: CaretEvent): void {
+    if (this.isPaused) {
+      return;
+    }
+    this.caretCallbacks.forEach((callback) => {
+      try {
+        callback(event);
+      } catch (error) {
+        console.error('[NATIVE-SERVICE] Error in caret callback:', error);
+      }
+    });
+  }
+
+  protected describeStatus(platform: string, method: string): SelectionStatus {
+    return {
+      isRunning: this.isRunning,
+      isPaused: this.isPaused,
+      platform,
+      method,
+      lastEventAt: this.lastEventAt,
+      lastError: this.lastError,
+      restarts: this.restartCount,
+    };
+  }
+
+  /**
+   * Forget any selection still waiting to settle
+   */
+  protected resetDispatcher(): void {
+    this.dispatcher.reset();
+    this.enrichment.cancel();
+    this.pipeline.reset();
+  }
+
+  private deliver(settled: SelectionEvent): void {
+    // Processors run once per settled selection, not per raw event
+    const event = this.pipeline.run(settled);
+    if (!event) {
+      this.selectionMetrics.recordDropped('filtered');
+      return;
+    }
+    this.lastEventAt = event.timestamp;
+    this.lastDelivered = event;
+    this.deliveredGeneration++;
+    this.selectionMetrics.recordDelivered(event.timestamp);
+    this.selectionHistory.push(event);
+    if (this.config.historyStore.enabled) {
+      this.openHistoryStore()
+        .then((store) => store?.add(event))
+        .catch((error) => console.error('[NATIVE-SERVICE] Error writing to the history store:', error));
+    }
+    this.selectionCallbacks.forEach((callback) => {
+      try {
+        callback(event);
+      } catch (error) {
+        console.error('[NATIVE-SERVICE] Error in callback:', error);
+      }
+    });
+    this.receivers.forEach((receiver) => receiver.push(event));
+    this.enrichment.schedule(event);
+  }
+
+  private addThumbnailEnricher(options: ThumbnailOptions): void {
+    let lastCaptureAt = 0;
+    this.addEnricher('screen.thumbnail', async (event, signal) => {
+      // Heavily rate-limited: capturing a whole display is expensive
+      if (!event.bounds || event.timestamp - lastCaptureAt < options.minIntervalMs) {
+        return undefined;
+      }
+      lastCaptureAt = event.timestamp;
+      const image = await captureRegion({
+        x: event.bounds.x - options.padding,
+        y: event.bounds.y - options.padding,
+        width: event.bounds.width + options.padding * 2,
+        height: event.bounds.height + options.padding * 2,
+      });
+      if (!image || signal.aborted) {
+        return undefined;
+      }
+      return shrink(image, options.maxWidth).toDataURL();
+    });
+  }
+
+  private deliverEnrichment(event: SelectionEvent): void {
+    this.enrichmentCallbacks.forEach((callback) => {
+      try {
+        callback(event);
+      } catch (error) {
+        console.error('[NATIVE-SERVICE] Error in enrichment callback:', error);
+      }
+    });
+  }
 }
 
 /**
  * Windows Native Selection Service - UIAutomation Implementation
  * Uses Microsoft UI Automation with debounced selection detection
  */
-export class WindowsNativeSelectionService implements NativeSelectionService {
-  private isRunning = false;
-  private selectionCallbacks: ((event: SelectionEvent) => void)[] = [];
-  private lastSelection = '';
-  private lastSelectionTime = 0;
+export class WindowsNativeSelectionService extends BaseNativeSelectionService {
   private nativeAddon: any = null;
+  private addonLoadError: string | null = null;
+  private startReport: StartReport | null = null;
+  private readyWaiter: ((state: 'ready' | 'failed') => void) | null = null;
 
-  constructor() {
+  constructor(config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
     console.log('[UIA-SERVICE] WindowsNativeSelectionService created');
     this.loadNativeAddon();
   }
@@ -48,45 +1274,157 @@ export class WindowsNativeSelectionService implements NativeSelectionService {
       const NativeSelectionMonitor = require('../../native-addon');
       this.nativeAddon = new NativeSelectionMonitor();
       console.log('[UIA-SERVICE] UIAutomation native addon loaded successfully');
+
+      // Registered once here so restarts and start retries don't duplicate them
+      this.nativeAddon.onSelection((payload: NativeSelectionPayload) => {
+        const selection = payload || { text: '', x: 0, y: 0, bounds: null, app: '' };
+        if (selection.unreadable) {
+          this.recognizeUnreadable(selection).catch((error) => {
+            console.error('[UIA-SERVICE] OCR fallback failed:', error);
+            this.emitError(new SelectionError(error instanceof Error ? error.message : String(error), 'PLATFORM_ERROR'));
+          });
+          return;
+        }
+        this.handleSelection(selection, selection.requested ? 'hotkey' : 'native');
+      });
+
+      this.nativeAddon.onStateChange((state: SelectionState) => {
+        if ((state === 'ready' || state === 'failed') && this.readyWaiter) {
+          this.readyWaiter(state);
+        }
+        if (state === 'backend-exited' && this.isRunning) {
+          this.restartAfterExit('Selection monitor thread exited unexpectedly');
+        }
+        if (state === 'suppressed-secure-desktop' || state === 'selection-cleared') {
+          // Whatever was pending came from the context we are now suppressing,
+          // or is no longer selected
+          this.resetDispatcher();
+        }
+        this.emitState(state);
+      });
+
+      if (typeof this.nativeAddon.onError === 'function') {
+        this.nativeAddon.onError((message: string) => this.emitError(nativeFailure(message)));
+      }
+
+      if (typeof this.nativeAddon.onCaret === 'function') {
+        this.nativeAddon.onCaret((caret: NativeAddon.CaretPayload) => {
+          const bounds = physicalRectToDip(caret);
+          this.emitCaret({
+            x: bounds.x,
+            y: bounds.y,
+            bounds,
+            app: caret.app || undefined,
+            timestamp: this.config.clock.now(),
+          });
+        });
+      }
     } catch (error) {
       console.error('[UIA-SERVICE] Failed to load UIAutomation native addon:', error);
       console.error('[UIA-SERVICE] UIAutomation is required for text selection monitoring');
       this.nativeAddon = null;
+      this.addonLoadError = error instanceof Error ? error.message : String(error);
     }
   }
 
-  async start(): Promise<void> {
+  async start(): Promise<StartReport> {
     if (this.isRunning) {
       console.log('[UIA-SERVICE] Already running');
-      return;
+      return this.startReport ?? createStartReport(this.getCaptureMethod(), ['selection-events', 'selection-position'], [], []);
     }
 
     console.log('[UIA-SERVICE] Starting UIAutomation selection monitoring');
 
     if (!this.nativeAddon) {
-      throw new Error('UIAutomation native addon not available. Please ensure the native addon is built correctly.');
+      this.lastError = this.addonLoadError || 'Native addon not loaded';
+      this.startReport = createStartReport('not-available', [], [
+        { backend: 'ui-automation', reason: this.lastError }
+      ], ['Build the native addon with `npm run build-native`']);
+      throw new SelectionError(
+        'UIAutomation native addon not available. Please ensure the native addon is built correctly.',
+        'BACKEND_UNAVAILABLE'
+      );
+    }
+
+    const retry: RetryOptions = { retries: this.config.startRetries, delayMs: this.config.startRetryDelayMs };
+
+    // At login the shell and displays may not exist yet; UIA started that early
+    // sees no windows. Proceed anyway once the retries are used up.
+    if (!(await waitUntil(() => this.isSessionReady(), retry, this.config.clock))) {
+      console.warn('[UIA-SERVICE] Desktop session still not ready, starting anyway');
+    }
+
+    try {
+      await withRetries(() => this.startUIAutomationMonitoring(), retry, '[UIA-SERVICE]', this.config.clock);
+    } catch (error) {
+      this.lastError = error instanceof Error ? error.message : String(error);
+      this.startReport = createStartReport('not-available', [], [
+        { backend: 'ui-automation', reason: this.lastError }
+      ], ['Run Phevere as Administrator (`npm run start-admin`)']);
+      throw error;
     }
 
-    await this.startUIAutomationMonitoring();
     this.isRunning = true;
+    this.lastError = null;
+    // The addon switches to WinEvents on its own when no UIA handler can be registered
+    const backend = this.config.backend === 'polling' ? 'ui-automation-polling'
+      : this.getCaptureMethod() === 'winevent' ? 'winevent' : 'ui-automation';
+    this.startReport = createStartReport(backend, ['selection-events', 'selection-position'], [], []);
+    return this.startReport;
   }
 
   private async startUIAutomationMonitoring(): Promise<void> {
     try {
-      
-      // Set up callback for debounced selection events
-      this.nativeAddon.onSelection((payload: { text: string; x: number; y: number }) => {
-        const { text, x, y } = payload || { text: '', x: 0, y: 0 };
-        this.handleSelection(text, 'native', x, y);
+      // Debouncing happens in the shared dispatcher; the addon only coalesces
+      // events that arrive within the same polling tick.
+      this.nativeAddon.configure({
+        debounceMs: 0,
+        triggers: this.config.triggers,
+        captureMode: this.config.captureMode,
+        mouseHook: this.config.mouseHook,
+        contextChars: this.config.contextChars,
+        trackCaret: this.config.trackCaret,
+        exposeElementHandles: this.config.exposeElementHandles,
+        captureRichText: this.config.captureRichText,
+        reportUnreadableDrags: this.config.ocr.enabled,
+        imeComposition: this.config.imeComposition,
+        callTimeoutMs: this.config.callTimeoutMs,
+        stopTimeoutMs: this.config.stopTimeoutMs,
+        maxReadLength: this.config.maxReadLength,
+        // 0 keeps UIA events; the addon still polls if no event handler can be registered
+        pollIntervalMs: this.config.backend === 'polling' ? this.config.pollIntervalMs : 0,
+        eventSource: this.config.backend === 'winevent' ? 'winevent' : 'uia',
+        includeApps: this.config.includeApps,
+        excludeApps: this.config.excludeApps,
       });
 
-      // Start UIAutomation monitoring
+      // Start UIAutomation monitoring; initialization finishes on the monitor thread
       const success = this.nativeAddon.start();
 
-      if (success) {
-        console.log('[UIA-SERVICE] ✅ UIAutomation monitoring started successfully');
-      } else {
-        throw new Error('Failed to start UIAutomation monitoring');
+      if (!success) {
+        throw new SelectionError('Failed to start UIAutomation monitoring', 'PLATFORM_ERROR');
+      }
+
+      // State callbacks are queued to this thread, so none can be missed before
+      // the waiter is installed
+      try {
+        await this.waitForReady();
+      } catch (error) {
+        // Join the threads the addon started (off the main thread) before reporting the failure
+        try {
+          if (await this.nativeAddon.stopAsync() === false) {
+            console.error('[UIA-SERVICE] UIAutomation did not stop after a failed start; replacing the native monitor');
+            this.loadNativeAddon();
+          }
+        } catch (stopError) {
+          console.error('[UIA-SERVICE] Error stopping UIAutomation after a failed start:', stopError);
+        }
+        throw error;
+      }
+      console.log('[UIA-SERVICE] ✅ UIAutomation monitoring started successfully');
+
+      if (this.config.captureMode === 'hotkey') {
+        this.registerCaptureHotkey();
       }
     } catch (error) {
       console.error('[UIA-SERVICE] ❌ Error starting UIAutomation:', error);
@@ -94,75 +1432,194 @@ export class WindowsNativeSelectionService implements NativeSelectionService {
     }
   }
 
+  /**
+   * Resolves on the addon's 'ready' state; rejects on 'failed' (with the native
+   * reason) or if neither arrives within readyTimeoutMs
+   */
+  private waitForReady(): Promise<void> {
+    return new Promise((resolve, reject) => {
+      const timer = this.config.clock.setTimeout(() => {
+        this.readyWaiter = null;
+        reject(new SelectionError(`UIAutomation did not become ready within ${this.config.readyTimeoutMs}ms`, 'TIMEOUT'));
+      }, this.config.readyTimeoutMs);
+
+      this.readyWaiter = (state) => {
+        this.config.clock.clearTimeout(timer);
+        this.readyWaiter = null;
+        if (state === 'ready') {
+          resolve();
+        } else {
+          reject(nativeFailure(this.nativeAddon.getInitError() || 'UIAutomation failed to initialize'));
+        }
+      };
+    });
+  }
+
+  private isSessionReady(): boolean {
+    if (screen.getAllDisplays().length === 0) {
+      return false;
+    }
+    return this.nativeAddon.isSessionReady();
+  }
+
   async stop(): Promise<void> {
+    this.cancelRestart();
     if (!this.isRunning) {
       return;
     }
 
     console.log('[UIA-SERVICE] Stopping UIAutomation selection monitoring');
     this.isRunning = false;
+    this.resetDispatcher();
+
+    if (this.config.captureMode === 'hotkey') {
+      globalShortcut.unregister(this.config.captureHotkey);
+    }
+
+    // Stop native addon if running
+    if (this.nativeAddon && typeof this.nativeAddon.stop === 'function') {
+      let stopped: boolean | undefined;
+      try {
+        // Joining the native threads can take up to stopTimeoutMs; do it off the main thread
+        stopped = await this.nativeAddon.stopAsync();
+        console.log('[UIA-SERVICE] UIAutomation stopped');
+      } catch (error) {
+        console.error('[UIA-SERVICE] Error stopping UIAutomation:', error);
+        this.lastError = error instanceof Error ? error.message : String(error);
+      }
+      if (stopped === false) {
+        // The hung thread keeps the old monitor; a fresh one lets start() work again
+        const message = `UIAutomation did not stop within ${this.config.stopTimeoutMs}ms`;
+        console.error(`[UIA-SERVICE] ${message}; replacing the native monitor`);
+        this.lastError = message;
+        this.loadNativeAddon();
+        throw new SelectionError(message, 'TIMEOUT');
+      }
+    }
+  }
+
+  isSupported(): boolean {
+    return process.platform === 'win32' && this.nativeAddon !== null;
+  }
+
+  getStatus(): SelectionStatus {
+    return this.describeStatus('windows', this.getCaptureMethod());
+  }
+
+  private getCaptureMethod(): string {
+    if (!this.nativeAddon) {
+      return 'not-available';
+    }
+    return this.isRunning ? this.nativeAddon.getCaptureMethod() : 'ui-automation';
+  }
+
+  getStartReport(): StartReport | null {
+    return this.startReport;
+  }
+
+  /**
+   * Last settled selection as reported by the native addon, including its bounds.
+   * Null means nothing is selected; a failed read throws a SelectionError.
+   */
+  getSelectionDetails(): { text: string; x: number; y: number; bounds?: SelectionBounds } | null {
+    if (!this.nativeAddon || typeof this.nativeAddon.getSelectionDetails !== 'function') {
+      throw new SelectionError(this.addonLoadError || 'Native addon not loaded', 'BACKEND_UNAVAILABLE');
+    }
+    if (!this.isRunning) {
+      throw new SelectionError('UIAutomation monitoring is not running', 'NOT_RUNNING');
+    }
+    let details: NativeSelectionPayload | null;
+    try {
+      details = this.nativeAddon.getSelectionDetails();
+    } catch (error) {
+      throw nativeFailure(error instanceof Error ? error.message : String(error));
+    }
+    if (!details) {
+      return null;
+    }
+    const text = redactSelection(details.text, this.config.redaction);
+    if (text === null) {
+      return null;
+    }
+    const anchor = physicalToDip({ x: details.x, y: details.y });
+    return { text, x: anchor.x, y: anchor.y, bounds: details.bounds ? physicalRectToDip(details.bounds) : undefined };
+  }
 
-    // Stop native addon if running
-    if (this.nativeAddon && typeof this.nativeAddon.stop === 'function') {
-      try {
-        this.nativeAddon.stop();
-        console.log('[UIA-SERVICE] UIAutomation stopped');
-      } catch (error) {
-        console.error('[UIA-SERVICE] Error stopping UIAutomation:', error);
+  /**
+   * In hotkey mode the selection is read on the monitor thread and delivered
+   * asynchronously through onSelection, so this returns null.
+   */
+  captureNow(): SelectionEvent | null {
+    if (this.config.captureMode === 'hotkey') {
+      if (this.isRunning) {
+        this.nativeAddon.requestCapture();
       }
+      return null;
     }
+    return super.captureNow();
   }
 
-  onSelection(callback: (event: SelectionEvent) => void): void {
-    this.selectionCallbacks.push(callback);
+  private registerCaptureHotkey(): void {
+    const registered = globalShortcut.register(this.config.captureHotkey, () => {
+      this.nativeAddon.requestCapture();
+    });
+    if (!registered) {
+      this.lastError = `Capture hotkey ${this.config.captureHotkey} is already in use`;
+      console.error(`[UIA-SERVICE] ${this.lastError}`);
+    }
   }
 
-  isSupported(): boolean {
-    return process.platform === 'win32' && this.nativeAddon !== null;
+  protected onPausedChanged(paused: boolean): void {
+    if (!this.nativeAddon) {
+      return;
+    }
+    if (paused) {
+      this.nativeAddon.pause();
+    } else {
+      this.nativeAddon.resume();
+    }
   }
 
-  getStatus(): { isRunning: boolean; platform: string; method: string } {
-    return {
-      isRunning: this.isRunning,
-      platform: 'windows',
-      method: this.nativeAddon ? 'ui-automation' : 'not-available'
-    };
+  protected async readTextAround(point: Point, chars: number): Promise<TextAround | null> {
+    if (!this.isRunning || !this.nativeAddon) {
+      return null;
+    }
+    try {
+      return await this.nativeAddon.getTextAtPointAsync(point.x, point.y, chars, this.config.callTimeoutMs || undefined);
+    } catch (error) {
+      console.error('[UIA-SERVICE] Error reading text at point:', error);
+      return null;
+    }
+  }
+
+  protected readCurrentSelection(): SelectionEvent | null {
+    const details = this.getSelectionDetails();
+    if (!details) {
+      return null;
+    }
+    return { ...details, timestamp: this.config.clock.now(), source: 'native' };
   }
 
   /**
    * Handle selection events from native addon
    */
-  private handleSelection(text: string, source: 'native', selX?: number, selY?: number): void {
+  private handleSelection(payload: NativeSelectionPayload, source: 'native' | 'hotkey'): void {
+    const { x: selX, y: selY } = payload;
+    this.selectionMetrics.recordReceived();
     try {
+      const text = payload.text;
+
       // Validate the selection
       if (!this.isValidTextSelection(text)) {
+        this.selectionMetrics.recordDropped('filtered');
         return;
       }
       
-      // Check for duplicate selections - allow duplicates after some time
-      const now = Date.now();
-      const timeSinceLastSelection = now - (this.lastSelectionTime || 0);
-
-      // More aggressive rate limiting to prevent bursts
-      const MIN_RETRIGGER_INTERVAL_MS = 300; // increased from 200ms to prevent bursts
-      if (timeSinceLastSelection < MIN_RETRIGGER_INTERVAL_MS) {
-        return;
-      }
-
-      // Additional check: if we just processed the same text very recently, be more restrictive
-      if (this.lastSelection === text && timeSinceLastSelection < 800) {
-        return;
-      }
-      
-      // Update last selection and time
-      this.lastSelection = text;
-      this.lastSelectionTime = now;
-      
       // Use selection bounds if provided, otherwise fall back to cursor position
       let anchorPosition = screen.getCursorScreenPoint();
       if (selX != null && selY != null) {
         // Use the provided selection coordinates as the anchor point
-        anchorPosition = { x: selX, y: selY };
+        anchorPosition = physicalToDip({ x: selX, y: selY });
       }
       
       // Create selection event
@@ -170,21 +1627,41 @@ export class WindowsNativeSelectionService implements NativeSelectionService {
         text,
         x: anchorPosition.x,
         y: anchorPosition.y,
-        timestamp: Date.now(),
-        source
+        timestamp: this.config.clock.now(),
+        source,
+        bounds: payload.bounds ? physicalRectToDip(payload.bounds) : undefined,
+        app: payload.app || undefined,
+        granularity: payload.granularity,
+        ranges: payload.ranges && payload.ranges.length > 1 ? payload.ranges.map((range) => ({ text: range })) : undefined,
+        context: payload.contextBefore || payload.contextAfter
+          ? { before: payload.contextBefore || '', after: payload.contextAfter || '' }
+          : undefined,
+        truncated: payload.truncated || undefined,
+        composing: payload.composing || undefined,
+        direction: payload.direction,
+        files: payload.files && payload.files.length > 0 ? payload.files : undefined,
+        element: payload.element ? { platform: 'windows', ...payload.element } : undefined,
+        rich: payload.runs && payload.runs.length > 0 ? { runs: payload.runs } : undefined,
+        extensions: payload.unreadable ? { 'ocr.engine': this.ocrEngine.name } : undefined
       };
 
-      // Notify all callbacks
-      this.selectionCallbacks.forEach((callback) => {
-        try {
-          callback(selectionEvent);
-        } catch (error) {
-          console.error(`[UIA-SERVICE] Error in callback:`, error);
-        }
-      });
-      
+      this.emitSelection(selectionEvent);
     } catch (error) {
       console.error('[UIA-SERVICE] Error handling selection:', error);
+      this.lastError = error instanceof Error ? error.message : String(error);
+    }
+  }
+
+  /**
+   * OCR fallback: the app exposed no text for a drag, so read the dragged area
+   */
+  private async recognizeUnreadable(payload: NativeSelectionPayload): Promise<void> {
+    if (!this.config.ocr.enabled || !payload.bounds || this.isPaused) {
+      return;
+    }
+    const text = await recognizeRegion(this.ocrEngine, physicalRectToDip(payload.bounds), this.config.ocr);
+    if (text && this.isRunning) {
+      this.handleSelection({ ...payload, text }, 'native');
     }
   }
 
@@ -229,127 +1706,616 @@ export class WindowsNativeSelectionService implements NativeSelectionService {
 /**
  * macOS Native Selection Service - Placeholder for future implementation
  */
-export class MacOSNativeSelectionService implements NativeSelectionService {
-  private isRunning = false;
-  private selectionCallbacks: ((event: SelectionEvent) => void)[] = [];
-
-  constructor() {
+export class MacOSNativeSelectionService extends BaseNativeSelectionService {
+  constructor(config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
     console.log('[NATIVE-SERVICE] MacOSNativeSelectionService created');
   }
 
-  async start(): Promise<void> {
+  async start(): Promise<StartReport> {
     console.log('[NATIVE-SERVICE] macOS native selection not implemented yet');
     this.isRunning = true;
+    return this.getStartReport();
   }
 
   async stop(): Promise<void> {
     this.isRunning = false;
   }
 
-  onSelection(callback: (event: SelectionEvent) => void): void {
-    this.selectionCallbacks.push(callback);
-  }
-
   isSupported(): boolean {
     return process.platform === 'darwin';
   }
 
-  getStatus(): { isRunning: boolean; platform: string; method: string } {
-    return {
-      isRunning: this.isRunning,
-      platform: 'macos',
-      method: 'not-implemented'
-    };
+  getStatus(): SelectionStatus {
+    return this.describeStatus('macos', 'not-implemented');
+  }
+
+  getStartReport(): StartReport {
+    return createStartReport('not-implemented', [], [
+      { backend: 'accessibility', reason: 'macOS native selection not implemented yet' }
+    ], []);
   }
 }
 
 /**
- * Linux Native Selection Service - Placeholder for future implementation
+ * Linux Native Selection Service - Placeholder for future implementation.
+ * Also used on the BSDs, which share the X11/Wayland desktop stack.
  */
-export class LinuxNativeSelectionService implements NativeSelectionService {
-  private isRunning = false;
-  private selectionCallbacks: ((event: SelectionEvent) => void)[] = [];
-
-  constructor() {
+export class LinuxNativeSelectionService extends BaseNativeSelectionService {
+  constructor(config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
     console.log('[NATIVE-SERVICE] LinuxNativeSelectionService created');
   }
 
-  async start(): Promise<void> {
+  async start(): Promise<StartReport> {
     console.log('[NATIVE-SERVICE] Linux native selection not implemented yet');
     this.isRunning = true;
+    return this.getStartReport();
   }
 
   async stop(): Promise<void> {
     this.isRunning = false;
   }
 
-  onSelection(callback: (event: SelectionEvent) => void): void {
-    this.selectionCallbacks.push(callback);
+  isSupported(): boolean {
+    return isUnixDesktop(process.platform);
+  }
+
+  getStatus(): SelectionStatus {
+    return this.describeStatus(process.platform, 'not-implemented');
+  }
+
+  getStartReport(): StartReport {
+    return createStartReport('not-implemented', [], [
+      { backend: 'accessibility', reason: 'Linux native selection not implemented yet' }
+    ], []);
+  }
+}
+
+/**
+ * Returned for backends that do not exist on this platform: the API stays
+ * usable everywhere, but start() rejects with BACKEND_UNAVAILABLE.
+ */
+export class UnsupportedSelectionService extends BaseNativeSelectionService {
+  constructor(private backend: string, config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
+  }
+
+  async start(): Promise<StartReport> {
+    this.lastError = this.reason();
+    throw new SelectionError(this.lastError, 'BACKEND_UNAVAILABLE');
+  }
+
+  async stop(): Promise<void> {
+    // Never started
+  }
+
+  isSupported(): boolean {
+    return false;
+  }
+
+  getStatus(): SelectionStatus {
+    return this.describeStatus(process.platform, 'not-available');
+  }
+
+  getStartReport(): StartReport {
+    return createStartReport('not-available', [], [{ backend: this.backend, reason: this.reason() }], []);
+  }
+
+  private reason(): string {
+    return `The ${this.backend} backend is not available on ${process.platform}`;
+  }
+}
+
+/**
+ * Clipboard Selection Service - universal fallback
+ *
+ * Polls the clipboard and reports newly copied text as a selection. Needs no
+ * permissions but only sees text the user copies, and has no selection position
+ * (the cursor position is used instead).
+ */
+export class ClipboardSelectionService extends BaseNativeSelectionService {
+  private static readonly POLL_INTERVAL_MS = 500;
+  private pollTimer: NodeJS.Timeout | null = null;
+  private lastText = '';
+
+  constructor(config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
+    console.log('[NATIVE-SERVICE] ClipboardSelectionService created');
+  }
+
+  async start(): Promise<StartReport> {
+    if (!this.isRunning) {
+      // Whatever is already on the clipboard was not selected during this session
+      this.lastText = clipboard.readText();
+      this.pollTimer = setInterval(() => this.poll(), ClipboardSelectionService.POLL_INTERVAL_MS);
+      this.isRunning = true;
+      console.log('[NATIVE-SERVICE] Clipboard selection mode started');
+    }
+    return this.getStartReport();
+  }
+
+  async stop(): Promise<void> {
+    if (this.pollTimer) {
+      clearInterval(this.pollTimer);
+      this.pollTimer = null;
+    }
+    this.isRunning = false;
+    this.resetDispatcher();
+  }
+
+  isSupported(): boolean {
+    return true;
+  }
+
+  getStatus(): SelectionStatus {
+    return this.describeStatus(process.platform, 'clipboard');
+  }
+
+  getStartReport(): StartReport {
+    return createStartReport('clipboard', ['selection-events'], [], []);
+  }
+
+  protected readCurrentSelection(): SelectionEvent | null {
+    return this.toEvent(clipboard.readText());
+  }
+
+  // The text/html flavour that browsers and office apps put next to the text
+  private readRich(): RichText | undefined {
+    if (!this.config.captureRichText) {
+      return undefined;
+    }
+    const html = clipboard.readHTML();
+    return html ? richTextFromHtml(html) : undefined;
+  }
+
+  private poll(): void {
+    try {
+      const text = clipboard.readText();
+      if (text === this.lastText) {
+        return;
+      }
+      this.lastText = text;
+      this.selectionMetrics.recordReceived();
+      const event = this.toEvent(text);
+      if (event) {
+        this.emitSelection(event);
+      }
+    } catch (error) {
+      console.error('[NATIVE-SERVICE] Error reading clipboard:', error);
+      this.emitError(new SelectionError(error instanceof Error ? error.message : String(error), 'PLATFORM_ERROR'));
+    }
+  }
+
+  private toEvent(text: string): SelectionEvent | null {
+    if (text.trim().length === 0) {
+      this.selectionMetrics.recordDropped('filtered');
+      return null;
+    }
+    const cursor = screen.getCursorScreenPoint();
+    return { text, x: cursor.x, y: cursor.y, timestamp: this.config.clock.now(), source: 'clipboard', rich: this.readRich() };
+  }
+}
+
+// Runs in the focused page: the DOM selection, or the selected part of a focused
+// text field (which window.getSelection() does not cover), with its client rect
+const READ_PAGE_SELECTION = `(() => {
+  const field = document.activeElement;
+  if (field && (field.tagName === 'TEXTAREA' || field.tagName === 'INPUT') &&
+      typeof field.selectionStart === 'number' && field.type !== 'password') {
+    const rect = field.getBoundingClientRect();
+    return { text: field.value.slice(field.selectionStart, field.selectionEnd),
+      rect: { x: rect.left, y: rect.top, width: rect.width, height: rect.height } };
+  }
+  const selection = window.getSelection();
+  if (!selection || selection.rangeCount === 0 || selection.isCollapsed) return { text: '', rect: null };
+  const rect = selection.getRangeAt(0).getBoundingClientRect();
+  return { text: selection.toString(),
+    rect: { x: rect.left, y: rect.top, width: rect.width, height: rect.height } };
+})()`;
+
+/**
+ * Own-App Selection Service - selections in this app's own windows only
+ *
+ * Polls the focused BrowserWindow's page for its selection instead of watching
+ * the desktop, so it needs no hooks, event taps or accessibility permission and
+ * behaves the same on every platform. Selections in other apps are never seen.
+ */
+export class OwnAppSelectionService extends BaseNativeSelectionService {
+  private pollTimer: NodeJS.Timeout | null = null;
+  private reading = false;
+  private lastText = '';
+  private lastEvent: SelectionEvent | null = null;
+
+  constructor(config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
+    console.log('[NATIVE-SERVICE] OwnAppSelectionService created');
+  }
+
+  async start(): Promise<StartReport> {
+    if (!this.isRunning) {
+      this.lastText = '';
+      this.lastEvent = null;
+      this.pollTimer = setInterval(() => this.poll(), Math.max(50, this.config.pollIntervalMs));
+      this.isRunning = true;
+      console.log('[NATIVE-SERVICE] Own-app selection mode started');
+    }
+    return this.getStartReport();
+  }
+
+  async stop(): Promise<void> {
+    if (this.pollTimer) {
+      clearInterval(this.pollTimer);
+      this.pollTimer = null;
+    }
+    this.isRunning = false;
+    this.resetDispatcher();
+  }
+
+  isSupported(): boolean {
+    return true;
+  }
+
+  getStatus(): SelectionStatus {
+    return this.describeStatus(process.platform, 'own-app');
+  }
+
+  getStartReport(): StartReport {
+    return createStartReport('own-app', ['selection-events', 'selection-position'], [], []);
+  }
+
+  protected readCurrentSelection(): SelectionEvent | null {
+    return this.lastEvent;
+  }
+
+  private async poll(): Promise<void> {
+    const window = BrowserWindow.getFocusedWindow();
+    if (this.reading || this.isPaused || !window || window.isDestroyed()) {
+      return;
+    }
+    // A slow page must not queue up reads behind it
+    this.reading = true;
+    try {
+      const result: { text: string; rect: SelectionBounds | null } =
+        await window.webContents.executeJavaScript(READ_PAGE_SELECTION);
+      if (!this.isRunning || window.isDestroyed() || result.text === this.lastText) {
+        return;
+      }
+      this.lastText = result.text;
+      if (!result.text) {
+        this.lastEvent = null;
+        this.resetDispatcher();
+        this.emitState('selection-cleared');
+        return;
+      }
+      this.selectionMetrics.recordReceived();
+      const event = this.toEvent(result.text, result.rect, window.getContentBounds());
+      this.lastEvent = event;
+      if (event) {
+        this.emitSelection(event);
+      }
+    } catch (error) {
+      // Navigation in progress or a page without script access; try again next tick
+      console.warn('[NATIVE-SERVICE] Could not read own-app selection:', error instanceof Error ? error.message : error);
+    } finally {
+      this.reading = false;
+    }
+  }
+
+  private toEvent(text: string, rect: SelectionBounds | null, content: SelectionBounds): SelectionEvent | null {
+    if (text.trim().length === 0) {
+      this.selectionMetrics.recordDropped('filtered');
+      return null;
+    }
+    // Page coordinates are DIP relative to the content area
+    const bounds = rect ? { x: content.x + rect.x, y: content.y + rect.y, width: rect.width, height: rect.height } : undefined;
+    const anchor = bounds ?? screen.getCursorScreenPoint();
+    return { text, x: anchor.x, y: anchor.y, bounds, timestamp: this.config.clock.now(), source: 'own-app' };
+  }
+}
+
+/**
+ * Runs the first backend in a chain that starts with selection events available.
+ *
+ * Candidates are built with debounce, dedupe, history and processors disabled;
+ * this service applies them once for whichever backend ends up active.
+ * Sanitizing, redaction and the length cap run in the candidate that captured,
+ * so its captureNow() result matches what is delivered.
+ */
+export class FallbackSelectionService extends BaseNativeSelectionService {
+  private active: NativeSelectionService | null = null;
+  private startReport: StartReport | null = null;
+
+  constructor(private candidates: NativeSelectionService[], config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
+    candidates.forEach((candidate) => {
+      candidate.onSelection((event) => {
+        // Already sanitized, redacted and length-checked by the candidate
+        if (candidate === this.active) {
+          this.dispatchSelection(event);
+        }
+      });
+      candidate.onStateChange((event) => {
+        if (candidate === this.active) {
+          this.emitState(event.state);
+        }
+      });
+      candidate.onError((error) => {
+        if (candidate === this.active) {
+          this.emitError(error);
+        }
+      });
+      candidate.onCaret((event) => {
+        if (candidate === this.active) {
+          this.emitCaret(event);
+        }
+      });
+    });
+  }
+
+  async start(): Promise<StartReport> {
+    if (this.isRunning && this.startReport) {
+      return this.startReport;
+    }
+
+    const failures: BackendFailure[] = [];
+    const remediation: string[] = [];
+
+    for (const candidate of this.candidates) {
+      try {
+        const report = await candidate.start();
+        if (report.capabilities.includes('selection-events')) {
+          this.active = candidate;
+          this.isRunning = true;
+          this.lastError = null;
+          if (this.isPaused) {
+            candidate.pause();
+          }
+          this.startReport = createStartReport(
+            report.backend,
+            report.capabilities,
+            [...failures, ...report.failures],
+            [...remediation, ...report.remediation]
+          );
+          console.log(`[NATIVE-SERVICE] Selection backend active: ${report.backend}`);
+          return this.startReport;
+        }
+        // Started, but cannot deliver selections (e.g. a placeholder backend)
+        await candidate.stop();
+        failures.push(...report.failures);
+        remediation.push(...report.remediation);
+      } catch (error) {
+        const report = candidate.getStartReport();
+        if (report && report.failures.length > 0) {
+          failures.push(...report.failures);
+          remediation.push(...report.remediation);
+        } else {
+          failures.push({
+            backend: candidate.getStatus().method,
+            reason: error instanceof Error ? error.message : String(error),
+          });
+        }
+      }
+      console.log('[NATIVE-SERVICE] Selection backend unavailable, trying next');
+    }
+
+    this.startReport = createStartReport('not-available', [], failures, remediation);
+    this.lastError = failures.length > 0 ? failures[failures.length - 1].reason : 'No selection backend configured';
+    throw new SelectionError(
+      `No selection backend could be started (${failures.map((f) => `${f.backend}: ${f.reason}`).join('; ')})`,
+      'BACKEND_UNAVAILABLE'
+    );
+  }
+
+  async stop(): Promise<void> {
+    const active = this.active;
+    this.active = null;
+    this.isRunning = false;
+    this.resetDispatcher();
+    if (active) {
+      await active.stop();
+    }
   }
 
   isSupported(): boolean {
-    return process.platform === 'linux';
+    return this.candidates.some((candidate) => candidate.isSupported());
   }
 
-  getStatus(): { isRunning: boolean; platform: string; method: string } {
+  getStatus(): SelectionStatus {
+    const platform = this.candidates.length > 0 ? this.candidates[0].getStatus().platform : process.platform;
+    const active = this.active ? this.active.getStatus() : null;
+    // The active candidate runs its own watchdog
     return {
-      isRunning: this.isRunning,
-      platform: 'linux',
-      method: 'not-implemented'
+      ...this.describeStatus(platform, active ? active.method : 'not-available'),
+      restarts: active ? active.restarts : 0,
     };
   }
+
+  getStartReport(): StartReport | null {
+    return this.startReport;
+  }
+
+  /**
+   * Received/filtered counts come from the active backend; debounce, dedupe and
+   * delivery happen in this service
+   */
+  metrics(): MetricsSnapshot {
+    const own = super.metrics();
+    if (!this.active) {
+      return own;
+    }
+    const backend = this.active.metrics();
+    return { ...own, received: backend.received, dropped: mergeDrops(backend.dropped, own.dropped) };
+  }
+
+  captureNow(): SelectionEvent | null {
+    // The active backend delivers the capture back through dispatchSelection()
+    return this.active ? this.active.captureNow() : null;
+  }
+
+  wordAtPoint(x: number, y: number, locale?: string): Promise<WordAtPoint | null> {
+    return this.active ? this.active.wordAtPoint(x, y, locale) : Promise.resolve(null);
+  }
+
+  setWordDictionary(dictionary: WordDictionary | null): void {
+    super.setWordDictionary(dictionary);
+    this.candidates.forEach((candidate) => candidate.setWordDictionary(dictionary));
+  }
+
+  protected onPausedChanged(paused: boolean): void {
+    if (!this.active) {
+      return;
+    }
+    if (paused) {
+      this.active.pause();
+    } else {
+      this.active.resume();
+    }
+  }
 }
 
 /**
- * Factory function to create the appropriate native selection service
+ * Unix-like platforms with an X11/Wayland desktop (Linux and the BSDs)
  */
-export function createNativeSelectionService(): NativeSelectionService {
-  console.log(`[NATIVE-SERVICE] Creating native selection service for platform: ${process.platform}`);
-  
+export function isUnixDesktop(platform: NodeJS.Platform): boolean {
+  return platform === 'linux' || platform === 'freebsd' || platform === 'openbsd' || platform === 'netbsd';
+}
+
+function createPlatformService(config: ListenerConfig): NativeSelectionService {
+  if (isUnixDesktop(process.platform)) {
+    return new LinuxNativeSelectionService(config);
+  }
   switch (process.platform) {
     case 'win32':
-      return new WindowsNativeSelectionService();
+      return new WindowsNativeSelectionService(config);
     case 'darwin':
-      return new MacOSNativeSelectionService();
-    case 'linux':
-      return new LinuxNativeSelectionService();
+      return new MacOSNativeSelectionService(config);
     default:
-      console.log(`[NATIVE-SERVICE] Platform ${process.platform} not supported, using mock service`);
-      return new MockNativeSelectionService();
+      console.log(`[NATIVE-SERVICE] Platform ${process.platform} has no native backend`);
+      return new UnsupportedSelectionService('native', config);
   }
 }
 
 /**
- * Mock service for unsupported platforms or testing
+ * Factory function to create the appropriate native selection service
  */
-class MockNativeSelectionService implements NativeSelectionService {
-  private isRunning = false;
-  private selectionCallbacks: ((event: SelectionEvent) => void)[] = [];
+export function createNativeSelectionService(options: Partial<ListenerConfig> = {}): NativeSelectionService {
+  const config: ListenerConfig = { ...DEFAULT_LISTENER_CONFIG, ...options };
+  const service = createConfiguredService(config);
+  if (config.pauseOnSessionLock) {
+    watchSession(service);
+  }
+  return service;
+}
+
+function createConfiguredService(config: ListenerConfig): BaseNativeSelectionService {
+  console.log(`[NATIVE-SERVICE] Creating ${config.backend} selection service for platform: ${process.platform}`);
+
+  switch (config.backend) {
+    case 'ui-automation':
+    case 'polling':
+    case 'winevent':
+      if (process.platform !== 'win32') {
+        console.log(`[NATIVE-SERVICE] ${config.backend} backend requested but only available on Windows`);
+        return new UnsupportedSelectionService(config.backend, config);
+      }
+      return new WindowsNativeSelectionService(config);
+    case 'clipboard':
+      return new ClipboardSelectionService(config);
+    case 'own-app':
+      return new OwnAppSelectionService(config);
+  }
+
+  // 'auto': native backend first, clipboard mode if it cannot start. The
+  // candidates capture and prepare (sanitize, redact, cap); processors, history
+  // and thumbnails run once, in the outer service
+  const candidateConfig: ListenerConfig = {
+    ...config,
+    debounceMs: 0,
+    dedupeWindowMs: 0,
+    historyCapacity: 0,
+    historyStore: { ...config.historyStore, enabled: false },
+    thumbnail: { ...config.thumbnail, enabled: false },
+    detectLanguage: false,
+    detectEntities: false,
+    detectDirection: false,
+    selectionDiffs: false,
+  };
+  const platformService = createPlatformService(candidateConfig);
+  return new FallbackSelectionService([platformService, new ClipboardSelectionService(candidateConfig)], config);
+}
 
-  async start(): Promise<void> {
-    console.log('[NATIVE-SERVICE] Mock service started (platform not supported)');
+/**
+ * Mock service for testing.
+ *
+ * Tests can inject synthetic selections (which go through the same debounce,
+ * dedupe and history pipeline as real ones), make start() fail, and inspect
+ * how often start()/stop() were called.
+ */
+export class MockNativeSelectionService extends BaseNativeSelectionService {
+  startCalls = 0;
+  stopCalls = 0;
+  private nextStartError: Error | null = null;
+
+  constructor(config: ListenerConfig = DEFAULT_LISTENER_CONFIG) {
+    super(config);
+  }
+
+  async start(): Promise<StartReport> {
+    this.startCalls++;
+    if (this.nextStartError) {
+      const error = this.nextStartError;
+      this.nextStartError = null;
+      this.lastError = error.message;
+      throw error;
+    }
+    console.log('[NATIVE-SERVICE] Mock service started');
     this.isRunning = true;
+    return this.getStartReport();
   }
 
   async stop(): Promise<void> {
+    this.stopCalls++;
     console.log('[NATIVE-SERVICE] Mock service stopped');
     this.isRunning = false;
+    this.resetDispatcher();
   }
 
   onSelection(callback: (event: SelectionEvent) => void): void {
-    this.selectionCallbacks.push(callback);
+    super.onSelection(callback);
     console.log('[NATIVE-SERVICE] Mock service callback registered');
   }
 
+  /**
+   * Feed a synthetic selection into the pipeline as if a backend had observed it
+   */
+  injectSelection(selection: Partial<SelectionEvent> & { text: string }): void {
+    this.selectionMetrics.recordReceived();
+    this.emitSelection({
+      x: 0,
+      y: 0,
+      timestamp: this.config.clock.now(),
+      source: 'native',
+      ...selection,
+    });
+  }
+
+  /**
+   * Make the next start() call reject with the given error
+   */
+  failNextStart(error: Error): void {
+    this.nextStartError = error;
+  }
+
   isSupported(): boolean {
-    return false;
+    return true;
   }
 
-  getStatus(): { isRunning: boolean; platform: string; method: string } {
-    return {
-      isRunning: this.isRunning,
-      platform: 'mock',
-      method: 'not-supported'
-    };
+  getStatus(): SelectionStatus {
+    return this.describeStatus(process.platform, 'mock');
+  }
+
+  getStartReport(): StartReport {
+    return createStartReport('mock', ['selection-events'], [], []);
   }
-}
\ No newline at end of file
+}
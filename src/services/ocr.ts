@@ -0,0 +1,107 @@
+/**
+ * OCR - recover text from apps that expose nothing through accessibility
+ *
+ * Opt-in fallback for games, remote-desktop windows and locked-down viewers:
+ * when a drag gesture yields no readable selection, the dragged region is
+ * captured (screen-capture.ts) and handed to an OcrEngine. Engines are
+ * pluggable; the default shells out to the tesseract CLI if it is installed.
+ */
+
+import { spawn } from 'child_process';
+import type { NativeImage, Rectangle } from 'electron';
+import { captureRegion } from './screen-capture';
+
+export interface OcrEngine {
+  /** Reported as extensions['ocr.engine'] on recognized selections */
+  readonly name: string;
+  /** Text in the image, or an empty string when nothing was recognized */
+  recognize(image: NativeImage, signal: AbortSignal): Promise<string>;
+}
+
+export interface OcrOptions {
+  enabled: boolean;
+  /** Margin (DIP) added around the dragged region so edge glyphs are not cut */
+  padding: number;
+  /** Recognition is abandoned after this long */
+  timeoutMs: number;
+  /** Tesseract language codes, e.g. ['eng', 'chi_sim'] */
+  languages: string[];
+}
+
+export const DEFAULT_OCR_OPTIONS: OcrOptions = {
+  enabled: false,
+  padding: 8,
+  timeoutMs: 5000,
+  languages: ['eng'],
+};
+
+/**
+ * Runs `tesseract stdin stdout`; requires tesseract on PATH
+ */
+export class TesseractEngine implements OcrEngine {
+  readonly name = 'tesseract';
+
+  constructor(private languages: string[] = DEFAULT_OCR_OPTIONS.languages, private command = 'tesseract') {}
+
+  recognize(image: NativeImage, signal: AbortSignal): Promise<string> {
+    return new Promise((resolve, reject) => {
+      const child = spawn(this.command, ['stdin', 'stdout', '-l', this.languages.join('+')], {
+        stdio: ['pipe', 'pipe', 'pipe'],
+        windowsHide: true,
+      });
+      const output: Buffer[] = [];
+      const errors: Buffer[] = [];
+      const abort = () => child.kill();
+      signal.addEventListener('abort', abort);
+
+      child.stdout.on('data', (chunk: Buffer) => output.push(chunk));
+      child.stderr.on('data', (chunk: Buffer) => errors.push(chunk));
+      child.on('error', (error) => {
+        signal.removeEventListener('abort', abort);
+        reject(error);
+      });
+      child.on('close', (code) => {
+        signal.removeEventListener('abort', abort);
+        if (signal.aborted) {
+          resolve('');
+        } else if (code === 0) {
+          resolve(Buffer.concat(output).toString('utf8').trim());
+        } else {
+          reject(new Error(`tesseract exited with ${code}: ${Buffer.concat(errors).toString('utf8').trim()}`));
+        }
+      });
+      child.stdin.on('error', () => {
+        // Reported through 'close' / 'error'
+      });
+      child.stdin.end(image.toPNG());
+    });
+  }
+}
+
+/**
+ * Capture a DIP region and run it through the engine. Returns null when the
+ * screen cannot be captured, nothing was recognized or the time ran out.
+ */
+export async function recognizeRegion(engine: OcrEngine, region: Rectangle, options: OcrOptions): Promise<string | null> {
+  const image = await captureRegion({
+    x: region.x - options.padding,
+    y: region.y - options.padding,
+    width: region.width + options.padding * 2,
+    height: region.height + options.padding * 2,
+  });
+  if (!image) {
+    return null;
+  }
+
+  const controller = new AbortController();
+  const timer = setTimeout(() => controller.abort(), options.timeoutMs);
+  try {
+    const text = await engine.recognize(image, controller.signal);
+    return text.trim() ? text.trim() : null;
+  } catch (error) {
+    console.warn(`[OCR] ${engine.name} failed:`, error instanceof Error ? error.message : error);
+    return null;
+  } finally {
+    clearTimeout(timer);
+  }
+}
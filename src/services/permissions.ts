@@ -0,0 +1,91 @@
+/**
+ * Permission Management for native selection monitoring
+ *
+ * check() reports what each platform backend is allowed to do right now;
+ * request() additionally triggers the OS prompt where one exists.
+ */
+
+import { systemPreferences } from 'electron';
+
+export type PermissionState = 'granted' | 'limited' | 'denied' | 'unknown';
+
+export interface PermissionStatus {
+  backend: string;
+  state: PermissionState;
+  /** Human-readable explanation / remediation */
+  detail: string;
+}
+
+/**
+ * Whether the current process runs elevated (Windows only)
+ */
+export function isElevated(): boolean {
+  try {
+    const { execSync } = require('child_process');
+    execSync('net session', { stdio: 'ignore' });
+    return true;
+  } catch (error) {
+    return false;
+  }
+}
+
+/**
+ * Silently check the permission state of the current platform's backend
+ */
+export function check(): PermissionStatus[] {
+  switch (process.platform) {
+    case 'win32':
+      return [checkWindows()];
+    case 'darwin':
+      return [checkMacOS(false)];
+    case 'linux':
+    case 'freebsd':
+    case 'openbsd':
+    case 'netbsd':
+      return [{
+        backend: 'accessibility',
+        state: 'unknown',
+        detail: 'Linux native selection is not implemented yet',
+      }];
+    default:
+      return [];
+  }
+}
+
+/**
+ * Check permissions and show the OS prompt where the platform has one
+ */
+export async function request(): Promise<PermissionStatus[]> {
+  switch (process.platform) {
+    case 'darwin':
+      // Shows the "grant accessibility access" dialog if not yet trusted
+      return [checkMacOS(true)];
+    default:
+      // Windows has no runtime prompt for UIAutomation access; elevation (or a
+      // signed UIAccess build) must be chosen when the app is launched.
+      return check();
+  }
+}
+
+function checkWindows(): PermissionStatus {
+  if (isElevated()) {
+    return { backend: 'ui-automation', state: 'granted', detail: 'Running as Administrator' };
+  }
+  return {
+    backend: 'ui-automation',
+    state: 'limited',
+    detail: 'Selections in elevated windows cannot be read. Run as Administrator (`npm run start-admin`) ' +
+      'or install a signed UIAccess build under Program Files.',
+  };
+}
+
+function checkMacOS(prompt: boolean): PermissionStatus {
+  const trusted = systemPreferences.isTrustedAccessibilityClient(prompt);
+  return {
+    backend: 'accessibility',
+    state: trusted ? 'granted' : 'denied',
+    detail: trusted
+      ? 'Accessibility access granted'
+      : 'Grant access in System Settings > Privacy & Security > Accessibility',
+  };
+}
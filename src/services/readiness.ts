@@ -0,0 +1,65 @@
+/**
+ * Readiness probing with bounded retries
+ *
+ * Apps launched at login can start before the desktop session is usable (no
+ * displays yet, shell not running, accessibility services still loading). These
+ * helpers wait for a probe to pass or retry an operation with backoff instead of
+ * failing permanently on the first attempt.
+ */
+
+import { Clock, sleep, systemClock } from './clock';
+
+export interface RetryOptions {
+  /** Attempts after the first one (0 = try once) */
+  retries: number;
+  /** Delay before the first retry; doubles on each further retry, up to 8x */
+  delayMs: number;
+}
+
+const MAX_BACKOFF_FACTOR = 8;
+
+function delayFor(attempt: number, options: RetryOptions): number {
+  return options.delayMs * Math.min(Math.pow(2, attempt), MAX_BACKOFF_FACTOR);
+}
+
+/**
+ * Resolve true once the probe passes, or false when retries are exhausted
+ */
+export async function waitUntil(probe: () => boolean, options: RetryOptions, clock: Clock = systemClock): Promise<boolean> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      if (probe()) {
+        return true;
+      }
+    } catch (error) {
+      // A throwing probe counts as "not ready yet"
+    }
+    if (attempt >= options.retries) {
+      return false;
+    }
+    await sleep(delayFor(attempt, options), clock);
+  }
+}
+
+/**
+ * Run an operation, retrying with backoff while it rejects; the last error is rethrown
+ */
+export async function withRetries<T>(
+  operation: () => Promise<T>,
+  options: RetryOptions,
+  label: string,
+  clock: Clock = systemClock
+): Promise<T> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return await operation();
+    } catch (error) {
+      if (attempt >= options.retries) {
+        throw error;
+      }
+      const delay = delayFor(attempt, options);
+      console.warn(`${label} Attempt ${attempt + 1} failed, retrying in ${delay}ms:`, error instanceof Error ? error.message : error);
+      await sleep(delay, clock);
+    }
+  }
+}
@@ -0,0 +1,179 @@
+/**
+ * Rich Text - formatted selection content in one small model
+ *
+ * Backends report formatting in different shapes: the Windows addon reads
+ * formatting runs from the UI Automation text pattern, the browser bridge and
+ * the clipboard carry HTML. Both are normalized into runs of text with
+ * bold/italic/underline/link flags, which is as much as popups and note-taking
+ * integrations need.
+ */
+
+export interface RichTextRun {
+  text: string;
+  bold?: boolean;
+  italic?: boolean;
+  underline?: boolean;
+  /** Target of the enclosing link */
+  link?: string;
+}
+
+export interface RichText {
+  runs: RichTextRun[];
+}
+
+export function plainRichText(text: string): RichText {
+  return { runs: text ? [{ text }] : [] };
+}
+
+export function richTextToPlain(rich: RichText): string {
+  return rich.runs.map((run) => run.text).join('');
+}
+
+const ENTITIES: Record<string, string> = { amp: '&', lt: '<', gt: '>', quot: '"', apos: "'", nbsp: ' ' };
+
+function decodeEntities(text: string): string {
+  return text.replace(/&(#x[0-9a-f]+|#\d+|[a-z]+);/gi, (match, name: string) => {
+    if (name[0] === '#') {
+      const code = name[1] === 'x' || name[1] === 'X' ? parseInt(name.slice(2), 16) : parseInt(name.slice(1), 10);
+      return code > 0 && code <= 0x10ffff ? String.fromCodePoint(code) : match;
+    }
+    return ENTITIES[name.toLowerCase()] ?? match;
+  });
+}
+
+const BLOCK_TAGS = new Set(['p', 'div', 'li', 'tr', 'h1', 'h2', 'h3', 'h4', 'h5', 'h6', 'blockquote', 'pre']);
+
+/**
+ * Runs from an HTML fragment (browser selection, text/html clipboard). Not a
+ * full parser: it tracks emphasis and link tags, turns block boundaries into
+ * line breaks and drops scripts, styles and comments.
+ */
+export function richTextFromHtml(html: string): RichText {
+  const runs: RichTextRun[] = [];
+  let bold = 0;
+  let italic = 0;
+  let underline = 0;
+  const links: (string | undefined)[] = [];
+
+  const push = (raw: string, preformatted = false) => {
+    const text = preformatted ? raw : raw.replace(/\s+/g, ' ');
+    if (!text) {
+      return;
+    }
+    const run: RichTextRun = { text };
+    if (bold > 0) run.bold = true;
+    if (italic > 0) run.italic = true;
+    if (underline > 0) run.underline = true;
+    const link = links.length > 0 ? links[links.length - 1] : undefined;
+    if (link) run.link = link;
+
+    const last = runs[runs.length - 1];
+    if (last && !!last.bold === !!run.bold && !!last.italic === !!run.italic &&
+        !!last.underline === !!run.underline && last.link === run.link) {
+      last.text += text;
+    } else {
+      runs.push(run);
+    }
+  };
+  const lineBreak = () => {
+    const last = runs[runs.length - 1];
+    if (last && !last.text.endsWith('\n')) {
+      push('\n', true);
+    }
+  };
+
+  const source = html
+    .replace(/<!--[\s\S]*?-->/g, '')
+    .replace(/<(script|style|head)\b[\s\S]*?<\/\1\s*>/gi, '');
+  const tag = /<(\/?)([a-z][a-z0-9]*)\b([^>]*)>/gi;
+  let index = 0;
+  let match: RegExpExecArray | null;
+  while ((match = tag.exec(source)) !== null) {
+    push(decodeEntities(source.slice(index, match.index)));
+    index = tag.lastIndex;
+
+    const closing = match[1] === '/';
+    const name = match[2].toLowerCase();
+    const delta = closing ? -1 : 1;
+    if (name === 'b' || name === 'strong') {
+      bold = Math.max(0, bold + delta);
+    } else if (name === 'i' || name === 'em') {
+      italic = Math.max(0, italic + delta);
+    } else if (name === 'u' || name === 'ins') {
+      underline = Math.max(0, underline + delta);
+    } else if (name === 'a') {
+      if (closing) {
+        links.pop();
+      } else {
+        const href = /\bhref\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))/i.exec(match[3]);
+        links.push(href ? decodeEntities(href[1] ?? href[2] ?? href[3]) : undefined);
+      }
+    } else if (name === 'br') {
+      push('\n', true);
+    } else if (BLOCK_TAGS.has(name)) {
+      lineBreak();
+    }
+  }
+  push(decodeEntities(source.slice(index)));
+
+  // Whitespace collapsing leaves spaces around line breaks and at the edges
+  const trimmed = runs
+    .map((run) => ({ ...run, text: run.text.replace(/ *\n */g, '\n') }))
+    .filter((run) => run.text.length > 0);
+  if (trimmed.length > 0) {
+    trimmed[0].text = trimmed[0].text.replace(/^\s+/, '');
+    const last = trimmed[trimmed.length - 1];
+    last.text = last.text.replace(/\s+$/, '');
+  }
+  return { runs: trimmed.filter((run) => run.text.length > 0) };
+}
+
+// Characters with inline meaning in Markdown, and block markers at a line start
+const MARKDOWN_INLINE = /[\\`*_[\]<>]/g;
+const MARKDOWN_LINE_START = /^(\s*)([#>+-]|\d+[.)])(?=\s)/;
+
+function escapeMarkdown(line: string, atLineStart: boolean): string {
+  const escaped = line.replace(MARKDOWN_INLINE, '\\$&');
+  return atLineStart ? escaped.replace(MARKDOWN_LINE_START, (_, space: string, marker: string) =>
+    `${space}${marker.replace(/[#>+.)-]$/, '\\$&')}`) : escaped;
+}
+
+/**
+ * Emphasis markers must hug the text, so leading and trailing whitespace goes
+ * outside them; line breaks close and reopen the markers
+ */
+function emphasize(run: RichTextRun, atLineStart: boolean): string {
+  const open = (run.bold ? '**' : '') + (run.italic ? '_' : '');
+  const close = (run.italic ? '_' : '') + (run.bold ? '**' : '');
+  return run.text.split('\n').map((line, index) => {
+    const match = /^(\s*)([\s\S]*?)(\s*)$/.exec(line) as RegExpExecArray;
+    const core = escapeMarkdown(match[2], atLineStart || index > 0);
+    return core ? `${match[1]}${open}${core}${close}${match[3]}` : line;
+  }).join('\n');
+}
+
+/**
+ * Markdown for pasting into notes: bold, italic and links are kept, underline
+ * has no Markdown form and is dropped. Adjacent runs with the same link share
+ * one link.
+ */
+export function richTextToMarkdown(rich: RichText): string {
+  let output = '';
+  let index = 0;
+  while (index < rich.runs.length) {
+    const link = rich.runs[index].link;
+    let inner = '';
+    for (; index < rich.runs.length && rich.runs[index].link === link; index++) {
+      const previous = inner || output;
+      const atLineStart = !link && (previous.length === 0 || previous.endsWith('\n'));
+      inner += emphasize(rich.runs[index], atLineStart);
+    }
+    if (link && inner.trim()) {
+      const target = link.replace(/[\s()]/g, (c) => `%${c.charCodeAt(0).toString(16).toUpperCase().padStart(2, '0')}`);
+      output += `[${inner}](${target})`;
+    } else {
+      output += inner;
+    }
+  }
+  return output;
+}
@@ -0,0 +1,73 @@
+/**
+ * Screen Capture - grab a region of the screen around a selection
+ *
+ * Used for optional selection thumbnails and as the image source for OCR.
+ * Regions are given in DIP (the coordinate space of selection events).
+ */
+
+import { desktopCapturer, NativeImage, Rectangle, screen, systemPreferences } from 'electron';
+
+/**
+ * Whether the app may capture the screen (macOS requires Screen Recording access)
+ */
+export function canCaptureScreen(): boolean {
+  if (process.platform !== 'darwin') {
+    return true;
+  }
+  return systemPreferences.getMediaAccessStatus('screen') === 'granted';
+}
+
+/**
+ * Capture a screen region in DIP, clipped to the display that contains its centre.
+ * Returns null when capture is not permitted or fails.
+ */
+export async function captureRegion(region: Rectangle): Promise<NativeImage | null> {
+  if (!canCaptureScreen()) {
+    return null;
+  }
+
+  const display = screen.getDisplayNearestPoint({
+    x: Math.round(region.x + region.width / 2),
+    y: Math.round(region.y + region.height / 2),
+  });
+  const scale = display.scaleFactor || 1;
+
+  try {
+    const sources = await desktopCapturer.getSources({
+      types: ['screen'],
+      thumbnailSize: {
+        width: Math.round(display.bounds.width * scale),
+        height: Math.round(display.bounds.height * scale),
+      },
+    });
+    const source = sources.find((candidate) => candidate.display_id === String(display.id)) || sources[0];
+    if (!source || source.thumbnail.isEmpty()) {
+      return null;
+    }
+
+    // Region relative to the display, in the thumbnail's physical pixels
+    const left = Math.max(0, Math.round((region.x - display.bounds.x) * scale));
+    const top = Math.max(0, Math.round((region.y - display.bounds.y) * scale));
+    const size = source.thumbnail.getSize();
+    const width = Math.min(size.width - left, Math.round(region.width * scale));
+    const height = Math.min(size.height - top, Math.round(region.height * scale));
+    if (width <= 0 || height <= 0) {
+      return null;
+    }
+    return source.thumbnail.crop({ x: left, y: top, width, height });
+  } catch (error) {
+    console.error('[SCREEN-CAPTURE] Failed to capture region:', error);
+    return null;
+  }
+}
+
+/**
+ * Scale an image down to at most maxWidth pixels wide
+ */
+export function shrink(image: NativeImage, maxWidth: number): NativeImage {
+  const { width } = image.getSize();
+  if (width <= maxWidth) {
+    return image;
+  }
+  return image.resize({ width: maxWidth, quality: 'good' });
+}
@@ -0,0 +1,64 @@
+/**
+ * Selection Diff - how a selection changed relative to the previous one
+ *
+ * When the user extends or shrinks a selection with the mouse or Shift+arrows,
+ * only one edge moves, so the new text is the old text with something added or
+ * removed at one end. Consumers such as reading-assist tools can then process
+ * just that part instead of the whole selection again.
+ */
+
+import type { SelectionEvent } from './native-selection';
+
+export interface SelectionDiff {
+  /** Text that is newly selected */
+  added: string;
+  /** Text that was selected before and no longer is */
+  removed: string;
+  /**
+   * Which edge moved: 'forward' when the end of the selection moved (text added
+   * or removed after the previous selection), 'backward' when the start moved
+   */
+  direction: 'forward' | 'backward';
+}
+
+function sameElement(previous: SelectionEvent, next: SelectionEvent): boolean {
+  if (previous.app !== next.app || previous.source !== next.source) {
+    return false;
+  }
+  if (previous.element && next.element) {
+    return previous.element.runtimeId.join('.') === next.element.runtimeId.join('.');
+  }
+  return true;
+}
+
+/**
+ * Diff of next against previous, or null when the two are unrelated (another
+ * element, both edges moved, or the same text)
+ */
+export function diffSelections(previous: SelectionEvent | null, next: SelectionEvent): SelectionDiff | null {
+  if (!previous || !sameElement(previous, next) || previous.text === next.text) {
+    return null;
+  }
+  const before = previous.text;
+  const after = next.text;
+  if (before.length === 0 || after.length === 0) {
+    return null;
+  }
+
+  if (after.length > before.length) {
+    if (after.startsWith(before)) {
+      return { added: after.slice(before.length), removed: '', direction: 'forward' };
+    }
+    if (after.endsWith(before)) {
+      return { added: after.slice(0, after.length - before.length), removed: '', direction: 'backward' };
+    }
+  } else {
+    if (before.startsWith(after)) {
+      return { added: '', removed: before.slice(after.length), direction: 'forward' };
+    }
+    if (before.endsWith(after)) {
+      return { added: '', removed: before.slice(0, before.length - after.length), direction: 'backward' };
+    }
+  }
+  return null;
+}
@@ -0,0 +1,147 @@
+/**
+ * Selection Dispatcher - platform-agnostic debouncing and deduplication
+ *
+ * Backends push every raw selection they observe; the dispatcher waits until the
+ * selection has been stable for the debounce interval and drops repeats of the
+ * previously delivered text, so every backend gets the same behaviour.
+ */
+
+import { Clock, systemClock, TimerHandle } from './clock';
+import type { SelectionEvent } from './native-selection';
+import type { DropReason } from './selection-metrics';
+
+export interface DispatcherOptions {
+  /** Emit only after the selection has been unchanged for this long (0 = immediately) */
+  debounceMs: number;
+  /** Identical text delivered within this window is suppressed (0 = never suppress) */
+  dedupeWindowMs: number;
+}
+
+export class SelectionDispatcher {
+  private pending: SelectionEvent | null = null;
+  private timer: TimerHandle | null = null;
+  private lastDelivered: SelectionEvent | null = null;
+  private listeners: ((event: SelectionEvent) => void)[] = [];
+  private dropListeners: ((event: SelectionEvent, reason: DropReason) => void)[] = [];
+
+  constructor(private options: DispatcherOptions, private clock: Clock = systemClock) {}
+
+  /**
+   * Register a listener for settled selections
+   */
+  onEvent(listener: (event: SelectionEvent) => void): void {
+    this.listeners.push(listener);
+  }
+
+  /**
+   * Register a listener for selections that were swallowed by debounce or dedupe
+   */
+  onDrop(listener: (event: SelectionEvent, reason: DropReason) => void): void {
+    this.dropListeners.push(listener);
+  }
+
+  /**
+   * Change the debounce interval; applies from the next pushed selection
+   */
+  setDebounceMs(debounceMs: number): void {
+    this.options = { ...this.options, debounceMs };
+  }
+
+  /**
+   * Queue a raw selection; it is delivered once it has settled
+   */
+  push(event: SelectionEvent): void {
+    if (this.pending) {
+      this.drop(this.pending, 'superseded');
+    }
+    this.pending = event;
+
+    if (this.timer) {
+      this.clock.clearTimeout(this.timer);
+      this.timer = null;
+    }
+
+    if (this.options.debounceMs <= 0) {
+      this.flush();
+      return;
+    }
+
+    this.timer = this.clock.setTimeout(() => {
+      this.timer = null;
+      this.flush();
+    }, this.options.debounceMs);
+  }
+
+  /**
+   * Priority lane for explicit user actions (e.g. a capture hotkey): the event is
+   * delivered right away, bypassing debounce and dedupe, and supersedes any
+   * passive selection still waiting to settle.
+   */
+  pushImmediate(event: SelectionEvent): void {
+    if (this.timer) {
+      this.clock.clearTimeout(this.timer);
+      this.timer = null;
+    }
+    if (this.pending) {
+      this.drop(this.pending, 'superseded');
+    }
+    this.pending = null;
+    this.deliver(event);
+  }
+
+  /**
+   * Drop any pending selection and forget the last delivered one
+   */
+  reset(): void {
+    if (this.timer) {
+      this.clock.clearTimeout(this.timer);
+      this.timer = null;
+    }
+    this.pending = null;
+    this.lastDelivered = null;
+  }
+
+  private flush(): void {
+    const event = this.pending;
+    this.pending = null;
+    if (!event) {
+      return;
+    }
+
+    if (this.isDuplicate(event)) {
+      this.drop(event, 'duplicate');
+      return;
+    }
+
+    this.deliver(event);
+  }
+
+  private deliver(event: SelectionEvent): void {
+    this.lastDelivered = event;
+    this.listeners.forEach((listener) => {
+      try {
+        listener(event);
+      } catch (error) {
+        console.error('[DISPATCHER] Error in listener:', error);
+      }
+    });
+  }
+
+  private drop(event: SelectionEvent, reason: DropReason): void {
+    this.dropListeners.forEach((listener) => {
+      try {
+        listener(event, reason);
+      } catch (error) {
+        console.error('[DISPATCHER] Error in drop listener:', error);
+      }
+    });
+  }
+
+  private isDuplicate(event: SelectionEvent): boolean {
+    if (!this.lastDelivered || this.options.dedupeWindowMs <= 0) {
+      return false;
+    }
+    return this.lastDelivered.text === event.text &&
+      event.timestamp - this.lastDelivered.timestamp < this.options.dedupeWindowMs;
+  }
+}
@@ -0,0 +1,94 @@
+/**
+ * Selection Entities - links, addresses and paths found in selected text
+ *
+ * Lets host apps offer "open link", "compose mail", "call" or "reveal file"
+ * actions without scanning the text themselves. Pattern based: a match is a
+ * likely candidate, not a validated address.
+ */
+
+export type SelectionEntityType = 'url' | 'email' | 'phone' | 'path';
+
+export interface SelectionEntity {
+  type: SelectionEntityType;
+  /** Matched text as it appears in the selection */
+  text: string;
+  /** UTF-16 offsets into the selection text */
+  start: number;
+  end: number;
+  /**
+   * Normalized form for acting on it: an absolute URL ("www." gets "https://"),
+   * the bare address, the phone number as "+" and digits, or the path
+   */
+  value: string;
+}
+
+const PATTERNS: [SelectionEntityType, RegExp][] = [
+  ['url', /\b(?:(?:https?|ftp):\/\/|www\.)[^\s<>"'`]+/gi],
+  ['email', /\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b/gi],
+  // Drive-letter, UNC and POSIX paths; POSIX paths need two segments so "and/or" is not one
+  ['path', /(?:\b[a-z]:\\|\\\\[\w.$-]+\\)[^\s<>"|?*]*|(?:~|\.{1,2})?\/[\w.@+-]+(?:\/[\w.@+-]+)+\/?/gi],
+  ['phone', /(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?)?\d{2,4}(?:[\s.-]\d{2,5}){1,4}/g],
+];
+
+// Sentence punctuation that usually follows a link or path rather than belonging to it
+const TRAILING = /[.,;:!?'")\]}]+$/;
+
+function trimTrailing(text: string): string {
+  let trimmed = text.replace(TRAILING, '');
+  // Keep a closing parenthesis that has a partner, as in wiki URLs
+  const extra = text.slice(trimmed.length);
+  if (extra.startsWith(')') && trimmed.includes('(')) {
+    trimmed += ')';
+  }
+  return trimmed;
+}
+
+function normalize(type: SelectionEntityType, text: string): string | null {
+  switch (type) {
+    case 'url':
+      return /^www\./i.test(text) ? `https://${text}` : text;
+    case 'phone': {
+      const digits = text.replace(/\D/g, '');
+      // Dates and IPv4 addresses are digit groups too
+      if (digits.length < 7 || digits.length > 15 || /^\d{4}[-./]\d{1,2}[-./]\d{1,2}$/.test(text) ||
+          /^\d{1,3}(?:\.\d{1,3}){3}$/.test(text)) {
+        return null;
+      }
+      return text.trim().startsWith('+') ? `+${digits}` : digits;
+    }
+    default:
+      return text;
+  }
+}
+
+/**
+ * Entities in text, ordered by position. Where matches overlap the earlier
+ * pattern wins (a URL over the path or phone number inside it).
+ */
+export function extractEntities(text: string): SelectionEntity[] {
+  const entities: SelectionEntity[] = [];
+  const overlaps = (start: number, end: number) =>
+    entities.some((entity) => start < entity.end && end > entity.start);
+
+  for (const [type, pattern] of PATTERNS) {
+    pattern.lastIndex = 0;
+    let match: RegExpExecArray | null;
+    while ((match = pattern.exec(text)) !== null) {
+      const matched = type === 'url' || type === 'path' ? trimTrailing(match[0]) : match[0];
+      const start = match.index;
+      const end = start + matched.length;
+      // POSIX paths must start a word, not continue one ("km/h/day")
+      if (type === 'path' && matched[0] !== '\\' && start > 0 && /[\w/]/.test(text[start - 1])) {
+        continue;
+      }
+      if (!matched || overlaps(start, end)) {
+        continue;
+      }
+      const value = normalize(type, matched);
+      if (value !== null) {
+        entities.push({ type, text: matched, start, end, value });
+      }
+    }
+  }
+  return entities.sort((a, b) => a.start - b.start);
+}
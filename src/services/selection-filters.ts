@@ -0,0 +1,118 @@
+/**
+ * Selection Filters - sensitive-content redaction
+ *
+ * Runs on raw selection text before it is stored or handed to any callback.
+ * A matching selection is either dropped entirely or has the sensitive parts masked.
+ */
+
+export type RedactionAction = 'drop' | 'mask';
+
+export interface RedactionOptions {
+  enabled: boolean;
+  /** What to do with a selection that contains sensitive content */
+  action: RedactionAction;
+  /** Detect payment card numbers (validated with the Luhn checksum) */
+  detectCreditCards: boolean;
+  /** Mask any run of at least this many digits, e.g. account or phone numbers (0 = off) */
+  minDigitRun: number;
+  /** Additional patterns to treat as sensitive (strings are compiled as regexes) */
+  patterns: (string | RegExp)[];
+}
+
+export const DEFAULT_REDACTION_OPTIONS: RedactionOptions = {
+  enabled: false,
+  action: 'mask',
+  detectCreditCards: true,
+  minDigitRun: 0,
+  patterns: [],
+};
+
+export const REDACTION_MASK = '[REDACTED]';
+
+// 13-19 digits, optionally grouped with spaces or dashes
+const CARD_CANDIDATE = /\b\d(?:[ -]?\d){12,18}\b/g;
+
+/**
+ * Apply redaction to a selection. Returns the (possibly masked) text,
+ * or null when the selection should be dropped.
+ */
+export function redactSelection(text: string, options: RedactionOptions): string | null {
+  if (!options.enabled) {
+    return text;
+  }
+
+  let sensitive = false;
+  let result = text;
+
+  if (options.detectCreditCards) {
+    result = result.replace(CARD_CANDIDATE, (match) => {
+      if (!passesLuhn(match.replace(/[ -]/g, ''))) {
+        return match;
+      }
+      sensitive = true;
+      return REDACTION_MASK;
+    });
+  }
+
+  if (options.minDigitRun > 0) {
+    const digitRun = new RegExp(`\\d{${Math.floor(options.minDigitRun)},}`, 'g');
+    result = result.replace(digitRun, () => {
+      sensitive = true;
+      return REDACTION_MASK;
+    });
+  }
+
+  for (const pattern of options.patterns) {
+    const regex = toGlobalRegExp(pattern);
+    if (regex.test(result)) {
+      sensitive = true;
+      regex.lastIndex = 0;
+      result = result.replace(regex, REDACTION_MASK);
+    }
+  }
+
+  if (!sensitive) {
+    return text;
+  }
+  return options.action === 'drop' ? null : result;
+}
+
+/**
+ * Redact the text of an event-like object, returning a copy (or null to drop it).
+ * Meant for trust boundaries such as IPC to a renderer, where a different policy
+ * than the in-process one may apply.
+ */
+export function redactEvent<T extends { text: string }>(event: T, options: RedactionOptions): T | null {
+  const text = redactSelection(event.text, options);
+  if (text === null) {
+    return null;
+  }
+  return text === event.text ? event : { ...event, text };
+}
+
+function toGlobalRegExp(pattern: string | RegExp): RegExp {
+  if (typeof pattern === 'string') {
+    return new RegExp(pattern, 'g');
+  }
+  return pattern.global ? new RegExp(pattern.source, pattern.flags) : new RegExp(pattern.source, pattern.flags + 'g');
+}
+
+/**
+ * Luhn checksum used by payment card numbers
+ */
+function passesLuhn(digits: string): boolean {
+  let sum = 0;
+  let double = false;
+  for (let i = digits.length - 1; i >= 0; i--) {
+    let digit = digits.charCodeAt(i) - 48;
+    if (double) {
+      digit *= 2;
+      if (digit > 9) {
+        digit -= 9;
+      }
+    }
+    sum += digit;
+    double = !double;
+  }
+  return sum % 10 === 0;
+}
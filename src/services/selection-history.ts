@@ -0,0 +1,76 @@
+/**
+ * Selection History - bounded ring buffer of delivered selection events
+ *
+ * Opt-in: a capacity of 0 keeps nothing. Once full, the oldest event is overwritten.
+ * Each entry is treated as the active selection from its timestamp until the next
+ * entry's timestamp, which is what at() answers queries from.
+ */
+
+import type { SelectionEvent } from './native-selection';
+
+export class SelectionHistory {
+  private buffer: (SelectionEvent | undefined)[] = [];
+  private head = 0; // index of the next slot to write
+  private size = 0;
+
+  constructor(private capacity: number = 0) {
+    this.buffer = new Array(Math.max(0, capacity));
+  }
+
+  /**
+   * Record a delivered selection
+   */
+  push(event: SelectionEvent): void {
+    if (this.capacity <= 0) {
+      return;
+    }
+    this.buffer[this.head] = event;
+    this.head = (this.head + 1) % this.capacity;
+    this.size = Math.min(this.size + 1, this.capacity);
+  }
+
+  /**
+   * Recorded selections, most recent first
+   */
+  entries(): SelectionEvent[] {
+    const result: SelectionEvent[] = [];
+    for (let i = 1; i <= this.size; i++) {
+      const index = (this.head - i + this.capacity) % this.capacity;
+      result.push(this.buffer[index] as SelectionEvent);
+    }
+    return result;
+  }
+
+  /**
+   * The selection that was active at the given time, or null if that time is
+   * before the oldest retained entry
+   */
+  at(timestamp: number): SelectionEvent | null {
+    for (const event of this.entries()) {
+      if (event.timestamp <= timestamp) {
+        return event;
+      }
+    }
+    return null;
+  }
+
+  clear(): void {
+    this.buffer = new Array(Math.max(0, this.capacity));
+    this.head = 0;
+    this.size = 0;
+  }
+
+  /**
+   * Change the capacity, keeping the most recent entries that still fit
+   */
+  setCapacity(capacity: number): void {
+    const kept = this.entries().slice(0, Math.max(0, capacity)).reverse();
+    this.capacity = Math.max(0, capacity);
+    this.clear();
+    kept.forEach((event) => this.push(event));
+  }
+
+  getCapacity(): number {
+    return this.capacity;
+  }
+}
@@ -0,0 +1,110 @@
+/**
+ * Selection Metrics - counters and latency histogram for tuning debounce settings
+ *
+ * Latency is measured from the moment a backend observed the selection (the
+ * event timestamp) to delivery to callbacks, so it includes the debounce delay.
+ */
+
+import { Clock, systemClock } from './clock';
+
+/**
+ * Why an observed selection was not delivered
+ * - 'filtered': not worth a lookup (whitespace, too short, ...)
+ * - 'redacted': dropped by the redaction policy
+ * - 'too-long': longer than maxSelectionLength
+ * - 'paused': observed while the service was paused
+ * - 'duplicate': repeat of the previous text within the dedupe window
+ * - 'superseded': replaced by a newer selection before it settled
+ */
+export type DropReason = 'filtered' | 'redacted' | 'too-long' | 'paused' | 'duplicate' | 'superseded';
+
+export interface LatencyBucket {
+  /** Upper bound in milliseconds (Infinity for the overflow bucket) */
+  le: number;
+  count: number;
+}
+
+export interface MetricsSnapshot {
+  /** Raw selections observed by the backend */
+  received: number;
+  /** Selections handed to callbacks */
+  delivered: number;
+  dropped: Record<DropReason, number>;
+  latency: {
+    buckets: LatencyBucket[];
+    count: number;
+    sumMs: number;
+    maxMs: number;
+  };
+}
+
+const LATENCY_BOUNDS_MS = [10, 50, 100, 250, 500, 1000, 2500, Infinity];
+
+function emptyDrops(): Record<DropReason, number> {
+  return { filtered: 0, redacted: 0, 'too-long': 0, paused: 0, duplicate: 0, superseded: 0 };
+}
+
+export class SelectionMetrics {
+  private received = 0;
+  private delivered = 0;
+  private dropped = emptyDrops();
+  private bucketCounts = LATENCY_BOUNDS_MS.map(() => 0);
+  private latencySum = 0;
+  private latencyMax = 0;
+
+  constructor(private clock: Clock = systemClock) {}
+
+  recordReceived(): void {
+    this.received++;
+  }
+
+  recordDropped(reason: DropReason): void {
+    this.dropped[reason]++;
+  }
+
+  /**
+   * Record a delivery of an event observed at the given time
+   */
+  recordDelivered(observedAt: number, now: number = this.clock.now()): void {
+    this.delivered++;
+    const latency = Math.max(0, now - observedAt);
+    this.latencySum += latency;
+    this.latencyMax = Math.max(this.latencyMax, latency);
+    const index = LATENCY_BOUNDS_MS.findIndex((bound) => latency <= bound);
+    this.bucketCounts[index]++;
+  }
+
+  snapshot(): MetricsSnapshot {
+    return {
+      received: this.received,
+      delivered: this.delivered,
+      dropped: { ...this.dropped },
+      latency: {
+        buckets: LATENCY_BOUNDS_MS.map((le, i) => ({ le, count: this.bucketCounts[i] })),
+        count: this.delivered,
+        sumMs: this.latencySum,
+        maxMs: this.latencyMax,
+      },
+    };
+  }
+
+  reset(): void {
+    this.received = 0;
+    this.delivered = 0;
+    this.dropped = emptyDrops();
+    this.bucketCounts = LATENCY_BOUNDS_MS.map(() => 0);
+    this.latencySum = 0;
+    this.latencyMax = 0;
+  }
+}
+
+/**
+ * Add up drop counters from two stages of the same pipeline
+ */
+export function mergeDrops(a: Record<DropReason, number>, b: Record<DropReason, number>): Record<DropReason, number> {
+  const result = emptyDrops();
+  (Object.keys(result) as DropReason[]).forEach((reason) => {
+    result[reason] = a[reason] + b[reason];
+  });
+  return result;
+}
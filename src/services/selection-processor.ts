@@ -0,0 +1,229 @@
+/**
+ * Selection Processors - the per-selection transform pipeline
+ *
+ * Every settled selection passes through an ordered list of processors before
+ * it is delivered, stored in history or enriched. Each one may return a changed
+ * copy of the event or drop it. The service installs the built-in stages its
+ * config asks for (diffs, entities, language and direction detection) and callers can add
+ * their own anywhere in the list, including the redaction, sanitize and filter
+ * stages below.
+ *
+ * Processors run synchronously on the delivery path; anything slow belongs in
+ * an enricher (see EnrichmentScheduler).
+ */
+
+import type { SelectedRange, SelectionEvent } from './native-selection';
+import type { RichTextRun } from './rich-text';
+import { diffSelections } from './selection-diff';
+import { extractEntities } from './selection-entities';
+import { RedactionOptions, redactSelection } from './selection-filters';
+import { detectLanguage } from './language-detect';
+import { SanitizeOptions, sanitizeText } from './text-sanitize';
+import { analyzeBidi, hasRtl } from './text-bidi';
+
+export interface SelectionProcessor {
+  /** Unique within a pipeline; adding another processor with this name replaces it */
+  readonly name: string;
+  /** The event to pass on (the same object when unchanged), or null to drop the selection */
+  process(event: SelectionEvent): SelectionEvent | null;
+  /** Forget state carried between selections, e.g. when the selection is cleared */
+  reset?(): void;
+}
+
+export interface ProcessorPosition {
+  /** Insert ahead of the processor with this name; appended when absent or unknown */
+  before?: string;
+}
+
+export class SelectionPipeline {
+  private processors: SelectionProcessor[] = [];
+
+  add(processor: SelectionProcessor, position: ProcessorPosition = {}): void {
+    this.remove(processor.name);
+    const index = position.before === undefined
+      ? -1
+      : this.processors.findIndex((existing) => existing.name === position.before);
+    if (index < 0) {
+      this.processors.push(processor);
+    } else {
+      this.processors.splice(index, 0, processor);
+    }
+  }
+
+  remove(name: string): void {
+    this.processors = this.processors.filter((processor) => processor.name !== name);
+  }
+
+  names(): string[] {
+    return this.processors.map((processor) => processor.name);
+  }
+
+  /**
+   * Run the event through every processor in order. A processor that throws is
+   * skipped (the event passes on unchanged) so one bad stage cannot stop capture.
+   */
+  run(event: SelectionEvent): SelectionEvent | null {
+    let current = event;
+    for (const processor of this.processors) {
+      try {
+        const next = processor.process(current);
+        if (!next) {
+          return null;
+        }
+        current = next;
+      } catch (error) {
+        console.error(`[NATIVE-SERVICE] Error in processor ${processor.name}:`, error);
+      }
+    }
+    return current;
+  }
+
+  reset(): void {
+    this.processors.forEach((processor) => processor.reset?.());
+  }
+}
+
+/**
+ * Attach a diff against the previous selection when only one edge moved
+ */
+export function diffProcessor(): SelectionProcessor {
+  let previous: SelectionEvent | null = null;
+  return {
+    name: 'diff',
+    process(event) {
+      const diff = diffSelections(previous, event);
+      previous = event;
+      return diff ? { ...event, diff } : event;
+    },
+    reset() {
+      previous = null;
+    },
+  };
+}
+
+export function entityProcessor(): SelectionProcessor {
+  return {
+    name: 'entities',
+    process(event) {
+      if (event.entities) {
+        return event;
+      }
+      const entities = extractEntities(event.text);
+      return entities.length > 0 ? { ...event, entities } : event;
+    },
+  };
+}
+
+export function languageProcessor(): SelectionProcessor {
+  return {
+    name: 'language',
+    process(event) {
+      if (event.language) {
+        return event;
+      }
+      const language = detectLanguage(event.text);
+      return language ? { ...event, language } : event;
+    },
+  };
+}
+
+/**
+ * Attach direction runs to right-to-left and mixed-direction text, using the
+ * paragraph direction the app reported as the base when there is one
+ */
+export function bidiProcessor(): SelectionProcessor {
+  return {
+    name: 'bidi',
+    process(event) {
+      if (event.bidi || (!hasRtl(event.text) && event.direction !== 'rtl')) {
+        return event;
+      }
+      return { ...event, bidi: analyzeBidi(event.text, event.direction ?? null) };
+    },
+  };
+}
+
+/**
+ * Sanitize text, ranges, context and rich runs. The service's own `sanitize` config is
+ * applied before debouncing (so dedupe compares cleaned text); this stage is
+ * for additional clean-up, e.g. only for one consumer's composed service.
+ */
+export function sanitizeProcessor(options: SanitizeOptions): SelectionProcessor {
+  const clean = (text: string) => sanitizeText(text, options);
+  return {
+    name: 'sanitize',
+    process(event) {
+      return {
+        ...event,
+        text: clean(event.text),
+        ranges: event.ranges && event.ranges.map((range) => ({ ...range, text: clean(range.text) })),
+        context: event.context && { before: clean(event.context.before), after: clean(event.context.after) },
+        rich: event.rich && { runs: event.rich.runs.map((run) => ({ ...run, text: clean(run.text) })) },
+      };
+    },
+  };
+}
+
+/**
+ * Redact text, ranges, context, rich runs and string extensions (such as a
+ * browser selection's markup); drops the selection when the policy rejects it.
+ * The service runs one with its `redaction` config on every event before it is
+ * buffered, so adding another is for stricter rules layered on top.
+ */
+export function redactionProcessor(options: RedactionOptions): SelectionProcessor {
+  return {
+    name: 'redaction',
+    process(event) {
+      const text = redactSelection(event.text, options);
+      if (text === null) {
+        return null;
+      }
+      const redact = (value: string) => redactSelection(value, options) ?? '';
+      return {
+        ...event,
+        text,
+        ranges: event.ranges && event.ranges
+          .map((range) => ({ ...range, text: redactSelection(range.text, options) }))
+          .filter((range): range is SelectedRange => range.text !== null),
+        context: event.context && { before: redact(event.context.before), after: redact(event.context.after) },
+        rich: event.rich && {
+          runs: event.rich.runs
+            .map((run) => ({ ...run, text: redactSelection(run.text, options) }))
+            .filter((run): run is RichTextRun => run.text !== null),
+        },
+        extensions: event.extensions && redactExtensions(event.extensions, options),
+        // Entities point into the original text
+        entities: text === event.text ? event.entities : undefined,
+      };
+    },
+  };
+}
+
+// Extensions a rejected value belongs to are left out rather than blanked
+function redactExtensions(extensions: Record<string, unknown>, options: RedactionOptions): Record<string, unknown> {
+  const result: Record<string, unknown> = {};
+  Object.keys(extensions).forEach((key) => {
+    const value = extensions[key];
+    if (typeof value !== 'string') {
+      result[key] = value;
+      return;
+    }
+    const redacted = redactSelection(value, options);
+    if (redacted !== null) {
+      result[key] = redacted;
+    }
+  });
+  return result;
+}
+
+/**
+ * Drop selections the predicate rejects
+ */
+export function filterProcessor(name: string, accept: (event: SelectionEvent) => boolean): SelectionProcessor {
+  return {
+    name,
+    process(event) {
+      return accept(event) ? event : null;
+    },
+  };
+}
@@ -0,0 +1,106 @@
+/**
+ * Selection Receiver - a pull-based, independently buffered view of delivered selections
+ *
+ * Each subscriber gets its own queue, so a slow reader never blocks the service
+ * or other subscribers and every reader sees each event at most once. What
+ * happens when a reader falls behind is its BackpressurePolicy; dropped events
+ * are counted as lag, which the reader collects with takeLagged().
+ */
+
+import type { SelectionEvent } from './native-selection';
+
+/**
+ * - 'block': never drop; the queue grows until the reader catches up (lossless, for loggers)
+ * - 'drop-oldest': when full, discard the oldest buffered event
+ * - 'drop-newest': when full, discard the incoming event
+ * - 'coalesce': keep only the latest event (real-time consumers)
+ */
+export type BackpressurePolicy = 'block' | 'drop-oldest' | 'drop-newest' | 'coalesce';
+
+export class SelectionReceiver {
+  private queue: SelectionEvent[] = [];
+  private waiters: ((event: SelectionEvent | null) => void)[] = [];
+  private lagged = 0;
+  private closed = false;
+
+  constructor(
+    private capacity: number,
+    private policy: BackpressurePolicy = 'drop-oldest',
+    private onClose: (receiver: SelectionReceiver) => void = () => undefined
+  ) {}
+
+  /**
+   * Called by the service for every delivered event
+   */
+  push(event: SelectionEvent): void {
+    if (this.closed) {
+      return;
+    }
+    const waiter = this.waiters.shift();
+    if (waiter) {
+      waiter(event);
+      return;
+    }
+    const limit = this.policy === 'coalesce' ? 1 : Math.max(1, this.capacity);
+    if (this.policy !== 'block' && this.queue.length >= limit) {
+      this.lagged++;
+      if (this.policy === 'drop-newest') {
+        return;
+      }
+      this.queue.shift();
+    }
+    this.queue.push(event);
+  }
+
+  /**
+   * Next event, waiting for one if the queue is empty. Resolves to null once the
+   * receiver is closed.
+   */
+  recv(): Promise<SelectionEvent | null> {
+    const event = this.queue.shift();
+    if (event) {
+      return Promise.resolve(event);
+    }
+    if (this.closed) {
+      return Promise.resolve(null);
+    }
+    return new Promise((resolve) => this.waiters.push(resolve));
+  }
+
+  /**
+   * Next buffered event without waiting
+   */
+  tryRecv(): SelectionEvent | null {
+    return this.queue.shift() ?? null;
+  }
+
+  /**
+   * Number of events dropped because this receiver fell behind since the last call
+   */
+  takeLagged(): number {
+    const lagged = this.lagged;
+    this.lagged = 0;
+    return lagged;
+  }
+
+  pending(): number {
+    return this.queue.length;
+  }
+
+  /**
+   * Stop receiving; pending recv() calls resolve to null and buffered events are discarded
+   */
+  close(): void {
+    if (this.closed) {
+      return;
+    }
+    this.closed = true;
+    this.queue = [];
+    this.waiters.splice(0).forEach((waiter) => waiter(null));
+    this.onClose(this);
+  }
+
+  isClosed(): boolean {
+    return this.closed;
+  }
+}
@@ -0,0 +1,79 @@
+/**
+ * Selection Rules - declarative conditions for onMatch() actions
+ *
+ * Integrators describe which selections they care about ("Japanese text longer
+ * than 2 characters in a browser") instead of filtering every event in their
+ * own callback. Rules are checked in the main process against delivered
+ * selections, so a renderer or plugin only hears about the ones that match.
+ */
+
+import type { SelectionEvent } from './native-selection';
+
+export type SelectionMatcher =
+  /** Text matches the pattern (strings are compiled as regexes) */
+  | { regex: RegExp | string }
+  /** Source process name, case-insensitive with ".exe" optional */
+  | { app: string | string[] }
+  | { minLength: number }
+  | { maxLength: number }
+  /** Detected language tag; needs detectLanguage in the config */
+  | { language: string | string[] }
+  | { source: SelectionEvent['source'] | SelectionEvent['source'][] }
+  | { all: SelectionMatcher[] }
+  | { any: SelectionMatcher[] }
+  | { not: SelectionMatcher }
+  | ((event: SelectionEvent) => boolean);
+
+function appName(name: string): string {
+  return name.toLowerCase().replace(/\.exe$/, '');
+}
+
+function list<T>(value: T | T[]): T[] {
+  return Array.isArray(value) ? value : [value];
+}
+
+/**
+ * Turn a matcher into a predicate; regexes and name lists are prepared once
+ * rather than per event
+ */
+export function compileMatcher(matcher: SelectionMatcher): (event: SelectionEvent) => boolean {
+  if (typeof matcher === 'function') {
+    return matcher;
+  }
+  if ('regex' in matcher) {
+    const source = matcher.regex;
+    // A global or sticky regex would carry lastIndex from one event to the next
+    const pattern = typeof source === 'string' ? new RegExp(source) : new RegExp(source.source, source.flags.replace(/[gy]/g, ''));
+    return (event) => pattern.test(event.text);
+  }
+  if ('app' in matcher) {
+    const apps = list(matcher.app).map(appName);
+    return (event) => !!event.app && apps.includes(appName(event.app));
+  }
+  if ('minLength' in matcher) {
+    const min = matcher.minLength;
+    return (event) => Array.from(event.text).length >= min;
+  }
+  if ('maxLength' in matcher) {
+    const max = matcher.maxLength;
+    return (event) => Array.from(event.text).length <= max;
+  }
+  if ('language' in matcher) {
+    const languages = list(matcher.language).map((language) => language.toLowerCase());
+    return (event) => !!event.language && languages.includes(event.language.toLowerCase());
+  }
+  if ('source' in matcher) {
+    const sources = list(matcher.source);
+    return (event) => sources.includes(event.source);
+  }
+  if ('all' in matcher) {
+    const predicates = matcher.all.map(compileMatcher);
+    return (event) => predicates.every((predicate) => predicate(event));
+  }
+  if ('any' in matcher) {
+    const predicates = matcher.any.map(compileMatcher);
+    return (event) => predicates.some((predicate) => predicate(event));
+  }
+  const inner = compileMatcher(matcher.not);
+  return (event) => !inner(event);
+}
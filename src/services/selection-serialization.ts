@@ -0,0 +1,91 @@
+/**
+ * Selection Serialization - JSON forms of events, errors and listener config
+ *
+ * Used wherever selections cross a process or storage boundary (the socket and
+ * WebSocket servers, logs, persisted history) so every consumer reads and
+ * writes the same shapes.
+ */
+
+import {
+  DEFAULT_LISTENER_CONFIG,
+  ListenerConfig,
+  SelectionError,
+  SelectionErrorCode,
+  SelectionEvent,
+} from './native-selection';
+import type { RedactionOptions } from './selection-filters';
+
+export interface SelectionErrorData {
+  code: SelectionErrorCode;
+  message: string;
+  platformCode?: number;
+}
+
+/**
+ * ListenerConfig without the parts that cannot be represented in JSON: the
+ * clock is dropped and regex patterns become their source strings
+ */
+export type ListenerConfigData = Omit<ListenerConfig, 'clock' | 'redaction'> & {
+  redaction: Omit<RedactionOptions, 'patterns'> & { patterns: string[] };
+};
+
+const SOURCES: SelectionEvent['source'][] = ['native', 'manual', 'hotkey', 'clipboard', 'browser', 'own-app'];
+
+export function eventToJSON(event: SelectionEvent): string {
+  return JSON.stringify(event);
+}
+
+/**
+ * Parse an event written by eventToJSON(); throws if required fields are missing
+ */
+export function eventFromJSON(json: string): SelectionEvent {
+  const data = JSON.parse(json);
+  if (!data || typeof data !== 'object') {
+    throw new TypeError('Selection event must be a JSON object');
+  }
+  if (typeof data.text !== 'string' || typeof data.x !== 'number' || typeof data.y !== 'number' ||
+      typeof data.timestamp !== 'number') {
+    throw new TypeError('Selection event needs text, x, y and timestamp');
+  }
+  if (!SOURCES.includes(data.source)) {
+    throw new TypeError(`Unknown selection source: ${data.source}`);
+  }
+  return data as SelectionEvent;
+}
+
+export function errorToData(error: SelectionError): SelectionErrorData {
+  return error.toJSON();
+}
+
+export function errorFromData(data: SelectionErrorData): SelectionError {
+  return new SelectionError(data.message, data.code, data.platformCode);
+}
+
+export function configToData(config: ListenerConfig): ListenerConfigData {
+  const data: ListenerConfigData & { clock?: unknown } = {
+    ...config,
+    redaction: {
+      ...config.redaction,
+      patterns: config.redaction.patterns.map((pattern) => (typeof pattern === 'string' ? pattern : pattern.source)),
+    },
+  };
+  delete data.clock;
+  return data;
+}
+
+/**
+ * Rebuild a full config from (possibly partial) data, filling gaps with the defaults
+ */
+export function configFromData(data: Partial<ListenerConfigData>): ListenerConfig {
+  return {
+    ...DEFAULT_LISTENER_CONFIG,
+    ...data,
+    redaction: { ...DEFAULT_LISTENER_CONFIG.redaction, ...data.redaction },
+    thumbnail: { ...DEFAULT_LISTENER_CONFIG.thumbnail, ...data.thumbnail },
+    ocr: { ...DEFAULT_LISTENER_CONFIG.ocr, ...data.ocr },
+    sanitize: { ...DEFAULT_LISTENER_CONFIG.sanitize, ...data.sanitize },
+    watchdog: { ...DEFAULT_LISTENER_CONFIG.watchdog, ...data.watchdog },
+    historyStore: { ...DEFAULT_LISTENER_CONFIG.historyStore, ...data.historyStore },
+    clock: DEFAULT_LISTENER_CONFIG.clock,
+  };
+}
@@ -0,0 +1,133 @@
+/**
+ * Selection Server - broadcast selections to other local processes
+ *
+ * Listens on a named pipe (Windows) or Unix domain socket (macOS/Linux) and
+ * writes every delivered selection to each connected client as one line of
+ * JSON, so editors and scripts can follow selections without linking against
+ * the app. Clients authenticate with the token first (see local-auth.ts) and
+ * then only receive; anything else they send is ignored.
+ */
+
+import * as net from 'net';
+import { authenticateLineClient, ClientAuthOptions, ClientAuthorizer, DEFAULT_CLIENT_AUTH_OPTIONS } from './local-auth';
+import { listenLocal, userSocketPath } from './local-socket';
+import type { NativeSelectionService, SelectionEvent } from './native-selection';
+import { DEFAULT_REDACTION_OPTIONS, RedactionOptions, redactEvent } from './selection-filters';
+import { eventToJSON } from './selection-serialization';
+
+export interface BroadcastOptions {
+  /** Empty = the per-user default (see defaultSocketPath()) */
+  socketPath: string;
+  /** Applied before an event leaves the process */
+  redaction: RedactionOptions;
+  /** Which clients may connect */
+  auth: ClientAuthOptions;
+}
+
+/** A client that lets more than this pile up unread is disconnected */
+const MAX_CLIENT_BUFFER_BYTES = 1024 * 1024;
+
+export function defaultSocketPath(): string {
+  return userSocketPath('phevere-selection');
+}
+
+export class SelectionBroadcastServer {
+  private server: net.Server | null = null;
+  private connections = new Set<net.Socket>();
+  private clients = new Set<net.Socket>();
+  private authorizer: ClientAuthorizer | null = null;
+  private options: BroadcastOptions;
+
+  constructor(service: NativeSelectionService, options: Partial<BroadcastOptions> = {}) {
+    this.options = {
+      socketPath: '',
+      redaction: DEFAULT_REDACTION_OPTIONS,
+      auth: DEFAULT_CLIENT_AUTH_OPTIONS,
+      ...options,
+    };
+    service.onSelection((event) => this.broadcast(event));
+  }
+
+  /**
+   * Start accepting clients
+   */
+  async listen(): Promise<void> {
+    if (this.server) {
+      return;
+    }
+
+    const socketPath = this.options.socketPath || defaultSocketPath();
+    this.authorizer = new ClientAuthorizer(this.options.auth);
+    const server = net.createServer((socket) => this.accept(socket));
+    this.server = server;
+
+    try {
+      await listenLocal(server, socketPath);
+    } catch (error) {
+      this.server = null;
+      throw error;
+    }
+    server.on('error', (error) => console.error('[SELECTION-SERVER] Server error:', error));
+    console.log(`[SELECTION-SERVER] Listening on ${socketPath}`);
+  }
+
+  async close(): Promise<void> {
+    const server = this.server;
+    if (!server) {
+      return;
+    }
+    this.server = null;
+    this.connections.forEach((connection) => connection.destroy());
+    this.connections.clear();
+    this.clients.clear();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  }
+
+  /**
+   * Write one event to every connected client as a JSON line
+   */
+  broadcast(event: SelectionEvent): void {
+    if (!this.server || this.clients.size === 0) {
+      return;
+    }
+    const redacted = redactEvent(event, this.options.redaction);
+    if (!redacted) {
+      return;
+    }
+    const line = eventToJSON(redacted) + '\n';
+    this.clients.forEach((client) => {
+      if (client.destroyed) {
+        return;
+      }
+      if (client.writableLength > MAX_CLIENT_BUFFER_BYTES) {
+        // Not reading; buffering for it would grow without bound
+        console.warn('[SELECTION-SERVER] Disconnecting a client that stopped reading');
+        this.clients.delete(client);
+        client.destroy();
+        return;
+      }
+      client.write(line);
+    });
+  }
+
+  private accept(socket: net.Socket): void {
+    this.connections.add(socket);
+    socket.on('error', (error) => {
+      console.warn('[SELECTION-SERVER] Client error:', error.message);
+    });
+    socket.on('close', () => {
+      this.connections.delete(socket);
+      this.clients.delete(socket);
+    });
+    if (!this.authorizer) {
+      socket.destroy();
+      return;
+    }
+    authenticateLineClient(socket, this.authorizer, () => {
+      this.clients.add(socket);
+      socket.on('data', () => {
+        // Receive-only protocol
+      });
+    });
+  }
+}
@@ -0,0 +1,211 @@
+/**
+ * Selection Store - persistent selection history in a local SQLite database
+ *
+ * Opt-in (historyStore.enabled): every delivered selection is written to disk
+ * with retention by row count and age, and can be queried by app, time range
+ * or full text. sqlite3 is only loaded once a store is opened, so apps that keep
+ * history in memory (or not at all) never load the native module.
+ *
+ * Events are stored as delivered, i.e. after redaction.
+ */
+
+import { app } from 'electron';
+import * as path from 'path';
+import type { Database } from 'sqlite3';
+import type { SelectionEvent } from './native-selection';
+
+export interface HistoryStoreOptions {
+  enabled: boolean;
+  /** Database file; empty = selection-history.sqlite in the app's userData directory */
+  path: string;
+  /** Oldest rows beyond this count are deleted (0 = no limit) */
+  maxRows: number;
+  /** Rows older than this are deleted (0 = keep forever) */
+  maxAgeMs: number;
+}
+
+export const DEFAULT_HISTORY_STORE_OPTIONS: HistoryStoreOptions = {
+  enabled: false,
+  path: '',
+  maxRows: 10000,
+  maxAgeMs: 30 * 24 * 60 * 60 * 1000,
+};
+
+export interface HistoryQuery {
+  /** Source process name, case-insensitive */
+  app?: string;
+  /** Inclusive time range, ms since epoch */
+  from?: number;
+  to?: number;
+  /** Words that must all occur in the text */
+  search?: string;
+  /** Most recent matches first, at most this many (default 100) */
+  limit?: number;
+}
+
+const SCHEMA = `
+  CREATE TABLE IF NOT EXISTS selections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp INTEGER NOT NULL,
+    app TEXT,
+    source TEXT NOT NULL,
+    text TEXT NOT NULL,
+    event TEXT NOT NULL
+  );
+  CREATE INDEX IF NOT EXISTS selections_timestamp ON selections (timestamp);
+  CREATE INDEX IF NOT EXISTS selections_app ON selections (app COLLATE NOCASE, timestamp);
+`;
+
+// External-content FTS5 index kept in sync by triggers. The trigram tokenizer
+// matches substrings, which also works for Chinese and Japanese (no spaces)
+const FULL_TEXT_SCHEMA = `
+  CREATE VIRTUAL TABLE IF NOT EXISTS selections_fts USING fts5(
+    text, content='selections', content_rowid='id', tokenize='trigram'
+  );
+  CREATE TRIGGER IF NOT EXISTS selections_fts_insert AFTER INSERT ON selections BEGIN
+    INSERT INTO selections_fts (rowid, text) VALUES (new.id, new.text);
+  END;
+  CREATE TRIGGER IF NOT EXISTS selections_fts_delete AFTER DELETE ON selections BEGIN
+    INSERT INTO selections_fts (selections_fts, rowid, text) VALUES ('delete', old.id, old.text);
+  END;
+`;
+
+export function defaultHistoryStorePath(): string {
+  return path.join(app.getPath('userData'), 'selection-history.sqlite');
+}
+
+export class SelectionStore {
+  private constructor(
+    private db: Database,
+    private options: HistoryStoreOptions,
+    private fullText: boolean
+  ) {}
+
+  /**
+   * Open (creating if needed) the database. Full-text search uses FTS5 when the
+   * bundled SQLite has it and falls back to substring matching otherwise.
+   */
+  static async open(options: HistoryStoreOptions): Promise<SelectionStore> {
+    const sqlite3 = await import('sqlite3');
+    const file = options.path || defaultHistoryStorePath();
+    const db = await new Promise<Database>((resolve, reject) => {
+      const opened = new sqlite3.Database(file, (error) => (error ? reject(error) : resolve(opened)));
+    });
+    // One statement at a time, in the order they were issued
+    db.serialize();
+    await exec(db, SCHEMA);
+    let fullText = true;
+    try {
+      await exec(db, FULL_TEXT_SCHEMA);
+    } catch (error) {
+      console.warn('[SELECTION-STORE] Full-text index unavailable, searching by substring:', error);
+      fullText = false;
+    }
+    const store = new SelectionStore(db, options, fullText);
+    await store.prune();
+    return store;
+  }
+
+  async add(event: SelectionEvent): Promise<void> {
+    await run(
+      this.db,
+      'INSERT INTO selections (timestamp, app, source, text, event) VALUES (?, ?, ?, ?, ?)',
+      [event.timestamp, event.app ?? null, event.source, event.text, JSON.stringify(event)]
+    );
+    await this.prune(event.timestamp);
+  }
+
+  /**
+   * Apply the retention policy; returns the number of rows deleted
+   */
+  async prune(now: number = Date.now()): Promise<number> {
+    let deleted = 0;
+    if (this.options.maxAgeMs > 0) {
+      deleted += await run(this.db, 'DELETE FROM selections WHERE timestamp < ?', [now - this.options.maxAgeMs]);
+    }
+    if (this.options.maxRows > 0) {
+      deleted += await run(
+        this.db,
+        'DELETE FROM selections WHERE id <= (SELECT id FROM selections ORDER BY id DESC LIMIT 1 OFFSET ?)',
+        [this.options.maxRows]
+      );
+    }
+    return deleted;
+  }
+
+  async query(query: HistoryQuery = {}): Promise<SelectionEvent[]> {
+    const where: string[] = [];
+    const params: unknown[] = [];
+    if (query.app) {
+      where.push('app = ? COLLATE NOCASE');
+      params.push(query.app);
+    }
+    if (query.from !== undefined) {
+      where.push('timestamp >= ?');
+      params.push(query.from);
+    }
+    if (query.to !== undefined) {
+      where.push('timestamp <= ?');
+      params.push(query.to);
+    }
+    const terms = query.search ? query.search.split(/\s+/).filter((term) => term.length > 0) : [];
+    // Trigrams cannot match terms shorter than three characters; those are scanned
+    const indexed = this.fullText ? terms.filter((term) => term.length >= 3) : [];
+    if (indexed.length > 0) {
+      // Each word as a quoted phrase, so FTS syntax in user input is taken literally
+      where.push('id IN (SELECT rowid FROM selections_fts WHERE selections_fts MATCH ?)');
+      params.push(indexed.map((term) => `"${term.replace(/"/g, '""')}"`).join(' '));
+    }
+    terms.filter((term) => !indexed.includes(term)).forEach((term) => {
+      where.push("text LIKE ? ESCAPE '\\'");
+      params.push(`%${term.replace(/[\\%_]/g, '\\$&')}%`);
+    });
+    const sql = `SELECT event FROM selections${where.length > 0 ? ` WHERE ${where.join(' AND ')}` : ''}` +
+      ' ORDER BY timestamp DESC, id DESC LIMIT ?';
+    params.push(query.limit ?? 100);
+    const rows = await all<{ event: string }>(this.db, sql, params);
+    return rows.map((row) => JSON.parse(row.event) as SelectionEvent);
+  }
+
+  byApp(appName: string, limit?: number): Promise<SelectionEvent[]> {
+    return this.query({ app: appName, limit });
+  }
+
+  byTimeRange(from: number, to: number, limit?: number): Promise<SelectionEvent[]> {
+    return this.query({ from, to, limit });
+  }
+
+  search(text: string, limit?: number): Promise<SelectionEvent[]> {
+    return this.query({ search: text, limit });
+  }
+
+  async clear(): Promise<void> {
+    await run(this.db, 'DELETE FROM selections', []);
+  }
+
+  close(): Promise<void> {
+    return new Promise((resolve, reject) => this.db.close((error) => (error ? reject(error) : resolve())));
+  }
+}
+
+function exec(db: Database, sql: string): Promise<void> {
+  return new Promise((resolve, reject) => db.exec(sql, (error) => (error ? reject(error) : resolve())));
+}
+
+function run(db: Database, sql: string, params: unknown[]): Promise<number> {
+  return new Promise((resolve, reject) => {
+    db.run(sql, params, function (this: { changes: number }, error: Error | null) {
+      if (error) {
+        reject(error);
+      } else {
+        resolve(this.changes);
+      }
+    });
+  });
+}
+
+function all<T>(db: Database, sql: string, params: unknown[]): Promise<T[]> {
+  return new Promise((resolve, reject) => {
+    db.all(sql, params, (error: Error | null, rows: T[]) => (error ? reject(error) : resolve(rows)));
+  });
+}
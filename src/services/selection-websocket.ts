@@ -0,0 +1,244 @@
+/**
+ * Selection WebSocket Server - stream selections to browser-based tooling
+ *
+ * A minimal RFC 6455 server (text frames out; ping and close handled) bound to
+ * loopback by default. Every delivered selection is sent to each client as one
+ * JSON message. Web pages can open WebSockets to localhost, so requests that
+ * carry an Origin header are refused unless the origin is explicitly allowed,
+ * and every client must present the token from local-auth.ts.
+ */
+
+import * as crypto from 'crypto';
+import * as http from 'http';
+import type { Duplex } from 'stream';
+import { URL } from 'url';
+import { ClientAuthOptions, ClientAuthorizer, DEFAULT_CLIENT_AUTH_OPTIONS } from './local-auth';
+import type { NativeSelectionService, SelectionEvent } from './native-selection';
+import { DEFAULT_REDACTION_OPTIONS, RedactionOptions, redactEvent } from './selection-filters';
+import { eventToJSON } from './selection-serialization';
+
+export interface WebSocketServerOptions {
+  host: string;
+  port: number;
+  /** Browser origins allowed to connect (clients without an Origin are always allowed) */
+  allowedOrigins: string[];
+  /** Applied before an event leaves the process */
+  redaction: RedactionOptions;
+  /** Which clients may connect: "Authorization: Bearer <token>" or ?token=<token> */
+  auth: ClientAuthOptions;
+}
+
+export const DEFAULT_WEBSOCKET_OPTIONS: WebSocketServerOptions = {
+  host: '127.0.0.1',
+  port: 8765,
+  allowedOrigins: [],
+  redaction: DEFAULT_REDACTION_OPTIONS,
+  auth: DEFAULT_CLIENT_AUTH_OPTIONS,
+};
+
+const HANDSHAKE_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11';
+
+const OPCODE_TEXT = 0x1;
+const OPCODE_CLOSE = 0x8;
+const OPCODE_PING = 0x9;
+const OPCODE_PONG = 0xa;
+
+// Clients only send control frames (at most 125 bytes) and ignored messages
+const MAX_CLIENT_FRAME_BYTES = 64 * 1024;
+const CLOSE_MESSAGE_TOO_BIG = 1009;
+
+function encodeFrame(opcode: number, payload: Buffer): Buffer {
+  let header: Buffer;
+  if (payload.length < 126) {
+    header = Buffer.from([0x80 | opcode, payload.length]);
+  } else if (payload.length < 65536) {
+    header = Buffer.alloc(4);
+    header[0] = 0x80 | opcode;
+    header[1] = 126;
+    header.writeUInt16BE(payload.length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[0] = 0x80 | opcode;
+    header[1] = 127;
+    // 64-bit length; payloads never reach 4 GiB, so the high word stays 0
+    header.writeUInt32BE(0, 2);
+    header.writeUInt32BE(payload.length, 6);
+  }
+  return Buffer.concat([header, payload]);
+}
+
+/**
+ * Split complete client frames off the buffer; returns the unconsumed rest, or
+ * null when a frame is larger than clients are allowed to send
+ */
+function readFrames(buffer: Buffer, onFrame: (opcode: number, payload: Buffer) => void): Buffer | null {
+  let offset = 0;
+  while (buffer.length - offset >= 2) {
+    const opcode = buffer[offset] & 0x0f;
+    const masked = (buffer[offset + 1] & 0x80) !== 0;
+    let length = buffer[offset + 1] & 0x7f;
+    let cursor = offset + 2;
+    if (length === 126) {
+      if (buffer.length < cursor + 2) break;
+      length = buffer.readUInt16BE(cursor);
+      cursor += 2;
+    } else if (length === 127) {
+      if (buffer.length < cursor + 8) break;
+      length = buffer.readUInt32BE(cursor) * 0x100000000 + buffer.readUInt32BE(cursor + 4);
+      cursor += 8;
+    }
+    if (length > MAX_CLIENT_FRAME_BYTES) {
+      return null;
+    }
+    const maskLength = masked ? 4 : 0;
+    if (buffer.length < cursor + maskLength + length) break;
+
+    const payload = Buffer.from(buffer.subarray(cursor + maskLength, cursor + maskLength + length));
+    if (masked) {
+      for (let i = 0; i < payload.length; i++) {
+        payload[i] ^= buffer[cursor + (i % 4)];
+      }
+    }
+    onFrame(opcode, payload);
+    offset = cursor + maskLength + length;
+  }
+  return buffer.subarray(offset);
+}
+
+// Browsers cannot set headers on a WebSocket, so the query string works too
+function requestToken(request: http.IncomingMessage): string | null {
+  const authorization = request.headers.authorization;
+  if (authorization && /^Bearer /i.test(authorization)) {
+    return authorization.slice(7).trim();
+  }
+  return new URL(request.url || '/', 'ws://localhost').searchParams.get('token');
+}
+
+export class SelectionWebSocketServer {
+  private server: http.Server | null = null;
+  private clients = new Set<Duplex>();
+  private authorizer: ClientAuthorizer | null = null;
+  private options: WebSocketServerOptions;
+
+  constructor(service: NativeSelectionService, options: Partial<WebSocketServerOptions> = {}) {
+    this.options = { ...DEFAULT_WEBSOCKET_OPTIONS, ...options };
+    service.onSelection((event) => this.broadcast(event));
+  }
+
+  listen(): Promise<void> {
+    if (this.server) {
+      return Promise.resolve();
+    }
+
+    this.authorizer = new ClientAuthorizer(this.options.auth);
+    const server = http.createServer((_request, response) => {
+      response.writeHead(426, { 'Content-Type': 'text/plain' });
+      response.end('WebSocket connections only');
+    });
+    server.on('upgrade', (request, socket) => this.upgrade(request, socket));
+    this.server = server;
+
+    return new Promise((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(this.options.port, this.options.host, () => {
+        server.off('error', reject);
+        server.on('error', (error) => console.error('[SELECTION-WS] Server error:', error));
+        console.log(`[SELECTION-WS] Listening on ws://${this.options.host}:${this.options.port}`);
+        resolve();
+      });
+    });
+  }
+
+  async close(): Promise<void> {
+    const server = this.server;
+    if (!server) {
+      return;
+    }
+    this.server = null;
+    this.clients.forEach((client) => {
+      client.end(encodeFrame(OPCODE_CLOSE, Buffer.alloc(0)));
+    });
+    this.clients.clear();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  }
+
+  /**
+   * Send one event to every connected client
+   */
+  broadcast(event: SelectionEvent): void {
+    if (!this.server || this.clients.size === 0) {
+      return;
+    }
+    const redacted = redactEvent(event, this.options.redaction);
+    if (!redacted) {
+      return;
+    }
+    const frame = encodeFrame(OPCODE_TEXT, Buffer.from(eventToJSON(redacted), 'utf8'));
+    this.clients.forEach((client) => {
+      if (!client.destroyed) {
+        client.write(frame);
+      }
+    });
+  }
+
+  private upgrade(request: http.IncomingMessage, socket: Duplex): void {
+    const key = request.headers['sec-websocket-key'];
+    const origin = request.headers.origin;
+    if (origin && !this.options.allowedOrigins.includes(origin)) {
+      console.warn(`[SELECTION-WS] Refused connection from origin ${origin}`);
+      socket.end('HTTP/1.1 403 Forbidden\r\n\r\n');
+      return;
+    }
+    if (typeof key !== 'string' || (request.headers.upgrade || '').toLowerCase() !== 'websocket') {
+      socket.end('HTTP/1.1 400 Bad Request\r\n\r\n');
+      return;
+    }
+    if (!this.authorizer || !this.authorizer.accepts(requestToken(request))) {
+      console.warn('[SELECTION-WS] Refused a client with a missing or wrong token');
+      socket.end('HTTP/1.1 401 Unauthorized\r\n\r\n');
+      return;
+    }
+
+    const accept = crypto.createHash('sha1').update(key + HANDSHAKE_GUID).digest('base64');
+    socket.write(
+      'HTTP/1.1 101 Switching Protocols\r\n' +
+      'Upgrade: websocket\r\n' +
+      'Connection: Upgrade\r\n' +
+      `Sec-WebSocket-Accept: ${accept}\r\n\r\n`
+    );
+    this.clients.add(socket);
+
+    // Holds at most one partial frame, since larger frames are refused
+    let pending = Buffer.alloc(0);
+    let refused = false;
+    socket.on('data', (chunk: Buffer) => {
+      if (refused) {
+        return;
+      }
+      const rest = readFrames(Buffer.concat([pending, chunk]), (opcode, payload) => {
+        if (opcode === OPCODE_PING) {
+          socket.write(encodeFrame(OPCODE_PONG, payload));
+        } else if (opcode === OPCODE_CLOSE) {
+          this.clients.delete(socket);
+          socket.end(encodeFrame(OPCODE_CLOSE, Buffer.alloc(0)));
+        }
+        // Clients only receive; text and binary messages are ignored
+      });
+      if (rest === null) {
+        console.warn('[SELECTION-WS] Client frame too large, closing connection');
+        refused = true;
+        this.clients.delete(socket);
+        const status = Buffer.alloc(2);
+        status.writeUInt16BE(CLOSE_MESSAGE_TOO_BIG, 0);
+        socket.end(encodeFrame(OPCODE_CLOSE, status), () => socket.destroy());
+        pending = Buffer.alloc(0);
+        return;
+      }
+      pending = rest;
+    });
+    socket.on('error', (error) => {
+      console.warn('[SELECTION-WS] Client error:', error.message);
+    });
+    socket.on('close', () => this.clients.delete(socket));
+  }
+}
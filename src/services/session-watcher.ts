@@ -0,0 +1,37 @@
+/**
+ * Session Watcher - lock and sleep notifications for selection services
+ *
+ * Electron's powerMonitor wraps the platform notifications (WTS session changes
+ * on Windows, NSWorkspace on macOS, logind on Linux). Backends are torn down
+ * while the session is locked or asleep and started again afterwards, so UI
+ * Automation handlers and hooks are re-registered instead of left stale.
+ */
+
+import { app, powerMonitor } from 'electron';
+
+export type SessionChange = 'lock' | 'unlock' | 'suspend' | 'resume';
+
+export interface SessionAware {
+  suspendForSession(change: SessionChange): Promise<void>;
+  resumeAfterSession(change: SessionChange): Promise<void>;
+}
+
+/**
+ * Forward lock/sleep notifications to the service once the app is ready
+ * (powerMonitor is unavailable before that)
+ */
+export function watchSession(target: SessionAware): void {
+  const forward = (change: SessionChange, handler: (change: SessionChange) => Promise<void>) => {
+    console.log(`[SESSION] Session ${change}`);
+    handler(change).catch((error) => {
+      console.error(`[SESSION] Failed to handle session ${change}:`, error);
+    });
+  };
+
+  void app.whenReady().then(() => {
+    powerMonitor.on('lock-screen', () => forward('lock', (change) => target.suspendForSession(change)));
+    powerMonitor.on('suspend', () => forward('suspend', (change) => target.suspendForSession(change)));
+    powerMonitor.on('unlock-screen', () => forward('unlock', (change) => target.resumeAfterSession(change)));
+    powerMonitor.on('resume', () => forward('resume', (change) => target.resumeAfterSession(change)));
+  });
+}
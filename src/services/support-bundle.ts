@@ -0,0 +1,99 @@
+/**
+ * Support Bundle - environment and selection diagnostics for bug reports
+ *
+ * Collects what maintainers otherwise have to ask for: OS and display setup,
+ * permission states, assistive technology, the selection backend's start report
+ * and its event counters. Selection text is never included, and the user's home
+ * directory and account name are masked in free-text fields.
+ */
+
+import { app, screen } from 'electron';
+import * as os from 'os';
+import { isUnixDesktop, NativeSelectionService, SelectionStatus, StartReport } from './native-selection';
+import type { MetricsSnapshot } from './selection-metrics';
+import * as permissions from './permissions';
+
+export interface SupportBundle {
+  generatedAt: string;
+  app: { version: string; electron: string; node: string };
+  os: { platform: string; release: string; arch: string; displayServer: string | null };
+  displays: { width: number; height: number; scaleFactor: number; primary: boolean }[];
+  /** A screen reader or other assistive technology has enabled Chromium accessibility */
+  assistiveTechnology: boolean;
+  permissions: permissions.PermissionStatus[];
+  selection: {
+    status: SelectionStatus;
+    startReport: StartReport | null;
+    metrics: MetricsSnapshot;
+  };
+}
+
+function maskPersonalData(text: string): string {
+  let masked = text.split(os.homedir()).join('~');
+  const username = os.userInfo().username;
+  if (username) {
+    masked = masked.split(username).join('<user>');
+  }
+  return masked;
+}
+
+function displayServer(): string | null {
+  if (!isUnixDesktop(process.platform)) {
+    return null;
+  }
+  if (process.env.WAYLAND_DISPLAY) {
+    return 'wayland';
+  }
+  return process.env.XDG_SESSION_TYPE || (process.env.DISPLAY ? 'x11' : null);
+}
+
+export function createSupportBundle(service: NativeSelectionService): SupportBundle {
+  const primaryId = screen.getPrimaryDisplay().id;
+  const status = service.getStatus();
+  const startReport = service.getStartReport();
+
+  return {
+    generatedAt: new Date().toISOString(),
+    app: { version: app.getVersion(), electron: process.versions.electron, node: process.versions.node },
+    os: { platform: process.platform, release: os.release(), arch: process.arch, displayServer: displayServer() },
+    displays: screen.getAllDisplays().map((display) => ({
+      width: display.size.width,
+      height: display.size.height,
+      scaleFactor: display.scaleFactor,
+      primary: display.id === primaryId,
+    })),
+    assistiveTechnology: app.accessibilitySupportEnabled,
+    permissions: permissions.check(),
+    selection: {
+      status: { ...status, lastError: status.lastError ? maskPersonalData(status.lastError) : null },
+      startReport: startReport && {
+        ...startReport,
+        failures: startReport.failures.map((failure) => ({ ...failure, reason: maskPersonalData(failure.reason) })),
+      },
+      metrics: service.metrics(),
+    },
+  };
+}
+
+/**
+ * Render a bundle as Markdown, ready to paste into an issue
+ */
+export function formatSupportBundle(bundle: SupportBundle): string {
+  return [
+    '### Phevere support bundle',
+    '',
+    `- Generated: ${bundle.generatedAt}`,
+    `- App: ${bundle.app.version} (Electron ${bundle.app.electron}, Node ${bundle.app.node})`,
+    `- OS: ${bundle.os.platform} ${bundle.os.release} ${bundle.os.arch}` +
+      (bundle.os.displayServer ? ` (${bundle.os.displayServer})` : ''),
+    `- Displays: ${bundle.displays.map((d) => `${d.width}x${d.height}@${d.scaleFactor}x${d.primary ? ' primary' : ''}`).join(', ')}`,
+    `- Assistive technology: ${bundle.assistiveTechnology ? 'active' : 'not detected'}`,
+    `- Permissions: ${bundle.permissions.map((p) => `${p.backend}=${p.state}`).join(', ') || 'n/a'}`,
+    `- Selection backend: ${bundle.selection.status.method}` +
+      (bundle.selection.status.lastError ? ` (last error: ${bundle.selection.status.lastError})` : ''),
+    '',
+    '```json',
+    JSON.stringify(bundle, null, 2),
+    '```',
+  ].join('\n');
+}
@@ -0,0 +1,163 @@
+/**
+ * Bidirectional Text - direction metadata for RTL and mixed selections
+ *
+ * Selections are delivered in logical (storage) order. Highlighters and
+ * tooltips that draw the text themselves need the paragraph's base direction
+ * and where the direction changes, or Hebrew and Arabic come out reversed and
+ * numbers jump around. This is a compact version of the Unicode Bidirectional
+ * Algorithm (UAX #9) for one paragraph without explicit embeddings: strong
+ * letters, European and Arabic numbers, and neutrals resolved from their
+ * neighbours. Explicit bidi controls are treated as neutrals.
+ */
+
+export type TextDirection = 'ltr' | 'rtl';
+
+export interface BidiRun {
+  /** UTF-16 offsets into the text, logical order */
+  start: number;
+  end: number;
+  /** Embedding level: even runs are laid out left-to-right, odd runs right-to-left */
+  level: number;
+  direction: TextDirection;
+}
+
+export interface BidiInfo {
+  /** Paragraph direction: reported by the app when available, else the first strong letter */
+  base: TextDirection;
+  /** Both directions occur */
+  mixed: boolean;
+  /** Directional runs in logical order */
+  runs: BidiRun[];
+  /** Indices into runs, left to right as displayed */
+  visualOrder: number[];
+}
+
+type BidiClass = 'L' | 'R' | 'AL' | 'EN' | 'AN' | 'N';
+
+// Hebrew, NKo, Samaritan, Mandaic and Hebrew presentation forms
+const HEBREW_LIKE = /[֐-׿߀-࡟יִ-ﭏ]/;
+// Arabic, Syriac, Thaana and Arabic presentation forms
+const ARABIC_LIKE = /[؀-ۿ܀-޿ࡠ-ࣿﭐ-﷿ﹰ-﻿]/;
+const LEFT_TO_RIGHT = /[A-Za-zªµºÀ-ÖØ-öø-ʸͰ-҂Ҋ-֏ऀ-῿Ⰰ-퟿豈-﬜]/;
+
+function classify(char: string): BidiClass {
+  if (char >= '0' && char <= '9') return 'EN';
+  const code = char.charCodeAt(0);
+  if (code >= 0x0660 && code <= 0x0669) return 'AN';
+  if (code >= 0x06f0 && code <= 0x06f9) return 'EN';
+  if (HEBREW_LIKE.test(char)) return 'R';
+  if (ARABIC_LIKE.test(char)) return 'AL';
+  if (LEFT_TO_RIGHT.test(char)) return 'L';
+  return 'N';
+}
+
+/**
+ * Direction of the first strong letter, or null when there is none
+ */
+export function firstStrongDirection(text: string): TextDirection | null {
+  for (const char of text) {
+    const type = classify(char);
+    if (type === 'L') return 'ltr';
+    if (type === 'R' || type === 'AL') return 'rtl';
+  }
+  return null;
+}
+
+/**
+ * True when the text contains right-to-left letters or Arabic digits
+ */
+export function hasRtl(text: string): boolean {
+  return HEBREW_LIKE.test(text) || ARABIC_LIKE.test(text);
+}
+
+/**
+ * Resolve direction runs and their display order for one paragraph of text
+ */
+export function analyzeBidi(text: string, base: TextDirection | null = null): BidiInfo {
+  const paragraph = base ?? firstStrongDirection(text) ?? 'ltr';
+  const baseLevel = paragraph === 'rtl' ? 1 : 0;
+  const types = Array.from({ length: text.length }, (_, index) => classify(text[index]));
+
+  // W2/W3/W7: European digits after Arabic letters are Arabic numbers, after
+  // left-to-right letters they are L; AL is R
+  let lastStrong: BidiClass = paragraph === 'rtl' ? 'R' : 'L';
+  for (let i = 0; i < types.length; i++) {
+    if (types[i] === 'EN' && lastStrong === 'AL') types[i] = 'AN';
+    if (types[i] === 'EN' && lastStrong === 'L') types[i] = 'L';
+    if (types[i] === 'L' || types[i] === 'R' || types[i] === 'AL') lastStrong = types[i];
+    if (types[i] === 'AL') types[i] = 'R';
+  }
+
+  // N1/N2: neutrals between the same direction take it (numbers count as R), others the base
+  const strongOf = (type: BidiClass): 'L' | 'R' | null =>
+    type === 'L' ? 'L' : type === 'R' || type === 'EN' || type === 'AN' ? 'R' : null;
+  for (let i = 0; i < types.length; i++) {
+    if (types[i] !== 'N') continue;
+    let end = i;
+    while (end < types.length && types[end] === 'N') end++;
+    const before = i > 0 ? strongOf(types[i - 1]) : (baseLevel ? 'R' : 'L');
+    const after = end < types.length ? strongOf(types[end]) : (baseLevel ? 'R' : 'L');
+    const resolved: BidiClass = before === after && before !== null ? before : (baseLevel ? 'R' : 'L');
+    types.fill(resolved, i, end);
+    i = end - 1;
+  }
+
+  // I1/I2: implicit levels
+  const levels = types.map((type) => {
+    if (baseLevel === 0) {
+      return type === 'R' ? 1 : type === 'EN' || type === 'AN' ? 2 : 0;
+    }
+    return type === 'L' || type === 'EN' || type === 'AN' ? 2 : 1;
+  });
+
+  const runs: BidiRun[] = [];
+  for (let i = 0; i < levels.length; i++) {
+    const last = runs[runs.length - 1];
+    if (last && last.level === levels[i]) {
+      last.end = i + 1;
+    } else {
+      runs.push({ start: i, end: i + 1, level: levels[i], direction: levels[i] % 2 ? 'rtl' : 'ltr' });
+    }
+  }
+
+  return {
+    base: paragraph,
+    mixed: runs.some((run) => run.direction === 'rtl') && runs.some((run) => run.direction === 'ltr'),
+    runs,
+    visualOrder: visualOrder(runs),
+  };
+}
+
+// L2: from the highest level down to the lowest odd one, reverse every
+// sequence of runs at that level or above
+function visualOrder(runs: BidiRun[]): number[] {
+  const order = runs.map((_, index) => index);
+  if (runs.length === 0) {
+    return order;
+  }
+  const highest = Math.max(...runs.map((run) => run.level));
+  const lowestOdd = Math.min(...runs.map((run) => (run.level % 2 ? run.level : run.level + 1)));
+  for (let level = highest; level >= lowestOdd; level--) {
+    for (let i = 0; i < order.length; i++) {
+      if (runs[order[i]].level < level) continue;
+      let end = i;
+      while (end < order.length && runs[order[end]].level >= level) end++;
+      const reversed = order.slice(i, end).reverse();
+      order.splice(i, reversed.length, ...reversed);
+      i = end;
+    }
+  }
+  return order;
+}
+
+/**
+ * The text in display order, left to right, for drawing it glyph by glyph
+ * where no bidi-aware renderer is available. Surrogate pairs stay intact.
+ */
+export function toVisualText(text: string, bidi: BidiInfo): string {
+  return bidi.visualOrder.map((index) => {
+    const run = bidi.runs[index];
+    const part = text.slice(run.start, run.end);
+    return run.direction === 'rtl' ? Array.from(part).reverse().join('') : part;
+  }).join('');
+}
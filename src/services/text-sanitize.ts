@@ -0,0 +1,60 @@
+/**
+ * Text Sanitization - clean up selection text before it is delivered
+ *
+ * Applied to every backend's events (and their ranges and context) ahead of the
+ * dispatcher, so dedupe compares cleaned text. Everything is off by default.
+ * Bidi control characters are left alone; they change how text renders.
+ */
+
+export type NormalizationForm = 'none' | 'NFC' | 'NFKC';
+
+export interface SanitizeOptions {
+  /** Unicode normalization form; NFKC also folds ligatures and full-width forms */
+  normalization: NormalizationForm;
+  /** Remove control characters (except tab and line breaks) and zero-width characters */
+  stripInvisible: boolean;
+  /**
+   * Undo line wrapping from PDFs and terminals: drop soft hyphens, join words
+   * hyphenated across a line break and turn single line breaks into spaces
+   * (blank lines still separate paragraphs)
+   */
+  unwrapLines: boolean;
+}
+
+export const DEFAULT_SANITIZE_OPTIONS: SanitizeOptions = {
+  normalization: 'none',
+  stripInvisible: false,
+  unwrapLines: false,
+};
+
+// C0 except \t \n \r, DEL, C1, and zero-width space/joiners/word joiner/BOM
+// eslint-disable-next-line no-control-regex
+const INVISIBLE = /[\u0000-\u0008\u000b\u000c\u000e-\u001f\u007f-\u009f\u200b-\u200d\u2060\ufeff]/g;
+
+export function isSanitizeEnabled(options: SanitizeOptions): boolean {
+  return options.normalization !== 'none' || options.stripInvisible || options.unwrapLines;
+}
+
+export function sanitizeText(text: string, options: SanitizeOptions): string {
+  let result = text;
+  if (options.normalization !== 'none') {
+    result = result.normalize(options.normalization);
+  }
+  if (options.stripInvisible) {
+    result = result.replace(INVISIBLE, '');
+  }
+  if (options.unwrapLines) {
+    result = unwrap(result);
+  }
+  return result;
+}
+
+function unwrap(text: string): string {
+  return text
+    .replace(/\u00ad/g, '')
+    // "exam-\nple" -> "example", only when the next line continues in lower case
+    .replace(/([a-zß-ÿ])-[ \t]*\r?\n[ \t]*(?=[a-zß-ÿ])/g, '$1')
+    .split(/(?:\r?\n[ \t]*){2,}/)
+    .map((paragraph) => paragraph.replace(/[ \t]*\r?\n[ \t]*/g, ' '))
+    .join('\n\n');
+}
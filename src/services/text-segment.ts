@@ -0,0 +1,218 @@
+/**
+ * Text Segmentation - locale-aware word, sentence and paragraph boundaries
+ *
+ * Uses Intl.Segmenter (ICU rules, available in Electron's Chromium/Node) and falls
+ * back to simple punctuation/whitespace rules where it is missing. Shared by the
+ * selection service and available to anything post-processing selected text.
+ */
+
+export type SegmentGranularity = 'grapheme' | 'word' | 'sentence';
+
+export interface TextSegment {
+  text: string;
+  /** UTF-16 offset of the first character */
+  start: number;
+  /** UTF-16 offset just past the last character */
+  end: number;
+  /** Word granularity only: false for whitespace and punctuation */
+  isWordLike?: boolean;
+}
+
+// Minimal typings: the project targets ES6, whose lib does not declare Intl.Segmenter
+interface IntlSegmentData {
+  segment: string;
+  index: number;
+  isWordLike?: boolean;
+}
+
+interface IntlSegmenter {
+  segment(input: string): Iterable<IntlSegmentData>;
+}
+
+type IntlSegmenterConstructor = new (
+  locale?: string,
+  options?: { granularity: SegmentGranularity }
+) => IntlSegmenter;
+
+const WORD_CHAR = /[\wÀ-ɏЀ-ӿ֐-׿؀-ۿ]/;
+const CJK_CHAR = /[一-鿿぀-ゟ゠-ヿ가-힯]/;
+const FALLBACK_WORD = /[\wÀ-ɏЀ-ӿ֐-׿؀-ۿ']+|[一-鿿぀-ゟ゠-ヿ가-힯]|\s+|[^\s]/g;
+const FALLBACK_SENTENCE = /[^.!?。！？]+(?:[.!?。！？]+["'”’)\]]*|$)\s*/g;
+
+function getSegmenter(): IntlSegmenterConstructor | null {
+  const ctor = (Intl as unknown as { Segmenter?: IntlSegmenterConstructor }).Segmenter;
+  return typeof ctor === 'function' ? ctor : null;
+}
+
+/**
+ * Split text into segments of the given granularity
+ */
+export function segment(text: string, granularity: SegmentGranularity, locale?: string): TextSegment[] {
+  if (!text) {
+    return [];
+  }
+
+  const Segmenter = getSegmenter();
+  if (Segmenter) {
+    const result: TextSegment[] = [];
+    for (const data of new Segmenter(locale, { granularity }).segment(text)) {
+      const item: TextSegment = { text: data.segment, start: data.index, end: data.index + data.segment.length };
+      if (granularity === 'word') {
+        item.isWordLike = !!data.isWordLike;
+      }
+      result.push(item);
+    }
+    return result;
+  }
+
+  return fallbackSegment(text, granularity);
+}
+
+export function words(text: string, locale?: string): TextSegment[] {
+  return segment(text, 'word', locale).filter((item) => item.isWordLike);
+}
+
+export function sentences(text: string, locale?: string): TextSegment[] {
+  return segment(text, 'sentence', locale);
+}
+
+/**
+ * Paragraphs are separated by one or more blank lines (or a lone line break
+ * when the text has no blank lines at all)
+ */
+export function paragraphs(text: string): TextSegment[] {
+  if (!text) {
+    return [];
+  }
+
+  const separator = /\r?\n\s*\r?\n/.test(text) ? /\r?\n\s*\r?\n/g : /\r?\n/g;
+  const result: TextSegment[] = [];
+  let start = 0;
+  let match: RegExpExecArray | null;
+  while ((match = separator.exec(text)) !== null) {
+    pushParagraph(result, text, start, match.index);
+    start = match.index + match[0].length;
+  }
+  pushParagraph(result, text, start, text.length);
+  return result;
+}
+
+/**
+ * Segment containing the given UTF-16 offset, if any
+ */
+export function segmentAt(segments: TextSegment[], offset: number): TextSegment | null {
+  return segments.find((item) => offset >= item.start && offset < item.end) || null;
+}
+
+/**
+ * Word list for languages written without spaces. ICU already segments
+ * Chinese and Japanese with its own dictionary; a custom one makes word units
+ * match a lookup tool's entries (e.g. the dictionary it is about to query).
+ */
+export interface WordDictionary {
+  has(word: string): boolean;
+  /** Length of the longest entry in UTF-16 code units; bounds the lookahead */
+  maxLength: number;
+}
+
+// Han and kana runs are re-segmented with a custom dictionary; Korean uses spaces
+const DICTIONARY_CHAR = /[一-鿿぀-ゟ゠-ヿ]/;
+
+/**
+ * The word at a UTF-16 offset, e.g. under the mouse pointer. An offset just
+ * past a word (pointer on its trailing edge) still finds it. Returns null over
+ * whitespace and punctuation.
+ */
+export function wordAt(text: string, offset: number, locale?: string, dictionary?: WordDictionary): TextSegment | null {
+  const segments = segment(text, 'word', locale);
+  let found = segmentAt(segments, offset);
+  if (!found || !found.isWordLike) {
+    const previous = segments.find((item) => item.end === offset);
+    found = previous && previous.isWordLike ? previous : null;
+  }
+  if (!found) {
+    return null;
+  }
+  if (!dictionary || !DICTIONARY_CHAR.test(text[Math.min(Math.max(offset, found.start), found.end - 1)])) {
+    return found;
+  }
+  return dictionaryWordAt(text, Math.min(offset, found.end - 1), dictionary);
+}
+
+/**
+ * Forward maximum matching over the Han/kana run containing offset: take the
+ * longest dictionary entry at each position (a single character when none
+ * matches) and return the token covering offset
+ */
+function dictionaryWordAt(text: string, offset: number, dictionary: WordDictionary): TextSegment {
+  let runStart = offset;
+  let runEnd = offset + 1;
+  while (runStart > 0 && DICTIONARY_CHAR.test(text[runStart - 1])) runStart--;
+  while (runEnd < text.length && DICTIONARY_CHAR.test(text[runEnd])) runEnd++;
+
+  let position = runStart;
+  for (;;) {
+    let length = Math.min(dictionary.maxLength, runEnd - position);
+    while (length > 1 && !dictionary.has(text.slice(position, position + length))) {
+      length--;
+    }
+    const end = position + Math.max(length, 1);
+    if (offset < end) {
+      return { text: text.slice(position, end), start: position, end, isWordLike: true };
+    }
+    position = end;
+  }
+}
+
+export type EnclosingUnit = 'word' | 'sentence' | 'paragraph';
+
+/**
+ * Smallest run of whole units covering text[start, end), e.g. to widen a
+ * partial word to the word or a phrase to its sentence. Surrounding whitespace
+ * is not included. An empty range expands to the unit containing start.
+ */
+export function enclosingSegment(text: string, start: number, end: number, unit: EnclosingUnit, locale?: string): TextSegment {
+  const units = unit === 'paragraph' ? paragraphs(text) : unit === 'word' ? words(text, locale) : sentences(text, locale);
+  const covering = units.filter((item) => (start === end
+    ? start >= item.start && start < item.end
+    : item.start < end && item.end > start));
+  let from = covering.length > 0 ? Math.min(start, covering[0].start) : start;
+  let to = covering.length > 0 ? Math.max(end, covering[covering.length - 1].end) : end;
+  while (from < to && /\s/.test(text[from]) && from < start) from++;
+  while (to > from && /\s/.test(text[to - 1]) && to > end) to--;
+  return { text: text.slice(from, to), start: from, end: to };
+}
+
+function pushParagraph(result: TextSegment[], text: string, start: number, end: number): void {
+  if (text.slice(start, end).trim().length > 0) {
+    result.push({ text: text.slice(start, end), start, end });
+  }
+}
+
+function fallbackSegment(text: string, granularity: SegmentGranularity): TextSegment[] {
+  if (granularity === 'grapheme') {
+    const result: TextSegment[] = [];
+    let index = 0;
+    for (const char of Array.from(text)) {
+      result.push({ text: char, start: index, end: index + char.length });
+      index += char.length;
+    }
+    return result;
+  }
+
+  const pattern = new RegExp(granularity === 'word' ? FALLBACK_WORD : FALLBACK_SENTENCE);
+  const result: TextSegment[] = [];
+  let match: RegExpExecArray | null;
+  while ((match = pattern.exec(text)) !== null) {
+    if (match[0].length === 0) {
+      pattern.lastIndex++;
+      continue;
+    }
+    const item: TextSegment = { text: match[0], start: match.index, end: match.index + match[0].length };
+    if (granularity === 'word') {
+      item.isWordLike = WORD_CHAR.test(match[0]) || CJK_CHAR.test(match[0]);
+    }
+    result.push(item);
+  }
+  return result;
+}
@@ -0,0 +1,85 @@
+import { test } from 'node:test';
+import * as assert from 'node:assert/strict';
+
+import { ManualClock } from '../src/services/clock';
+import {
+  DEFAULT_LISTENER_CONFIG,
+  ListenerConfig,
+  MockNativeSelectionService,
+  SelectionEvent,
+} from '../src/services/native-selection';
+import { DEFAULT_REDACTION_OPTIONS, REDACTION_MASK } from '../src/services/selection-filters';
+
+function createService(overrides: Partial<ListenerConfig> = {}): {
+  service: MockNativeSelectionService;
+  clock: ManualClock;
+  delivered: SelectionEvent[];
+} {
+  const clock = new ManualClock(0);
+  const service = new MockNativeSelectionService({
+    ...DEFAULT_LISTENER_CONFIG,
+    debounceMs: 100,
+    dedupeWindowMs: 1000,
+    clock,
+    ...overrides,
+  });
+  const delivered: SelectionEvent[] = [];
+  service.onSelection((event) => delivered.push(event));
+  return { service, clock, delivered };
+}
+
+test('mock service reports itself as a supported backend', async () => {
+  const { service } = createService();
+  assert.equal(service.isSupported(), true);
+
+  const report = await service.start();
+  assert.equal(report.backend, 'mock');
+  assert.deepEqual(report.failures, []);
+  assert.equal(service.getStatus().isRunning, true);
+  assert.equal(service.getStatus().method, 'mock');
+});
+
+test('injected selections are debounced and deduplicated', async () => {
+  const { service, clock, delivered } = createService();
+  await service.start();
+
+  service.injectSelection({ text: 'wor' });
+  clock.advance(50);
+  service.injectSelection({ text: 'word' });
+  clock.advance(100);
+  service.injectSelection({ text: 'word' });
+  clock.advance(100);
+
+  assert.deepEqual(delivered.map((event) => event.text), ['word']);
+  const metrics = service.metrics();
+  assert.equal(metrics.received, 3);
+  assert.equal(metrics.delivered, 1);
+  assert.equal(metrics.dropped.superseded, 1);
+  assert.equal(metrics.dropped.duplicate, 1);
+});
+
+test('card numbers are masked even when hidden by zero-width characters', async () => {
+  const { service, clock, delivered } = createService({
+    redaction: { ...DEFAULT_REDACTION_OPTIONS, enabled: true },
+    sanitize: { ...DEFAULT_LISTENER_CONFIG.sanitize, stripInvisible: true },
+  });
+  await service.start();
+
+  service.injectSelection({ text: 'card 4111\u200b1111\u200b1111\u200b1111' });
+  clock.advance(100);
+
+  assert.deepEqual(delivered.map((event) => event.text), [`card ${REDACTION_MASK}`]);
+});
+
+test('redaction with the drop action counts the selection as redacted', async () => {
+  const { service, clock, delivered } = createService({
+    redaction: { ...DEFAULT_REDACTION_OPTIONS, enabled: true, action: 'drop' },
+  });
+  await service.start();
+
+  service.injectSelection({ text: '4111 1111 1111 1111' });
+  clock.advance(100);
+
+  assert.deepEqual(delivered, []);
+  assert.equal(service.metrics().dropped.redacted, 1);
+});
@@ -0,0 +1,75 @@
+import { test } from 'node:test';
+import * as assert from 'node:assert/strict';
+
+import { ManualClock } from '../src/services/clock';
+import { SelectionDispatcher } from '../src/services/selection-dispatcher';
+import { SelectionMetrics } from '../src/services/selection-metrics';
+import type { SelectionEvent } from '../src/services/native-selection';
+
+function selection(text: string, timestamp: number): SelectionEvent {
+  return { text, x: 0, y: 0, timestamp, source: 'native' };
+}
+
+test('debounce delivers only the settled selection', () => {
+  const clock = new ManualClock(1000);
+  const dispatcher = new SelectionDispatcher({ debounceMs: 200, dedupeWindowMs: 0 }, clock);
+  const delivered: string[] = [];
+  const dropped: string[] = [];
+  dispatcher.onEvent((event) => delivered.push(event.text));
+  dispatcher.onDrop((event, reason) => dropped.push(`${event.text}:${reason}`));
+
+  dispatcher.push(selection('he', clock.now()));
+  clock.advance(100);
+  dispatcher.push(selection('hello', clock.now()));
+  clock.advance(199);
+  assert.deepEqual(delivered, []);
+
+  clock.advance(1);
+  assert.deepEqual(delivered, ['hello']);
+  assert.deepEqual(dropped, ['he:superseded']);
+  assert.equal(clock.pendingTimers(), 0);
+});
+
+test('dedupe suppresses repeats only inside the window', () => {
+  const clock = new ManualClock(0);
+  const dispatcher = new SelectionDispatcher({ debounceMs: 50, dedupeWindowMs: 1000 }, clock);
+  const delivered: number[] = [];
+  const dropped: string[] = [];
+  dispatcher.onEvent((event) => delivered.push(event.timestamp));
+  dispatcher.onDrop((_event, reason) => dropped.push(reason));
+
+  dispatcher.push(selection('same', clock.now()));
+  clock.advance(50);
+  dispatcher.push(selection('same', clock.now()));
+  clock.advance(50);
+  assert.deepEqual(delivered, [0]);
+  assert.deepEqual(dropped, ['duplicate']);
+
+  clock.advance(1000);
+  dispatcher.push(selection('same', clock.now()));
+  clock.advance(50);
+  assert.deepEqual(delivered, [0, 1100]);
+});
+
+test('metrics measure latency on the injected clock', () => {
+  const clock = new ManualClock(0);
+  const dispatcher = new SelectionDispatcher({ debounceMs: 200, dedupeWindowMs: 0 }, clock);
+  const metrics = new SelectionMetrics(clock);
+  dispatcher.onEvent((event) => metrics.recordDelivered(event.timestamp));
+  dispatcher.onDrop((_event, reason) => metrics.recordDropped(reason));
+
+  metrics.recordReceived();
+  dispatcher.push(selection('a', clock.now()));
+  clock.advance(30);
+  metrics.recordReceived();
+  dispatcher.push(selection('ab', clock.now()));
+  clock.advance(200);
+
+  const snapshot = metrics.snapshot();
+  assert.equal(snapshot.received, 2);
+  assert.equal(snapshot.delivered, 1);
+  assert.equal(snapshot.dropped.superseded, 1);
+  assert.equal(snapshot.latency.sumMs, 200);
+  assert.equal(snapshot.latency.maxMs, 200);
+  assert.equal(snapshot.latency.buckets.find((bucket) => bucket.le === 250)?.count, 1);
+});